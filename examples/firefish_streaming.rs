@@ -51,6 +51,9 @@ async fn streaming(url: &str, access_token: String) {
                         Message::Heartbeat() => {
                             println!("heartbeat");
                         }
+                        other => {
+                            println!("{:#?}", other);
+                        }
                     }
                 }
             })
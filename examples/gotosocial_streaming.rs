@@ -44,6 +44,9 @@ async fn streaming(url: &str, access_token: String) {
                         Message::Heartbeat() => {
                             println!("heartbeat");
                         }
+                        other => {
+                            println!("{:#?}", other);
+                        }
                     }
                 }
             })
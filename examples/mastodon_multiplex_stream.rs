@@ -0,0 +1,49 @@
+use futures_util::StreamExt;
+use megalodon::{
+    generator,
+    streaming::{StreamEvent, Subscription},
+};
+use std::env;
+
+#[tokio::main]
+async fn main() {
+    tracing_subscriber::fmt::init();
+
+    let Ok(url) = env::var("MASTODON_URL") else {
+        println!("Specify MASTODON_URL!!");
+        return;
+    };
+    let Ok(token) = env::var("MASTODON_ACCESS_TOKEN") else {
+        println!("Specify MASTODON_ACCESS_TOKEN!!");
+        return;
+    };
+
+    streaming(url.as_str(), token).await;
+}
+
+async fn streaming(url: &str, access_token: String) {
+    let client = generator(
+        megalodon::SNS::Mastodon,
+        url.to_string(),
+        Some(access_token),
+        None,
+    )
+    .unwrap();
+    let streaming = client.multiplex_streaming().await.unwrap();
+    let (mut events, handle) = streaming.stream(vec![
+        Subscription::User,
+        Subscription::Hashtag(String::from("rust")),
+    ]);
+
+    while let Some(event) = events.next().await {
+        match event {
+            StreamEvent::Connected => {
+                println!("connected");
+                handle.subscribe(Subscription::PublicLocal);
+            }
+            StreamEvent::Reconnecting { attempt } => println!("reconnecting: {}", attempt),
+            StreamEvent::Disconnected { reason } => println!("disconnected: {}", reason),
+            StreamEvent::Message(tagged) => println!("{:?}: {:#?}", tagged.stream, tagged.message),
+        }
+    }
+}
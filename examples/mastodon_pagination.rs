@@ -0,0 +1,63 @@
+use futures_util::{pin_mut, StreamExt};
+use megalodon::{
+    generator,
+    megalodon::GetHomeTimelineInputOptions,
+    pagination::{paginate, PaginateOptions},
+};
+use std::env;
+
+#[tokio::main]
+async fn main() {
+    tracing_subscriber::fmt::init();
+
+    let Ok(url) = env::var("MASTODON_URL") else {
+        println!("Specify MASTODON_URL!!");
+        return;
+    };
+    let Ok(token) = env::var("MASTODON_ACCESS_TOKEN") else {
+        println!("Specify MASTODON_ACCESS_TOKEN!!");
+        return;
+    };
+
+    home_timeline(url.as_str(), token).await;
+}
+
+async fn home_timeline(url: &str, access_token: String) {
+    let client = generator(
+        megalodon::SNS::Mastodon,
+        url.to_string(),
+        Some(access_token),
+        None,
+    )
+    .unwrap();
+
+    let options = PaginateOptions {
+        limit: Some(40),
+        max_items: Some(100),
+        ..Default::default()
+    };
+    let statuses = paginate(options, |page| {
+        let client = &client;
+        async move {
+            let options = GetHomeTimelineInputOptions {
+                limit: page.limit,
+                max_id: page.max_id,
+                min_id: page.min_id,
+                since_id: page.since_id,
+                ..Default::default()
+            };
+            client.get_home_timeline(Some(&options)).await
+        }
+    });
+    pin_mut!(statuses);
+
+    while let Some(status) = statuses.next().await {
+        match status {
+            Ok(status) => println!("{}: {}", status.id, status.content),
+            Err(err) => {
+                println!("{:#?}", err);
+                return;
+            }
+        }
+    }
+}
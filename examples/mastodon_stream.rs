@@ -0,0 +1,64 @@
+use futures_util::StreamExt;
+use megalodon::{
+    generator,
+    streaming::{ReconnectPolicy, StreamEvent, Transport},
+};
+use std::env;
+use std::time::Duration;
+
+#[tokio::main]
+async fn main() {
+    tracing_subscriber::fmt::init();
+
+    let Ok(url) = env::var("MASTODON_URL") else {
+        println!("Specify MASTODON_URL!!");
+        return;
+    };
+    let Ok(token) = env::var("MASTODON_ACCESS_TOKEN") else {
+        println!("Specify MASTODON_ACCESS_TOKEN!!");
+        return;
+    };
+
+    streaming(url.as_str(), token).await;
+}
+
+async fn streaming(url: &str, access_token: String) {
+    let client = generator(
+        megalodon::SNS::Mastodon,
+        url.to_string(),
+        Some(access_token),
+        None,
+    )
+    .unwrap();
+    let mut streaming = client.user_streaming().await;
+    streaming.set_reconnect_policy(ReconnectPolicy {
+        max_attempts: Some(10),
+        ..ReconnectPolicy::exponential(Duration::from_secs(1), Duration::from_secs(60))
+    });
+    streaming.set_transport(Transport::Auto);
+    let (mut events, handle) = streaming.stream();
+
+    let timeout = tokio::time::sleep(Duration::from_secs(60));
+    tokio::pin!(timeout);
+
+    loop {
+        tokio::select! {
+            _ = &mut timeout, if !handle.is_closed() => {
+                handle.close();
+            }
+            event = events.next() => {
+                match event {
+                    Some(StreamEvent::Connected) => println!("connected"),
+                    Some(StreamEvent::Reconnecting { attempt }) => {
+                        println!("reconnecting: {}", attempt)
+                    }
+                    Some(StreamEvent::Disconnected { reason }) => {
+                        println!("disconnected: {}", reason)
+                    }
+                    Some(StreamEvent::Message(message)) => println!("{:#?}", message),
+                    None => return,
+                }
+            }
+        }
+    }
+}
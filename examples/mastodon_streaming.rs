@@ -50,6 +50,9 @@ async fn streaming(url: &str, access_token: String) {
                         Message::Heartbeat() => {
                             println!("heartbeat");
                         }
+                        other => {
+                            println!("{:#?}", other);
+                        }
                     }
                 }
             })
@@ -40,6 +40,9 @@ async fn streaming(url: &str) {
                         Message::Heartbeat() => {
                             println!("heartbeat");
                         }
+                        other => {
+                            println!("{:#?}", other);
+                        }
                     }
                 }
             })
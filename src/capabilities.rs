@@ -0,0 +1,347 @@
+//! Capabilities modules, which tell what the server supports
+use std::collections::HashSet;
+
+use crate::entities;
+use crate::error::Error;
+use crate::response::Response;
+use crate::SNS;
+use tracing::warn;
+
+/// Features which are not supported by every SNS.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Feature {
+    /// Bookmark statuses.
+    Bookmarks,
+    /// Schedule statuses to publish later.
+    ScheduledStatuses,
+    /// Edit posted statuses.
+    EditStatus,
+    /// Vote polls.
+    Polls,
+    /// Quote statuses.
+    QuotePosts,
+    /// React to statuses with emojis.
+    EmojiReactions,
+    /// Web Push subscriptions.
+    PushSubscriptions,
+    /// Lists of accounts.
+    Lists,
+    /// Filters v1 API.
+    Filters,
+    /// Filters v2 API, which groups keywords and statuses.
+    FiltersV2,
+    /// Direct conversations.
+    Conversations,
+    /// Read positions of timelines.
+    Markers,
+    /// Announcements of the server.
+    Announcements,
+    /// Block domains.
+    DomainBlocks,
+    /// Follow hashtags.
+    FollowTags,
+    /// Feature hashtags on the profile.
+    FeaturedTags,
+    /// Suggestions of accounts to follow.
+    Suggestions,
+    /// Trending hashtags.
+    Trends,
+    /// Directory of accounts.
+    Directory,
+    /// Report accounts.
+    Reports,
+    /// Notifications grouped by the server.
+    GroupedNotifications,
+    /// Notification requests and the notification filtering policy.
+    NotificationRequests,
+    /// Moderation with admin API.
+    Admin,
+    /// Translate statuses.
+    Translation,
+}
+
+/// How the streaming is provided.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum StreamingKind {
+    /// Streaming over WebSocket.
+    WebSocket,
+    /// Streaming over Server-Sent Events.
+    ServerSentEvents,
+    /// Multiple streams over a single connection.
+    Multiplex,
+    /// Emulated streaming by polling timelines.
+    Polling,
+}
+
+/// Capabilities of the server.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Capabilities {
+    /// Which SNS.
+    pub sns: SNS,
+    /// Version of the server, if it is known.
+    pub version: Option<String>,
+    /// Supported features.
+    pub features: HashSet<Feature>,
+    /// Supported streaming.
+    pub streaming: HashSet<StreamingKind>,
+    /// Maximum number of characters in a status.
+    pub max_characters: Option<u32>,
+    /// Maximum number of media attachments in a status.
+    pub max_media_attachments: Option<u32>,
+}
+
+impl Capabilities {
+    /// Capabilities which are known from the SNS, without asking the server.
+    pub fn from_sns(sns: SNS) -> Capabilities {
+        use Feature::*;
+
+        let features: &[Feature] = match sns {
+            SNS::Mastodon => &[
+                Bookmarks,
+                ScheduledStatuses,
+                Polls,
+                PushSubscriptions,
+                Lists,
+                Filters,
+                Conversations,
+                Markers,
+                Announcements,
+                DomainBlocks,
+                FollowTags,
+                FeaturedTags,
+                Suggestions,
+                Trends,
+                Directory,
+                Reports,
+                Admin,
+            ],
+            SNS::Pleroma => &[
+                Bookmarks,
+                ScheduledStatuses,
+                EditStatus,
+                Polls,
+                QuotePosts,
+                EmojiReactions,
+                PushSubscriptions,
+                Lists,
+                Filters,
+                Conversations,
+                Markers,
+                Announcements,
+                DomainBlocks,
+                FeaturedTags,
+                Suggestions,
+                Trends,
+                Directory,
+                Reports,
+                Admin,
+                Translation,
+            ],
+            SNS::Gotosocial => &[
+                Bookmarks, EditStatus, Polls, Lists, Filters, Markers, Reports, Admin,
+            ],
+            SNS::Friendica => &[
+                Bookmarks,
+                EditStatus,
+                PushSubscriptions,
+                Lists,
+                Conversations,
+                Markers,
+                Announcements,
+                Suggestions,
+                Trends,
+                Directory,
+            ],
+            SNS::Pixelfed => &[
+                Bookmarks,
+                ScheduledStatuses,
+                EditStatus,
+                Polls,
+                Filters,
+                Conversations,
+                Markers,
+                Announcements,
+                DomainBlocks,
+                FollowTags,
+                Trends,
+                Reports,
+            ],
+            SNS::Firefish => &[
+                EditStatus,
+                Polls,
+                QuotePosts,
+                EmojiReactions,
+                Lists,
+                Conversations,
+                Announcements,
+                Suggestions,
+                Trends,
+                Reports,
+                Translation,
+            ],
+        };
+        let streaming: &[StreamingKind] = match sns {
+            SNS::Mastodon => &[
+                StreamingKind::WebSocket,
+                StreamingKind::ServerSentEvents,
+                StreamingKind::Multiplex,
+            ],
+            SNS::Pleroma => &[
+                StreamingKind::WebSocket,
+                StreamingKind::ServerSentEvents,
+                StreamingKind::Multiplex,
+            ],
+            SNS::Gotosocial => &[StreamingKind::WebSocket, StreamingKind::Multiplex],
+            SNS::Firefish => &[StreamingKind::WebSocket],
+            SNS::Friendica | SNS::Pixelfed => &[StreamingKind::Polling],
+        };
+        Capabilities {
+            sns,
+            version: None,
+            features: features.iter().copied().collect(),
+            streaming: streaming.iter().copied().collect(),
+            max_characters: None,
+            max_media_attachments: None,
+        }
+    }
+
+    /// Refine the capabilities with the version and the configuration of the server.
+    pub fn with_instance(mut self, instance: &entities::InstanceV2) -> Capabilities {
+        if self.sns == SNS::Mastodon {
+            if let Some(version) = parse_version(&instance.version) {
+                if version >= (3, 5, 0) {
+                    self.features.insert(Feature::EditStatus);
+                }
+                if version >= (4, 0, 0) {
+                    self.features.insert(Feature::FiltersV2);
+                }
+                if version >= (4, 3, 0) {
+                    self.features.insert(Feature::GroupedNotifications);
+                    self.features.insert(Feature::NotificationRequests);
+                }
+                if version >= (4, 5, 0) {
+                    self.features.insert(Feature::QuotePosts);
+                }
+            }
+            // Translation depends on whether the admin configured a translation service.
+            if instance.configuration.translation.enabled {
+                self.features.insert(Feature::Translation);
+            }
+        }
+        self.version = Some(instance.version.clone());
+        self.max_characters = Some(instance.configuration.statuses.max_characters);
+        self.max_media_attachments = instance.configuration.statuses.max_media_attachments;
+        self
+    }
+
+    /// Refine the capabilities with the instance if it was fetched.
+    /// The known capabilities of the SNS are kept when the instance is not available.
+    pub(crate) fn detect(
+        sns: SNS,
+        instance: Result<Response<entities::InstanceV2>, Error>,
+    ) -> Capabilities {
+        let capabilities = Capabilities::from_sns(sns);
+        match instance {
+            Ok(instance) => capabilities.with_instance(&instance.json),
+            Err(err) => {
+                warn!("Failed to get instance for capabilities: {}", err);
+                capabilities
+            }
+        }
+    }
+
+    /// Whether the feature is supported.
+    pub fn supports(&self, feature: Feature) -> bool {
+        self.features.contains(&feature)
+    }
+
+    /// Whether the kind of streaming is supported.
+    pub fn supports_streaming(&self, kind: StreamingKind) -> bool {
+        self.streaming.contains(&kind)
+    }
+}
+
+/// Parse the leading `major.minor.patch` of the version, like `4.2.1+glitch`.
+fn parse_version(version: &str) -> Option<(u32, u32, u32)> {
+    let mut numbers = version.split('.').map(|part| {
+        let digits: String = part.chars().take_while(|c| c.is_ascii_digit()).collect();
+        digits.parse::<u32>().ok()
+    });
+    let major = numbers.next()??;
+    let minor = numbers.next().flatten().unwrap_or(0);
+    let patch = numbers.next().flatten().unwrap_or(0);
+    Some((major, minor, patch))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_version() {
+        assert_eq!(parse_version("4.2.1"), Some((4, 2, 1)));
+        assert_eq!(parse_version("4.3.0-beta.1+glitch"), Some((4, 3, 0)));
+        assert_eq!(parse_version("3.5"), Some((3, 5, 0)));
+        assert_eq!(parse_version("unknown"), None);
+    }
+
+    #[test]
+    fn test_from_sns() {
+        let capabilities = Capabilities::from_sns(SNS::Firefish);
+        assert!(capabilities.supports(Feature::EmojiReactions));
+        assert!(!capabilities.supports(Feature::Bookmarks));
+        assert!(capabilities.supports_streaming(StreamingKind::WebSocket));
+
+        let capabilities = Capabilities::from_sns(SNS::Friendica);
+        assert!(!capabilities.supports(Feature::ScheduledStatuses));
+        assert!(capabilities.supports_streaming(StreamingKind::Polling));
+        assert!(!capabilities.supports_streaming(StreamingKind::WebSocket));
+    }
+
+    #[test]
+    fn test_detect_without_instance() {
+        let err = Error::new_own(
+            String::from("unavailable"),
+            crate::error::Kind::HTTPStatusError,
+            None,
+            Some(503),
+            None,
+        );
+        let capabilities = Capabilities::detect(SNS::Mastodon, Err(err));
+        assert_eq!(capabilities, Capabilities::from_sns(SNS::Mastodon));
+        assert!(capabilities.version.is_none());
+    }
+
+    #[test]
+    fn test_with_instance_translation() {
+        let mut instance: entities::InstanceV2 = serde_json::from_value(serde_json::json!({
+            "domain": "mastodon.example",
+            "title": "Mastodon",
+            "version": "4.2.1",
+            "source_url": null,
+            "description": "",
+            "thumbnail": null,
+            "languages": [],
+            "configuration": {
+                "urls": {"streaming": null},
+                "statuses": {"max_characters": 500, "max_media_attachments": 4},
+                "media_attachments": null,
+                "polls": null,
+                "translation": {"enabled": false}
+            },
+            "registrations": {"enabled": false, "approval_required": false, "message": null, "url": null},
+            "api_versions": {},
+            "contact": {"email": "", "account": null},
+            "rules": []
+        }))
+        .unwrap();
+        let capabilities = Capabilities::from_sns(SNS::Mastodon).with_instance(&instance);
+        assert!(capabilities.supports(Feature::FiltersV2));
+        assert!(!capabilities.supports(Feature::Translation));
+        assert_eq!(capabilities.max_characters, Some(500));
+
+        instance.configuration.translation.enabled = true;
+        let capabilities = Capabilities::from_sns(SNS::Mastodon).with_instance(&instance);
+        assert!(capabilities.supports(Feature::Translation));
+    }
+}
@@ -1,5 +1,6 @@
 use serde::Deserialize;
 
+use crate::http::ClientBuilder;
 use crate::{SNS, error};
 
 #[derive(Deserialize, Debug)]
@@ -52,10 +53,14 @@ struct Upstream {
 
 /// Detect which SNS the provided URL is. To detect SNS, the URL has to open `/api/v1/instance` or `/api/meta` endpoint.
 pub async fn detector(url: &str) -> Result<SNS, error::Error> {
-    let client = reqwest::Client::builder().user_agent("megalodon").build()?;
+    detector_with_client(url, &ClientBuilder::default()).await
+}
+
+/// Detect which SNS the provided URL is with the HTTP client settings, which are the same as [`crate::generator_with_client`].
+pub async fn detector_with_client(url: &str, client: &ClientBuilder) -> Result<SNS, error::Error> {
+    let client = client.build(Some(String::from("megalodon")))?;
     let links = client
-        .get(format!("{}{}", url, "/.well-known/nodeinfo"))
-        .send()
+        .send(client.get(format!("{}{}", url, "/.well-known/nodeinfo")))
         .await?
         .json::<Links>()
         .await?;
@@ -76,8 +81,7 @@ pub async fn detector(url: &str) -> Result<SNS, error::Error> {
     match link.rel.as_str() {
         NODEINFO_10 => {
             let nodeinfo = client
-                .get(link.href.as_str())
-                .send()
+                .send(client.get(link.href.as_str()))
                 .await?
                 .json::<Nodeinfo10>()
                 .await?;
@@ -109,8 +113,7 @@ pub async fn detector(url: &str) -> Result<SNS, error::Error> {
         }
         NODEINFO_20 => {
             let nodeinfo = client
-                .get(link.href.as_str())
-                .send()
+                .send(client.get(link.href.as_str()))
                 .await?
                 .json::<Nodeinfo20>()
                 .await?;
@@ -142,8 +145,7 @@ pub async fn detector(url: &str) -> Result<SNS, error::Error> {
         }
         NODEINFO_21 => {
             let nodeinfo = client
-                .get(link.href.as_str())
-                .send()
+                .send(client.get(link.href.as_str()))
                 .await?
                 .json::<Nodeinfo21>()
                 .await?;
@@ -258,4 +260,53 @@ mod tests {
         assert!(sns.is_ok());
         assert_eq!(sns.unwrap(), SNS::Pixelfed);
     }
+
+    #[tokio::test]
+    async fn test_detector_with_client() {
+        use reqwest::header::{HeaderMap, HeaderValue};
+        use tokio::io::{AsyncReadExt, AsyncWriteExt};
+        use tokio::net::TcpListener;
+
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        let nodeinfo_url = format!("{}/nodeinfo/2.0", url);
+        let server = tokio::spawn(async move {
+            let mut requests = Vec::new();
+            for _ in 0..2 {
+                let (mut tcp, _) = listener.accept().await.unwrap();
+                let mut request = Vec::new();
+                let mut buf = [0u8; 1024];
+                while !request.ends_with(b"\r\n\r\n") {
+                    let n = tcp.read(&mut buf).await.unwrap();
+                    request.extend_from_slice(&buf[..n]);
+                }
+                let request = String::from_utf8(request).unwrap().to_lowercase();
+                let body = match request.starts_with("get /.well-known/nodeinfo ") {
+                    true => format!(
+                        r#"{{"links":[{{"rel":"{}","href":"{}"}}]}}"#,
+                        NODEINFO_20, nodeinfo_url
+                    ),
+                    false => String::from(r#"{"software":{"name":"mastodon"},"metadata":{}}"#),
+                };
+                let response = format!(
+                    "HTTP/1.1 200 OK\r\ncontent-type: application/json\r\ncontent-length: {}\r\nconnection: close\r\n\r\n{}",
+                    body.len(),
+                    body
+                );
+                tcp.write_all(response.as_bytes()).await.unwrap();
+                requests.push(request);
+            }
+            requests
+        });
+
+        let mut headers = HeaderMap::new();
+        headers.insert("x-megalodon", HeaderValue::from_static("detector"));
+        let client = ClientBuilder::new().default_headers(headers);
+        let sns = detector_with_client(&url, &client).await;
+
+        assert_eq!(sns.unwrap(), SNS::Mastodon);
+        let requests = server.await.unwrap();
+        assert_eq!(requests.len(), 2);
+        assert!(requests.iter().all(|r| r.contains("x-megalodon: detector")));
+    }
 }
@@ -0,0 +1,218 @@
+//! Diff modules, which compare revisions of an edited status
+use crate::entities::StatusEdit;
+use crate::filtering::html_to_text;
+
+// Maximum number of cells in the table of the longest common subsequence.
+const MAX_TABLE_SIZE: usize = 1_000_000;
+
+/// A piece of the difference between two texts.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Change {
+    /// Words which are in both texts.
+    Equal(String),
+    /// Words which are added in the new text.
+    Insert(String),
+    /// Words which are removed from the old text.
+    Delete(String),
+}
+
+/// Word-level difference of the content between two revisions of a status.
+///
+/// HTML of the content is converted to plain text before comparing.
+///
+/// ```rust
+/// # use megalodon::diff::{self, Change};
+/// # use megalodon::entities::StatusEdit;
+/// #
+/// # fn run(history: Vec<StatusEdit>) {
+/// for pair in history.windows(2) {
+///     for change in diff::diff_edits(&pair[0], &pair[1]) {
+///         match change {
+///             Change::Equal(text) => print!("{}", text),
+///             Change::Insert(text) => print!("[+{}]", text),
+///             Change::Delete(text) => print!("[-{}]", text),
+///         }
+///     }
+/// }
+/// # }
+/// ```
+pub fn diff_edits(old: &StatusEdit, new: &StatusEdit) -> Vec<Change> {
+    diff_words(&html_to_text(&old.content), &html_to_text(&new.content))
+}
+
+/// Word-level difference between two texts.
+///
+/// Whitespaces are compared as words, so joining the equal and deleted pieces gives the old text,
+/// and joining the equal and inserted pieces gives the new text.
+/// When the changed part is too long to compare, it is returned as a single deletion and insertion.
+pub fn diff_words(old: &str, new: &str) -> Vec<Change> {
+    let old = tokenize(old);
+    let new = tokenize(new);
+
+    let prefix = old
+        .iter()
+        .zip(new.iter())
+        .take_while(|(a, b)| a == b)
+        .count();
+    let suffix = old[prefix..]
+        .iter()
+        .rev()
+        .zip(new[prefix..].iter().rev())
+        .take_while(|(a, b)| a == b)
+        .count();
+    let old_middle = &old[prefix..old.len() - suffix];
+    let new_middle = &new[prefix..new.len() - suffix];
+
+    let mut changes = Vec::new();
+    push(&mut changes, Change::Equal(old[..prefix].concat()));
+    for change in lcs_changes(old_middle, new_middle) {
+        push(&mut changes, change);
+    }
+    push(
+        &mut changes,
+        Change::Equal(old[old.len() - suffix..].concat()),
+    );
+    changes
+}
+
+// Split the text into runs of whitespaces and runs of the other characters.
+fn tokenize(text: &str) -> Vec<&str> {
+    let mut tokens = Vec::new();
+    let mut start = 0;
+    let mut last_is_space: Option<bool> = None;
+    for (i, c) in text.char_indices() {
+        let is_space = c.is_whitespace();
+        if last_is_space.is_some_and(|last| last != is_space) {
+            tokens.push(&text[start..i]);
+            start = i;
+        }
+        last_is_space = Some(is_space);
+    }
+    if start < text.len() {
+        tokens.push(&text[start..]);
+    }
+    tokens
+}
+
+fn lcs_changes(old: &[&str], new: &[&str]) -> Vec<Change> {
+    // The table grows with the product of the lengths.
+    if old.len().saturating_mul(new.len()) > MAX_TABLE_SIZE {
+        return vec![Change::Delete(old.concat()), Change::Insert(new.concat())];
+    }
+    // lengths[i][j] is the length of the longest common subsequence of old[i..] and new[j..].
+    let mut lengths = vec![vec![0usize; new.len() + 1]; old.len() + 1];
+    for i in (0..old.len()).rev() {
+        for j in (0..new.len()).rev() {
+            lengths[i][j] = if old[i] == new[j] {
+                lengths[i + 1][j + 1] + 1
+            } else {
+                lengths[i + 1][j].max(lengths[i][j + 1])
+            };
+        }
+    }
+
+    let mut changes = Vec::new();
+    let (mut i, mut j) = (0, 0);
+    while i < old.len() && j < new.len() {
+        if old[i] == new[j] {
+            changes.push(Change::Equal(old[i].to_string()));
+            i += 1;
+            j += 1;
+        } else if lengths[i + 1][j] >= lengths[i][j + 1] {
+            changes.push(Change::Delete(old[i].to_string()));
+            i += 1;
+        } else {
+            changes.push(Change::Insert(new[j].to_string()));
+            j += 1;
+        }
+    }
+    changes.extend(old[i..].iter().map(|t| Change::Delete(t.to_string())));
+    changes.extend(new[j..].iter().map(|t| Change::Insert(t.to_string())));
+    changes
+}
+
+// Append the change, merging it into the last one when both are the same kind.
+fn push(changes: &mut Vec<Change>, change: Change) {
+    match (changes.last_mut(), change) {
+        (_, Change::Equal(text) | Change::Insert(text) | Change::Delete(text))
+            if text.is_empty() => {}
+        (Some(Change::Equal(last)), Change::Equal(text))
+        | (Some(Change::Insert(last)), Change::Insert(text))
+        | (Some(Change::Delete(last)), Change::Delete(text)) => last.push_str(&text),
+        (_, change) => changes.push(change),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn join(changes: &[Change], old: bool) -> String {
+        changes
+            .iter()
+            .filter_map(|change| match change {
+                Change::Equal(text) => Some(text.as_str()),
+                Change::Delete(text) if old => Some(text.as_str()),
+                Change::Insert(text) if !old => Some(text.as_str()),
+                _ => None,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_diff_words() {
+        let changes = diff_words("the quick brown fox", "the slow brown fox jumps");
+        assert_eq!(
+            changes,
+            vec![
+                Change::Equal("the ".to_string()),
+                Change::Delete("quick".to_string()),
+                Change::Insert("slow".to_string()),
+                Change::Equal(" brown fox".to_string()),
+                Change::Insert(" jumps".to_string()),
+            ]
+        );
+        assert_eq!(join(&changes, true), "the quick brown fox");
+        assert_eq!(join(&changes, false), "the slow brown fox jumps");
+    }
+
+    #[test]
+    fn test_diff_words_edge_cases() {
+        assert_eq!(diff_words("", ""), Vec::new());
+        assert_eq!(
+            diff_words("same  text", "same  text"),
+            vec![Change::Equal("same  text".to_string())]
+        );
+        assert_eq!(
+            diff_words("", "new"),
+            vec![Change::Insert("new".to_string())]
+        );
+
+        let changes = diff_words("a b c d", "d c b a");
+        assert_eq!(join(&changes, true), "a b c d");
+        assert_eq!(join(&changes, false), "d c b a");
+    }
+
+    #[test]
+    fn test_diff_words_large_input() {
+        let old = "a ".repeat(2000);
+        let new = "b ".repeat(2000);
+        let changes = diff_words(&old, &new);
+        assert_eq!(
+            changes,
+            vec![
+                Change::Delete(old[..old.len() - 1].to_string()),
+                Change::Insert(new[..new.len() - 1].to_string()),
+                Change::Equal(" ".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_tokenize() {
+        assert_eq!(
+            tokenize(" hello  world\n"),
+            vec![" ", "hello", "  ", "world", "\n"]
+        );
+    }
+}
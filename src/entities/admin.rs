@@ -0,0 +1,191 @@
+use super::{instance::InstanceRule, report::Category, History, Role, Status};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct Account {
+    pub id: String,
+    pub username: String,
+    pub domain: Option<String>,
+    pub created_at: Option<DateTime<Utc>>,
+    pub email: Option<String>,
+    pub ip: Option<String>,
+    pub ips: Vec<Ip>,
+    pub locale: Option<String>,
+    pub invite_request: Option<String>,
+    pub role: Option<Role>,
+    pub confirmed: bool,
+    pub approved: bool,
+    pub disabled: bool,
+    pub silenced: bool,
+    pub suspended: bool,
+    // The public account is not provided by Pleroma.
+    pub account: Option<super::Account>,
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct Ip {
+    pub ip: String,
+    pub used_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct Report {
+    pub id: String,
+    pub action_taken: bool,
+    pub action_taken_at: Option<DateTime<Utc>>,
+    pub category: Category,
+    pub comment: String,
+    pub forwarded: bool,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: Option<DateTime<Utc>>,
+    pub account: Account,
+    pub target_account: Account,
+    pub assigned_account: Option<Account>,
+    pub action_taken_by_account: Option<Account>,
+    pub statuses: Vec<Status>,
+    pub rules: Vec<InstanceRule>,
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct DomainBlock {
+    pub id: String,
+    pub domain: String,
+    pub digest: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub severity: DomainBlockSeverity,
+    pub reject_media: bool,
+    pub reject_reports: bool,
+    pub private_comment: Option<String>,
+    pub public_comment: Option<String>,
+    pub obfuscate: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum DomainBlockSeverity {
+    Silence,
+    Suspend,
+    Noop,
+}
+
+impl fmt::Display for DomainBlockSeverity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DomainBlockSeverity::Silence => write!(f, "silence"),
+            DomainBlockSeverity::Suspend => write!(f, "suspend"),
+            DomainBlockSeverity::Noop => write!(f, "noop"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct DomainAllow {
+    pub id: String,
+    pub domain: String,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct EmailDomainBlock {
+    pub id: String,
+    pub domain: String,
+    pub created_at: DateTime<Utc>,
+    pub history: Vec<History>,
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct IpBlock {
+    pub id: String,
+    pub ip: String,
+    pub severity: IpBlockSeverity,
+    pub comment: String,
+    pub created_at: DateTime<Utc>,
+    pub expires_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum IpBlockSeverity {
+    SignUpRequiresApproval,
+    SignUpBlock,
+    NoAccess,
+}
+
+impl fmt::Display for IpBlockSeverity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            IpBlockSeverity::SignUpRequiresApproval => write!(f, "sign_up_requires_approval"),
+            IpBlockSeverity::SignUpBlock => write!(f, "sign_up_block"),
+            IpBlockSeverity::NoAccess => write!(f, "no_access"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct CanonicalEmailBlock {
+    pub id: String,
+    pub canonical_email_hash: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct Measure {
+    pub key: String,
+    pub unit: Option<String>,
+    pub total: String,
+    pub human_value: Option<String>,
+    pub previous_total: Option<String>,
+    pub data: Vec<MeasureData>,
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct MeasureData {
+    pub date: DateTime<Utc>,
+    pub value: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct Dimension {
+    pub key: String,
+    pub data: Vec<DimensionData>,
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct DimensionData {
+    pub key: String,
+    pub human_key: String,
+    pub value: String,
+    pub unit: Option<String>,
+    pub human_value: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct Cohort {
+    pub period: DateTime<Utc>,
+    pub frequency: CohortFrequency,
+    pub data: Vec<CohortData>,
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum CohortFrequency {
+    Day,
+    Month,
+}
+
+impl fmt::Display for CohortFrequency {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CohortFrequency::Day => write!(f, "day"),
+            CohortFrequency::Month => write!(f, "month"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct CohortData {
+    pub date: DateTime<Utc>,
+    pub rate: f64,
+    pub value: u64,
+}
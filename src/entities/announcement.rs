@@ -43,3 +43,10 @@ pub struct Reaction {
     pub url: Option<String>,
     pub static_url: Option<String>,
 }
+
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct AnnouncementReaction {
+    pub name: String,
+    pub count: i64,
+    pub announcement_id: String,
+}
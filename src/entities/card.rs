@@ -1,3 +1,4 @@
+use super::{Account, History};
 use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
@@ -16,6 +17,24 @@ pub struct Card {
     pub height: Option<u32>,
     pub embed_url: Option<String>,
     pub blurhash: Option<String>,
+    #[serde(default)]
+    pub authors: Vec<CardAuthor>,
+}
+
+/// Author of the linked page, which may be connected to a fediverse account.
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct CardAuthor {
+    pub name: String,
+    pub url: String,
+    pub account: Option<Account>,
+}
+
+/// Link which is trending on the server, with its usage history.
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct TrendsLink {
+    #[serde(flatten)]
+    pub card: Card,
+    pub history: Vec<History>,
 }
 
 #[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
@@ -0,0 +1,25 @@
+use super::{Account, Attachment, Card, Emoji};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct Chat {
+    pub id: String,
+    pub account: Account,
+    pub unread: u32,
+    pub last_message: Option<ChatMessage>,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct ChatMessage {
+    pub id: String,
+    pub chat_id: String,
+    pub account_id: String,
+    pub content: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub emojis: Vec<Emoji>,
+    pub attachment: Option<Attachment>,
+    pub card: Option<Card>,
+    pub unread: bool,
+}
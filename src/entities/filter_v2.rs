@@ -0,0 +1,53 @@
+use super::filter::FilterContext;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct FilterV2 {
+    pub id: String,
+    pub title: String,
+    pub context: Vec<FilterContext>,
+    pub expires_at: Option<DateTime<Utc>>,
+    pub filter_action: FilterAction,
+    pub keywords: Vec<FilterKeyword>,
+    pub statuses: Vec<FilterStatus>,
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum FilterAction {
+    Warn,
+    Hide,
+    Blur,
+}
+
+impl fmt::Display for FilterAction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FilterAction::Warn => write!(f, "warn"),
+            FilterAction::Hide => write!(f, "hide"),
+            FilterAction::Blur => write!(f, "blur"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct FilterKeyword {
+    pub id: String,
+    pub keyword: String,
+    pub whole_word: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct FilterStatus {
+    pub id: String,
+    pub status_id: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct FilterResult {
+    pub filter: FilterV2,
+    pub keyword_matches: Option<Vec<String>>,
+    pub status_matches: Option<Vec<String>>,
+}
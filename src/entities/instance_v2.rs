@@ -0,0 +1,119 @@
+use super::instance::{InstanceRule, Polls, Statuses};
+use super::{Account, Instance};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
+pub struct InstanceV2 {
+    pub domain: String,
+    pub title: String,
+    pub version: String,
+    pub source_url: Option<String>,
+    pub description: String,
+    pub thumbnail: Option<InstanceThumbnail>,
+    pub languages: Vec<String>,
+    pub configuration: InstanceV2Config,
+    pub registrations: InstanceRegistrations,
+    pub api_versions: HashMap<String, u32>,
+    pub contact: InstanceContact,
+    pub rules: Vec<InstanceRule>,
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
+pub struct InstanceThumbnail {
+    pub url: String,
+    pub blurhash: Option<String>,
+    pub versions: Option<ThumbnailVersions>,
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
+pub struct ThumbnailVersions {
+    #[serde(rename = "@1x")]
+    pub at_1x: Option<String>,
+    #[serde(rename = "@2x")]
+    pub at_2x: Option<String>,
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
+pub struct InstanceV2Config {
+    pub urls: InstanceV2URLs,
+    pub statuses: Statuses,
+    pub media_attachments: Option<MediaAttachments>,
+    pub polls: Option<Polls>,
+    pub translation: Translation,
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
+pub struct InstanceV2URLs {
+    pub streaming: Option<String>,
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
+pub struct MediaAttachments {
+    pub supported_mime_types: Vec<String>,
+    pub description_limit: Option<u32>,
+    pub image_size_limit: u32,
+    pub image_matrix_limit: u32,
+    pub video_size_limit: u32,
+    pub video_frame_rate_limit: u32,
+    pub video_matrix_limit: u32,
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
+pub struct Translation {
+    pub enabled: bool,
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
+pub struct InstanceRegistrations {
+    pub enabled: bool,
+    pub approval_required: bool,
+    pub message: Option<String>,
+    pub url: Option<String>,
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
+pub struct InstanceContact {
+    pub email: String,
+    pub account: Option<Account>,
+}
+
+/// Map v1 instance for servers which don't provide v2 instance.
+impl From<Instance> for InstanceV2 {
+    fn from(val: Instance) -> Self {
+        InstanceV2 {
+            domain: val.uri,
+            title: val.title,
+            version: val.version,
+            source_url: None,
+            description: val.description,
+            thumbnail: val.thumbnail.map(|url| InstanceThumbnail {
+                url,
+                blurhash: None,
+                versions: None,
+            }),
+            languages: val.languages,
+            configuration: InstanceV2Config {
+                urls: InstanceV2URLs {
+                    streaming: val.urls.map(|u| u.streaming_api),
+                },
+                statuses: val.configuration.statuses,
+                media_attachments: None,
+                polls: val.configuration.polls,
+                translation: Translation { enabled: false },
+            },
+            registrations: InstanceRegistrations {
+                enabled: val.registrations,
+                approval_required: val.approval_required,
+                message: None,
+                url: None,
+            },
+            api_versions: HashMap::new(),
+            contact: InstanceContact {
+                email: val.email,
+                account: val.contact_account,
+            },
+            rules: val.rules.unwrap_or_default(),
+        }
+    }
+}
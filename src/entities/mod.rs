@@ -2,25 +2,32 @@
 
 pub mod account;
 pub mod activity;
+pub mod admin;
 pub mod announcement;
 pub mod application;
 pub mod async_attachment;
 pub mod attachment;
 pub mod card;
+pub mod chat;
 pub mod context;
 pub mod conversation;
 pub mod emoji;
 pub mod featured_tag;
 pub mod field;
 pub mod filter;
+pub mod filter_v2;
 pub mod follow_request;
 pub mod history;
 pub mod identity_proof;
 pub mod instance;
+pub mod instance_v2;
 pub mod list;
 pub mod marker;
 pub mod mention;
 pub mod notification;
+pub mod notification_group;
+pub mod notification_policy;
+pub mod notification_request;
 pub mod poll;
 pub mod poll_option;
 pub mod preferences;
@@ -36,10 +43,12 @@ pub mod scheduled_status;
 pub mod source;
 pub mod stats;
 pub mod status;
+pub mod status_edit;
 pub mod status_params;
 pub mod status_source;
 pub mod tag;
 pub mod token;
+pub mod translation;
 pub mod urls;
 
 pub use account::Account;
@@ -49,21 +58,29 @@ pub use application::Application;
 pub use async_attachment::AsyncAttachment;
 pub use async_attachment::UploadMedia;
 pub use attachment::Attachment;
-pub use card::Card;
+pub use card::{Card, CardAuthor, TrendsLink};
+pub use chat::{Chat, ChatMessage};
 pub use context::Context;
 pub use conversation::Conversation;
 pub use emoji::Emoji;
 pub use featured_tag::FeaturedTag;
 pub use field::Field;
 pub use filter::Filter;
+pub use filter_v2::{FilterAction, FilterKeyword, FilterResult, FilterStatus, FilterV2};
 pub use follow_request::FollowRequest;
 pub use history::History;
 pub use identity_proof::IdentityProof;
 pub use instance::Instance;
+pub use instance_v2::InstanceV2;
 pub use list::List;
 pub use marker::Marker;
 pub use mention::Mention;
 pub use notification::Notification;
+pub use notification_group::{
+    GroupedNotificationsResults, NotificationGroup, PartialAccountWithAvatar, UnreadCount,
+};
+pub use notification_policy::NotificationPolicy;
+pub use notification_request::NotificationRequest;
 pub use poll::Poll;
 pub use poll_option::PollOption;
 pub use preferences::Preferences;
@@ -79,8 +96,10 @@ pub use scheduled_status::ScheduledStatus;
 pub use source::Source;
 pub use stats::Stats;
 pub use status::{Status, StatusVisibility};
+pub use status_edit::StatusEdit;
 pub use status_params::StatusParams;
 pub use status_source::StatusSource;
 pub use tag::Tag;
 pub use token::Token;
+pub use translation::Translation;
 pub use urls::URLs;
@@ -0,0 +1,62 @@
+use super::{notification::NotificationType, Account, Report, Status};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct GroupedNotificationsResults {
+    pub accounts: Vec<Account>,
+    pub partial_accounts: Option<Vec<PartialAccountWithAvatar>>,
+    pub statuses: Vec<Status>,
+    pub notification_groups: Vec<NotificationGroup>,
+}
+
+impl GroupedNotificationsResults {
+    /// Find the account in the deduplicated accounts.
+    pub fn account(&self, id: &str) -> Option<&Account> {
+        self.accounts.iter().find(|a| a.id == id)
+    }
+
+    /// Find the status in the deduplicated statuses.
+    pub fn status(&self, id: &str) -> Option<&Status> {
+        self.statuses.iter().find(|s| s.id == id)
+    }
+
+    /// Accounts of the group which are included in the results.
+    pub fn sample_accounts(&self, group: &NotificationGroup) -> Vec<&Account> {
+        group
+            .sample_account_ids
+            .iter()
+            .filter_map(|id| self.account(id))
+            .collect()
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct NotificationGroup {
+    pub group_key: String,
+    pub notifications_count: u32,
+    pub r#type: NotificationType,
+    pub most_recent_notification_id: String,
+    pub page_min_id: Option<String>,
+    pub page_max_id: Option<String>,
+    pub latest_page_notification_at: Option<DateTime<Utc>>,
+    pub sample_account_ids: Vec<String>,
+    pub status_id: Option<String>,
+    pub report: Option<Report>,
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct PartialAccountWithAvatar {
+    pub id: String,
+    pub acct: String,
+    pub url: String,
+    pub avatar: String,
+    pub avatar_static: String,
+    pub locked: bool,
+    pub bot: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct UnreadCount {
+    pub count: u32,
+}
@@ -0,0 +1,36 @@
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct NotificationPolicy {
+    pub for_not_following: NotificationFilteringPolicy,
+    pub for_not_followers: NotificationFilteringPolicy,
+    pub for_new_accounts: NotificationFilteringPolicy,
+    pub for_private_mentions: NotificationFilteringPolicy,
+    pub for_limited_accounts: NotificationFilteringPolicy,
+    pub summary: NotificationPolicySummary,
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum NotificationFilteringPolicy {
+    Accept,
+    Filter,
+    Drop,
+}
+
+impl fmt::Display for NotificationFilteringPolicy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            NotificationFilteringPolicy::Accept => write!(f, "accept"),
+            NotificationFilteringPolicy::Filter => write!(f, "filter"),
+            NotificationFilteringPolicy::Drop => write!(f, "drop"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct NotificationPolicySummary {
+    pub pending_requests_count: u32,
+    pub pending_notifications_count: u32,
+}
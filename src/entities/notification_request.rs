@@ -0,0 +1,13 @@
+use super::{Account, Status};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct NotificationRequest {
+    pub id: String,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+    pub account: Account,
+    pub notifications_count: u32,
+    pub last_status: Option<Status>,
+}
@@ -1,4 +1,5 @@
 use serde::{Deserialize, Serialize};
+use std::fmt;
 
 #[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
 pub struct QuoteApproval {
@@ -26,3 +27,22 @@ impl QuoteApproval {
         }
     }
 }
+
+/// Who can quote a status without the approval of the author.
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum QuoteApprovalPolicy {
+    Public,
+    Followers,
+    Nobody,
+}
+
+impl fmt::Display for QuoteApprovalPolicy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            QuoteApprovalPolicy::Public => write!(f, "public"),
+            QuoteApprovalPolicy::Followers => write!(f, "followers"),
+            QuoteApprovalPolicy::Nobody => write!(f, "nobody"),
+        }
+    }
+}
@@ -24,6 +24,7 @@ pub struct Report {
 pub enum Category {
     Spam,
     Violation,
+    Legal,
     Other,
 }
 
@@ -32,6 +33,7 @@ impl fmt::Display for Category {
         match self {
             Category::Spam => write!(f, "spam"),
             Category::Violation => write!(f, "violation"),
+            Category::Legal => write!(f, "legal"),
             Category::Other => write!(f, "other"),
         }
     }
@@ -1,6 +1,6 @@
 use super::{
-    Account, Application, Attachment, Card, Emoji, Mention, Poll, QuoteApproval, QuotedStatus,
-    Reaction,
+    Account, Application, Attachment, Card, Emoji, FilterResult, Mention, Poll, QuoteApproval,
+    QuotedStatus, Reaction,
 };
 use crate::error::{Error, Kind};
 use chrono::{DateTime, Utc};
@@ -43,6 +43,7 @@ pub struct Status {
     pub quote: Option<QuotedStatus>,
     pub quote_approval: QuoteApproval,
     pub bookmarked: Option<bool>,
+    pub filtered: Vec<FilterResult>,
 }
 
 #[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
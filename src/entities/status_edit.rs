@@ -0,0 +1,26 @@
+use super::{Account, Attachment, Emoji};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/// A revision of a status. The last revision is the current status.
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct StatusEdit {
+    pub content: String,
+    pub spoiler_text: String,
+    pub sensitive: bool,
+    pub created_at: DateTime<Utc>,
+    pub account: Account,
+    pub poll: Option<StatusEditPoll>,
+    pub media_attachments: Vec<Attachment>,
+    pub emojis: Vec<Emoji>,
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct StatusEditPoll {
+    pub options: Vec<StatusEditPollOption>,
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct StatusEditPollOption {
+    pub title: String,
+}
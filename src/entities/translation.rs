@@ -0,0 +1,29 @@
+use serde::{Deserialize, Serialize};
+
+/// Translation of a status. Parts which the server does not translate are left empty.
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct Translation {
+    pub content: String,
+    pub spoiler_text: String,
+    pub poll: Option<TranslationPoll>,
+    pub media_attachments: Vec<TranslationAttachment>,
+    pub detected_source_language: String,
+    pub provider: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct TranslationPoll {
+    pub id: String,
+    pub options: Vec<TranslationPollOption>,
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct TranslationPollOption {
+    pub title: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct TranslationAttachment {
+    pub id: String,
+    pub description: String,
+}
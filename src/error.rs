@@ -1,5 +1,7 @@
 //! Own errors
 use reqwest::header::HeaderMap;
+use serde::Deserialize;
+use std::collections::HashMap;
 use std::fmt;
 
 /// Possible megalodon errors.
@@ -67,6 +69,39 @@ pub enum Kind {
     /// The entity does not satisfy required parameters.
     #[error("unsatisfied error")]
     UnsatisfiedError,
+    /// The requested resource is not found.
+    #[error("not found error")]
+    NotFoundError,
+    /// The access token is missing or invalid.
+    #[error("unauthorized error")]
+    UnauthorizedError,
+    /// The access token is not permitted to do the request.
+    #[error("forbidden error")]
+    ForbiddenError,
+    /// Too many requests are sent. The header of [`OwnError`] may contain `X-RateLimit-*`.
+    #[error("rate limited error")]
+    RateLimitedError,
+    /// The parameters are invalid. Details are grouped by the name of the field.
+    #[error("validation error")]
+    ValidationError(HashMap<String, Vec<ValidationDetail>>),
+    /// The requested resource has been deleted.
+    #[error("gone error")]
+    GoneError,
+    /// The server failed to process the request.
+    #[error("server error")]
+    ServerError,
+    /// The server does not support the request.
+    #[error("unsupported error")]
+    UnsupportedError,
+}
+
+/// Reason why a field is invalid.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct ValidationDetail {
+    /// Error code, like `ERR_BLANK`.
+    pub error: String,
+    /// Human readable description.
+    pub description: String,
 }
 
 impl Error {
@@ -88,6 +123,108 @@ impl Error {
     }
 }
 
+impl Error {
+    /// Create an error from the error response of the server.
+    /// The body is parsed as Mastodon or Misskey error format, and the kind is decided by the status code or the error code.
+    pub(crate) fn from_response(
+        body: String,
+        url: String,
+        status: u16,
+        header: HeaderMap,
+    ) -> Error {
+        let (message, kind) = match serde_json::from_str::<ErrorBody>(&body) {
+            Ok(ErrorBody::Misskey { error }) => {
+                let kind = match error.code.as_str() {
+                    "INVALID_PARAM" => Some(Kind::ValidationError(error.validation_details())),
+                    code => kind_from_code(code),
+                };
+                let message = format!("{} ({})", error.message, error.code);
+                (
+                    message,
+                    kind.unwrap_or(kind_from_status(status, HashMap::new())),
+                )
+            }
+            Ok(ErrorBody::Mastodon { error, details }) => {
+                (error, kind_from_status(status, details))
+            }
+            Err(_) => (body, kind_from_status(status, HashMap::new())),
+        };
+        Error::new_own(message, kind, Some(url), Some(status), Some(header))
+    }
+}
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum ErrorBody {
+    Misskey {
+        error: MisskeyError,
+    },
+    Mastodon {
+        error: String,
+        #[serde(default)]
+        details: HashMap<String, Vec<ValidationDetail>>,
+    },
+}
+
+#[derive(Deserialize)]
+struct MisskeyError {
+    message: String,
+    code: String,
+    info: Option<MisskeyErrorInfo>,
+}
+
+#[derive(Deserialize)]
+struct MisskeyErrorInfo {
+    param: Option<String>,
+    reason: Option<String>,
+}
+
+impl MisskeyError {
+    fn validation_details(&self) -> HashMap<String, Vec<ValidationDetail>> {
+        let Some(info) = &self.info else {
+            return HashMap::new();
+        };
+        let Some(param) = &info.param else {
+            return HashMap::new();
+        };
+        // Param is a JSON pointer of the schema, like `#/properties/noteId`.
+        let field = param.trim_start_matches("#/properties/").to_string();
+        let detail = ValidationDetail {
+            error: self.code.clone(),
+            description: info.reason.clone().unwrap_or(self.message.clone()),
+        };
+        HashMap::from([(field, vec![detail])])
+    }
+}
+
+fn kind_from_code(code: &str) -> Option<Kind> {
+    match code {
+        "CREDENTIAL_REQUIRED" | "AUTHENTICATION_FAILED" => Some(Kind::UnauthorizedError),
+        "PERMISSION_DENIED" | "ACCESS_DENIED" | "YOU_HAVE_BEEN_BLOCKED" => {
+            Some(Kind::ForbiddenError)
+        }
+        "RATE_LIMIT_EXCEEDED" => Some(Kind::RateLimitedError),
+        "INTERNAL_ERROR" => Some(Kind::ServerError),
+        code if code.starts_with("NO_SUCH_") => Some(Kind::NotFoundError),
+        _ => None,
+    }
+}
+
+fn kind_from_status(status: u16, details: HashMap<String, Vec<ValidationDetail>>) -> Kind {
+    match status {
+        401 => Kind::UnauthorizedError,
+        403 => Kind::ForbiddenError,
+        404 => Kind::NotFoundError,
+        410 => Kind::GoneError,
+        422 => Kind::ValidationError(details),
+        400 if !details.is_empty() => Kind::ValidationError(details),
+        429 => Kind::RateLimitedError,
+        405 | 501 => Kind::UnsupportedError,
+        500..=599 => Kind::ServerError,
+        _ => Kind::HTTPStatusError,
+    }
+}
+
 impl fmt::Debug for OwnError {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         let mut builder = f.debug_struct("megalodon::OwnError");
@@ -109,3 +246,76 @@ impl fmt::Debug for OwnError {
         builder.finish()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn kind(body: &str, status: u16) -> (String, Kind) {
+        match Error::from_response(
+            body.to_string(),
+            String::from("https://example.com"),
+            status,
+            HeaderMap::new(),
+        ) {
+            Error::OwnError(own) => (own.message, own.kind),
+            other => panic!("unexpected error: {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_from_mastodon_response() {
+        let (message, k) = kind(r#"{"error":"Record not found"}"#, 404);
+        assert_eq!(message, "Record not found");
+        assert!(matches!(k, Kind::NotFoundError));
+
+        let (_, k) = kind(
+            r#"{"error":"Validation failed: Text can't be blank","details":{"text":[{"error":"ERR_BLANK","description":"can't be blank"}]}}"#,
+            422,
+        );
+        match k {
+            Kind::ValidationError(details) => assert_eq!(
+                details.get("text"),
+                Some(&vec![ValidationDetail {
+                    error: String::from("ERR_BLANK"),
+                    description: String::from("can't be blank"),
+                }])
+            ),
+            other => panic!("unexpected kind: {:?}", other),
+        }
+
+        let (message, k) = kind("<html>Bad Gateway</html>", 502);
+        assert_eq!(message, "<html>Bad Gateway</html>");
+        assert!(matches!(k, Kind::ServerError));
+    }
+
+    #[test]
+    fn test_from_misskey_response() {
+        let (message, k) = kind(
+            r#"{"error":{"message":"No such note.","code":"NO_SUCH_NOTE","id":"24fcbfc6-2e37-42b6-8388-c29b3861a08d"}}"#,
+            400,
+        );
+        assert_eq!(message, "No such note. (NO_SUCH_NOTE)");
+        assert!(matches!(k, Kind::NotFoundError));
+
+        let (_, k) = kind(
+            r##"{"error":{"message":"Invalid param.","code":"INVALID_PARAM","id":"3d81ceae-475f-4600-b2a8-2bc116157532","info":{"param":"#/properties/noteId","reason":"must NOT have fewer than 1 characters"}}}"##,
+            400,
+        );
+        match k {
+            Kind::ValidationError(details) => {
+                assert_eq!(
+                    details.get("noteId").unwrap()[0].description,
+                    "must NOT have fewer than 1 characters"
+                )
+            }
+            other => panic!("unexpected kind: {:?}", other),
+        }
+
+        let (_, k) = kind(
+            r#"{"error":{"message":"Unknown.","code":"SOMETHING_NEW","id":"x"}}"#,
+            400,
+        );
+        assert!(matches!(k, Kind::HTTPStatusError));
+    }
+}
@@ -0,0 +1,304 @@
+//! Filtering modules, which evaluate filters on the client side for SNS without server-side filtering
+use std::sync::OnceLock;
+
+use chrono::{DateTime, Utc};
+use regex::{Regex, RegexBuilder};
+
+use crate::entities::{
+    self, filter::FilterContext, FilterAction, FilterKeyword, FilterResult, FilterV2,
+};
+
+/// Filters which are matched against statuses on the client.
+///
+/// Servers which don't apply filters, like Firefish, leave it to the client.
+/// Keywords are compared with the spoiler text, the content, poll options and media descriptions of a status.
+///
+/// ```rust
+/// # use megalodon::entities::{self, filter::FilterContext};
+/// # use megalodon::filtering::Filters;
+/// #
+/// # fn run(filters: Vec<entities::Filter>, statuses: Vec<entities::Status>) {
+/// let filters = Filters::from_v1(&filters);
+/// // Hidden statuses are removed, and warned statuses carry their matches in `filtered`.
+/// let statuses = filters.apply(statuses, &FilterContext::Home);
+/// # }
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct Filters {
+    rules: Vec<Rule>,
+}
+
+#[derive(Debug, Clone)]
+struct Rule {
+    filter: FilterV2,
+    keywords: Vec<(String, Regex)>,
+}
+
+impl Filters {
+    /// Compile filters of Filters v1 API. Irreversible filters hide statuses, and the others warn.
+    pub fn from_v1(filters: &[entities::Filter]) -> Self {
+        let filters = filters
+            .iter()
+            .map(|filter| FilterV2 {
+                id: filter.id.clone(),
+                title: filter.phrase.clone(),
+                context: filter.context.clone(),
+                expires_at: filter.expires_at,
+                filter_action: if filter.irreversible {
+                    FilterAction::Hide
+                } else {
+                    FilterAction::Warn
+                },
+                keywords: vec![FilterKeyword {
+                    id: filter.id.clone(),
+                    keyword: filter.phrase.clone(),
+                    whole_word: filter.whole_word,
+                }],
+                statuses: Vec::new(),
+            })
+            .collect::<Vec<_>>();
+        Self::from_v2(&filters)
+    }
+
+    /// Compile filters of Filters v2 API.
+    pub fn from_v2(filters: &[FilterV2]) -> Self {
+        let rules = filters
+            .iter()
+            .map(|filter| Rule {
+                keywords: filter
+                    .keywords
+                    .iter()
+                    .filter_map(|keyword| {
+                        keyword_regex(&keyword.keyword, keyword.whole_word)
+                            .map(|regex| (keyword.keyword.clone(), regex))
+                    })
+                    .collect(),
+                filter: filter.clone(),
+            })
+            .collect();
+        Self { rules }
+    }
+
+    /// Filters which match the status in the context. Reblogs are matched by the reblogged status.
+    pub fn matches(&self, status: &entities::Status, context: &FilterContext) -> Vec<FilterResult> {
+        self.matches_at(status, context, Utc::now())
+    }
+
+    /// Same as [`Filters::matches`], but filters which expire before `now` are ignored.
+    pub fn matches_at(
+        &self,
+        status: &entities::Status,
+        context: &FilterContext,
+        now: DateTime<Utc>,
+    ) -> Vec<FilterResult> {
+        let target = status.reblog.as_deref().unwrap_or(status);
+        let ids = [status.id.as_str(), target.id.as_str()];
+        self.evaluate(&searchable_text(target), &ids, context, now)
+    }
+
+    /// Drop statuses which should be hidden, and set the matched filters to `filtered` of the rest.
+    pub fn apply(
+        &self,
+        statuses: Vec<entities::Status>,
+        context: &FilterContext,
+    ) -> Vec<entities::Status> {
+        let now = Utc::now();
+        statuses
+            .into_iter()
+            .filter_map(|mut status| {
+                let results = self.matches_at(&status, context, now);
+                if action(&results) == Some(FilterAction::Hide) {
+                    return None;
+                }
+                status.filtered = results;
+                Some(status)
+            })
+            .collect()
+    }
+
+    fn evaluate(
+        &self,
+        text: &str,
+        status_ids: &[&str],
+        context: &FilterContext,
+        now: DateTime<Utc>,
+    ) -> Vec<FilterResult> {
+        self.rules
+            .iter()
+            .filter(|rule| rule.filter.context.contains(context))
+            .filter(|rule| match rule.filter.expires_at {
+                Some(expires_at) => expires_at > now,
+                None => true,
+            })
+            .filter_map(|rule| {
+                let keyword_matches = rule
+                    .keywords
+                    .iter()
+                    .filter(|(_, regex)| regex.is_match(text))
+                    .map(|(keyword, _)| keyword.clone())
+                    .collect::<Vec<String>>();
+                let status_matches = rule
+                    .filter
+                    .statuses
+                    .iter()
+                    .filter(|s| status_ids.contains(&s.status_id.as_str()))
+                    .map(|s| s.id.clone())
+                    .collect::<Vec<String>>();
+                if keyword_matches.is_empty() && status_matches.is_empty() {
+                    return None;
+                }
+                Some(FilterResult {
+                    filter: rule.filter.clone(),
+                    keyword_matches: Some(keyword_matches).filter(|m| !m.is_empty()),
+                    status_matches: Some(status_matches).filter(|m| !m.is_empty()),
+                })
+            })
+            .collect()
+    }
+}
+
+/// The strongest action of the matched filters, in the order of hide, blur and warn.
+pub fn action(results: &[FilterResult]) -> Option<FilterAction> {
+    let rank = |action: &FilterAction| match action {
+        FilterAction::Warn => 0,
+        FilterAction::Blur => 1,
+        FilterAction::Hide => 2,
+    };
+    results
+        .iter()
+        .map(|result| result.filter.filter_action.clone())
+        .max_by_key(rank)
+}
+
+// Word boundaries are added only where the keyword starts or ends with a word character, as Mastodon does.
+fn keyword_regex(keyword: &str, whole_word: bool) -> Option<Regex> {
+    let keyword = keyword.trim();
+    if keyword.is_empty() {
+        return None;
+    }
+    let is_word = |c: Option<char>| c.is_some_and(|c| c.is_alphanumeric() || c == '_');
+    let mut pattern = regex::escape(keyword);
+    if whole_word {
+        if is_word(keyword.chars().next()) {
+            pattern = format!(r"\b{}", pattern);
+        }
+        if is_word(keyword.chars().last()) {
+            pattern = format!(r"{}\b", pattern);
+        }
+    }
+    RegexBuilder::new(&pattern)
+        .case_insensitive(true)
+        .build()
+        .ok()
+}
+
+fn searchable_text(status: &entities::Status) -> String {
+    let mut parts = vec![status.spoiler_text.clone()];
+    parts.push(match &status.plain_content {
+        Some(plain) => plain.clone(),
+        None => html_to_text(&status.content),
+    });
+    if let Some(poll) = &status.poll {
+        parts.extend(poll.options.iter().map(|option| option.title.clone()));
+    }
+    parts.extend(
+        status
+            .media_attachments
+            .iter()
+            .filter_map(|attachment| attachment.description.clone()),
+    );
+    parts.join("\n\n")
+}
+
+pub(crate) fn html_to_text(html: &str) -> String {
+    static BREAKS: OnceLock<Regex> = OnceLock::new();
+    static TAGS: OnceLock<Regex> = OnceLock::new();
+    let breaks = BREAKS.get_or_init(|| Regex::new(r"(?i)<br\s*/?>|</p>").unwrap());
+    let tags = TAGS.get_or_init(|| Regex::new(r"<[^>]*>").unwrap());
+    let text = breaks.replace_all(html, "\n");
+    let text = tags.replace_all(&text, "");
+    text.replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&#39;", "'")
+        .replace("&apos;", "'")
+        .replace("&nbsp;", " ")
+        .replace("&amp;", "&")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Duration;
+
+    fn filter(keyword: &str, whole_word: bool, action: FilterAction) -> FilterV2 {
+        FilterV2 {
+            id: "1".to_string(),
+            title: keyword.to_string(),
+            context: vec![FilterContext::Home],
+            expires_at: None,
+            filter_action: action,
+            keywords: vec![FilterKeyword {
+                id: "10".to_string(),
+                keyword: keyword.to_string(),
+                whole_word,
+            }],
+            statuses: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_keyword_regex() {
+        let regex = keyword_regex("cat", true).unwrap();
+        assert!(regex.is_match("A Cat sleeps"));
+        assert!(!regex.is_match("concatenate"));
+
+        let regex = keyword_regex("cat", false).unwrap();
+        assert!(regex.is_match("concatenate"));
+
+        let regex = keyword_regex("#rust", true).unwrap();
+        assert!(regex.is_match("love #rust!"));
+        assert!(!regex.is_match("love #rustacean"));
+
+        assert!(keyword_regex("  ", true).is_none());
+    }
+
+    #[test]
+    fn test_evaluate() {
+        let now = Utc::now();
+        let mut expired = filter("dog", false, FilterAction::Hide);
+        expired.expires_at = Some(now - Duration::minutes(1));
+        let filters = Filters::from_v2(&[filter("cat", true, FilterAction::Warn), expired]);
+
+        let results = filters.evaluate("cat and dog", &["100"], &FilterContext::Home, now);
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].keyword_matches, Some(vec!["cat".to_string()]));
+        assert_eq!(action(&results), Some(FilterAction::Warn));
+
+        let results = filters.evaluate("cat", &["100"], &FilterContext::Public, now);
+        assert!(results.is_empty());
+    }
+
+    #[test]
+    fn test_evaluate_status_matches() {
+        let mut hide = filter("unused", true, FilterAction::Hide);
+        hide.statuses = vec![entities::FilterStatus {
+            id: "20".to_string(),
+            status_id: "100".to_string(),
+        }];
+        let filters = Filters::from_v2(&[hide, filter("cat", false, FilterAction::Blur)]);
+
+        let results = filters.evaluate("cat", &["100"], &FilterContext::Home, Utc::now());
+        assert_eq!(results.len(), 2);
+        assert_eq!(results[0].status_matches, Some(vec!["20".to_string()]));
+        assert_eq!(action(&results), Some(FilterAction::Hide));
+    }
+
+    #[test]
+    fn test_html_to_text() {
+        assert_eq!(
+            html_to_text("<p>Hello<br />world &amp; <a href=\"x\">friends</a></p>"),
+            "Hello\nworld & friends\n"
+        );
+    }
+}
@@ -1,5 +1,5 @@
-use crate::default::DEFAULT_UA;
 use crate::error::{Error as MegalodonError, Kind};
+use crate::http::{ClientBuilder, HttpClient};
 use crate::response::Response;
 use reqwest::header::HeaderMap;
 use reqwest::Url;
@@ -12,7 +12,7 @@ use std::fmt::Debug;
 pub struct APIClient {
     access_token: Option<String>,
     base_url: String,
-    client: reqwest::Client,
+    client: HttpClient,
 }
 
 impl APIClient {
@@ -20,14 +20,9 @@ impl APIClient {
         base_url: String,
         access_token: Option<String>,
         user_agent: Option<String>,
+        client: &ClientBuilder,
     ) -> Result<Self, MegalodonError> {
-        let ua: String;
-        match user_agent {
-            Some(agent) => ua = agent,
-            None => ua = DEFAULT_UA.to_string(),
-        }
-
-        let client = reqwest::Client::builder().user_agent(ua).build()?;
+        let client = client.build(user_agent)?;
 
         Ok(Self {
             access_token,
@@ -55,7 +50,7 @@ impl APIClient {
             req = req.headers(headers);
         }
 
-        let res = req.send().await?;
+        let res = self.client.send(req).await?;
         let res_headers = res.headers().clone();
         let status = res.status();
         match status {
@@ -74,19 +69,17 @@ impl APIClient {
                 Some(res_headers),
             )),
             _ => match res.text().await {
-                Ok(text) => Err(MegalodonError::new_own(
+                Ok(text) => Err(MegalodonError::from_response(
                     text,
-                    Kind::HTTPStatusError,
-                    Some(url_str),
-                    Some(status.as_u16()),
-                    Some(res_headers),
+                    url_str,
+                    status.as_u16(),
+                    res_headers,
                 )),
-                Err(_err) => Err(MegalodonError::new_own(
+                Err(_err) => Err(MegalodonError::from_response(
                     "Unknown error".to_string(),
-                    Kind::HTTPStatusError,
-                    Some(url_str),
-                    Some(status.as_u16()),
-                    Some(res_headers),
+                    url_str,
+                    status.as_u16(),
+                    res_headers,
                 )),
             },
         }
@@ -112,7 +105,7 @@ impl APIClient {
             req = req.headers(headers);
         }
 
-        let res = req.json(params).send().await?;
+        let res = self.client.send(req.json(params)).await?;
         let res_headers = res.headers().clone();
         let status = res.status();
         match status {
@@ -124,19 +117,17 @@ impl APIClient {
                 Ok(res)
             }
             _ => match res.text().await {
-                Ok(text) => Err(MegalodonError::new_own(
+                Ok(text) => Err(MegalodonError::from_response(
                     text,
-                    Kind::HTTPStatusError,
-                    Some(url_str),
-                    Some(status.as_u16()),
-                    Some(res_headers),
+                    url_str,
+                    status.as_u16(),
+                    res_headers,
                 )),
-                Err(_err) => Err(MegalodonError::new_own(
+                Err(_err) => Err(MegalodonError::from_response(
                     "Unknown error".to_string(),
-                    Kind::HTTPStatusError,
-                    Some(url_str),
-                    Some(status.as_u16()),
-                    Some(res_headers),
+                    url_str,
+                    status.as_u16(),
+                    res_headers,
                 )),
             },
         }
@@ -162,7 +153,7 @@ impl APIClient {
             req = req.headers(headers);
         }
 
-        let res = req.multipart(params).send().await?;
+        let res = self.client.send(req.multipart(params)).await?;
         let res_headers = res.headers().clone();
         let status = res.status();
         match status {
@@ -174,19 +165,17 @@ impl APIClient {
                 Ok(res)
             }
             _ => match res.text().await {
-                Ok(text) => Err(MegalodonError::new_own(
+                Ok(text) => Err(MegalodonError::from_response(
                     text,
-                    Kind::HTTPStatusError,
-                    Some(url_str),
-                    Some(status.as_u16()),
-                    Some(res_headers),
+                    url_str,
+                    status.as_u16(),
+                    res_headers,
                 )),
-                Err(_err) => Err(MegalodonError::new_own(
+                Err(_err) => Err(MegalodonError::from_response(
                     "Unknown error".to_string(),
-                    Kind::HTTPStatusError,
-                    Some(url_str),
-                    Some(status.as_u16()),
-                    Some(res_headers),
+                    url_str,
+                    status.as_u16(),
+                    res_headers,
                 )),
             },
         }
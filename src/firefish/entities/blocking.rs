@@ -5,7 +5,7 @@ use super::UserDetail;
 #[derive(Debug, Deserialize, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct Blocking {
-    // id: String,
+    pub id: String,
     // created_at: DateTime<Utc>,
     // blockee_id: String,
     pub blockee: UserDetail,
@@ -5,7 +5,7 @@ use super::Note;
 #[derive(Debug, Deserialize, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct Favorite {
-    // id: String,
+    pub id: String,
     // created_at: String,
     // note_id: String,
     pub note: Note,
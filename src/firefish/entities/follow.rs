@@ -5,7 +5,7 @@ use super::UserDetail;
 #[derive(Debug, Deserialize, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct Follow {
-    // id: String,
+    pub id: String,
     // created_at: DateTime<Utc>,
     // followee_id: String,
     // follower_id: String,
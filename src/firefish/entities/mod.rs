@@ -22,6 +22,7 @@ pub mod reaction;
 pub mod relation;
 pub mod session;
 pub mod stats;
+pub mod translation;
 pub mod user;
 pub mod user_detail;
 
@@ -52,5 +53,6 @@ pub use relation::Relation;
 pub use session::Session;
 #[allow(unused_imports)]
 pub use stats::Stats;
+pub use translation::Translation;
 pub use user::User;
 pub use user_detail::UserDetail;
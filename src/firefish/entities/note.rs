@@ -117,6 +117,16 @@ fn convert_html(plain: String) -> String {
         .to_string()
 }
 
+impl Note {
+    // A renote which has its own text, files or poll is a quote.
+    pub(crate) fn is_quote(&self) -> bool {
+        self.renote.is_some()
+            && (self.text.is_some()
+                || self.files.as_ref().is_some_and(|f| !f.is_empty())
+                || self.poll.is_some())
+    }
+}
+
 impl From<Note> for MegalodonEntities::Status {
     fn from(val: Note) -> Self {
         let mut uri = "".to_string();
@@ -126,9 +136,10 @@ impl From<Note> for MegalodonEntities::Status {
         let mut reblog_status: Option<Box<MegalodonEntities::Status>> = None;
         let mut quote: Option<MegalodonEntities::QuotedStatus> = None;
 
+        let is_quote = val.is_quote();
         if let Some(renote) = val.renote {
             let rs: Note = *renote;
-            if let Some(_) = val.text.clone() {
+            if is_quote {
                 quote = Some(MegalodonEntities::QuotedStatus::Quote(
                     MegalodonEntities::quote::Quote {
                         state: MegalodonEntities::quote::QuoteState::Accepted,
@@ -209,6 +220,7 @@ impl From<Note> for MegalodonEntities::Status {
             quote,
             quote_approval: MegalodonEntities::QuoteApproval::automatic_unsupported(),
             bookmarked: None,
+            filtered: Vec::new(),
         }
     }
 }
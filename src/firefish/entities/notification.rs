@@ -10,7 +10,7 @@ use super::{reaction::map_reaction, Note, User};
 #[derive(Debug, Deserialize, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct Notification {
-    id: String,
+    pub(crate) id: String,
     created_at: DateTime<Utc>,
     // is_read: bool,
     pub r#type: NotificationType,
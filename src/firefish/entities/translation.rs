@@ -0,0 +1,22 @@
+use crate::entities as MegalodonEntities;
+use serde::Deserialize;
+
+#[derive(Debug, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct Translation {
+    source_lang: String,
+    text: String,
+}
+
+impl From<Translation> for MegalodonEntities::Translation {
+    fn from(val: Translation) -> Self {
+        MegalodonEntities::Translation {
+            content: val.text,
+            spoiler_text: "".to_string(),
+            poll: None,
+            media_attachments: Vec::new(),
+            detected_source_language: val.source_lang.to_lowercase(),
+            provider: "".to_string(),
+        }
+    }
+}
@@ -14,12 +14,15 @@ use super::{
     entities, oauth,
     web_socket::WebSocket,
 };
+use crate::SNS;
+use crate::capabilities::Capabilities;
+use crate::http::ClientBuilder;
 use crate::{
     Streaming, entities as MegalodonEntities,
     error::{self, Error},
     megalodon::{self, FollowRequestOutput},
     oauth as MegalodonOAuth,
-    response::Response,
+    response::{Pagination, Response},
 };
 
 /// Firefish API Client which satisfies megalodon trait.
@@ -38,7 +41,22 @@ impl Firefish {
         access_token: Option<String>,
         user_agent: Option<String>,
     ) -> Result<Firefish, Error> {
-        let client = APIClient::new(base_url.clone(), access_token.clone(), user_agent.clone())?;
+        Self::new_with_client(base_url, access_token, user_agent, ClientBuilder::default())
+    }
+
+    /// Create a new [`Firefish`] with the HTTP client settings.
+    pub fn new_with_client(
+        base_url: String,
+        access_token: Option<String>,
+        user_agent: Option<String>,
+        client: ClientBuilder,
+    ) -> Result<Firefish, Error> {
+        let client = APIClient::new(
+            base_url.clone(),
+            access_token.clone(),
+            user_agent.clone(),
+            &client,
+        )?;
         Ok(Firefish {
             client,
             base_url,
@@ -533,12 +551,14 @@ impl megalodon::Megalodon for Firefish {
             .client
             .post::<Vec<entities::Note>>("/api/users/notes", &params, None)
             .await?;
+        let pagination = Pagination::from_ids(res.json.iter().map(|i| i.id.as_str()));
         Ok(Response::<Vec<MegalodonEntities::Status>>::new(
             res.json.into_iter().map(|i| i.into()).collect(),
             res.status,
             res.status_text,
             res.header,
-        ))
+        )
+        .with_pagination(pagination))
     }
 
     async fn get_account_favourites(
@@ -602,12 +622,14 @@ impl megalodon::Megalodon for Firefish {
             .client
             .post::<Vec<entities::Follow>>("/api/users/followers", &params, None)
             .await?;
+        let pagination = Pagination::from_ids(res.json.iter().map(|i| i.id.as_str()));
         Ok(Response::<Vec<MegalodonEntities::Account>>::new(
             res.json.into_iter().map(|i| i.follower.into()).collect(),
             res.status,
             res.status_text,
             res.header,
-        ))
+        )
+        .with_pagination(pagination))
     }
 
     async fn get_account_following(
@@ -631,12 +653,14 @@ impl megalodon::Megalodon for Firefish {
             .client
             .post::<Vec<entities::Follow>>("/api/users/following", &params, None)
             .await?;
+        let pagination = Pagination::from_ids(res.json.iter().map(|i| i.id.as_str()));
         Ok(Response::<Vec<MegalodonEntities::Account>>::new(
             res.json.into_iter().map(|i| i.followee.into()).collect(),
             res.status,
             res.status_text,
             res.header,
-        ))
+        )
+        .with_pagination(pagination))
     }
 
     async fn get_account_lists(
@@ -927,12 +951,14 @@ impl megalodon::Megalodon for Firefish {
             .client
             .post::<Vec<entities::Favorite>>("/api/i/favorites", &params, None)
             .await?;
+        let pagination = Pagination::from_ids(res.json.iter().map(|i| i.id.as_str()));
         Ok(Response::<Vec<MegalodonEntities::Status>>::new(
             res.json.into_iter().map(|i| i.note.into()).collect(),
             res.status,
             res.status_text,
             res.header,
-        ))
+        )
+        .with_pagination(pagination))
     }
 
     async fn get_mutes(
@@ -955,12 +981,14 @@ impl megalodon::Megalodon for Firefish {
             .client
             .post::<Vec<entities::Mute>>("/api/mute/list", &params, None)
             .await?;
+        let pagination = Pagination::from_ids(res.json.iter().map(|i| i.id.as_str()));
         Ok(Response::<Vec<MegalodonEntities::Account>>::new(
             res.json.into_iter().map(|i| i.mutee.into()).collect(),
             res.status,
             res.status_text,
             res.header,
-        ))
+        )
+        .with_pagination(pagination))
     }
 
     async fn get_blocks(
@@ -983,12 +1011,14 @@ impl megalodon::Megalodon for Firefish {
             .client
             .post::<Vec<entities::Blocking>>("/api/blocking/list", &params, None)
             .await?;
+        let pagination = Pagination::from_ids(res.json.iter().map(|i| i.id.as_str()));
         Ok(Response::<Vec<MegalodonEntities::Account>>::new(
             res.json.into_iter().map(|i| i.blockee.into()).collect(),
             res.status,
             res.status_text,
             res.header,
-        ))
+        )
+        .with_pagination(pagination))
     }
 
     async fn get_domain_blocks(
@@ -1521,6 +1551,42 @@ impl megalodon::Megalodon for Firefish {
         ))
     }
 
+    async fn get_status_quotes(
+        &self,
+        id: String,
+        options: Option<&megalodon::GetStatusQuotesInputOptions>,
+    ) -> Result<Response<Vec<MegalodonEntities::Status>>, Error> {
+        let mut params = HashMap::<&str, Value>::from([("noteId", Value::String(id))]);
+        if let Some(options) = options {
+            if let Some(limit) = options.limit {
+                params.insert("limit", serde_json::Number::from(limit).into());
+            }
+            if let Some(max_id) = &options.max_id {
+                params.insert("untilId", Value::String(max_id.clone()));
+            }
+            if let Some(since_id) = &options.since_id {
+                params.insert("sinceId", Value::String(since_id.clone()));
+            }
+        }
+        let res = self
+            .client
+            .post::<Vec<entities::Note>>("/api/notes/renotes", &params, None)
+            .await?;
+        // Paginate over the whole page, so pure renotes don't stop the paging.
+        let pagination = Pagination::from_ids(res.json.iter().map(|i| i.id.as_str()));
+        Ok(Response::<Vec<MegalodonEntities::Status>>::new(
+            res.json
+                .into_iter()
+                .filter(|i| i.is_quote())
+                .map(|i| i.into())
+                .collect(),
+            res.status,
+            res.status_text,
+            res.header,
+        )
+        .with_pagination(pagination))
+    }
+
     async fn favourite_status(
         &self,
         id: String,
@@ -1643,6 +1709,36 @@ impl megalodon::Megalodon for Firefish {
         Ok(res)
     }
 
+    async fn translate_status(
+        &self,
+        id: String,
+        lang: Option<String>,
+    ) -> Result<Response<MegalodonEntities::Translation>, Error> {
+        let Some(lang) = lang else {
+            return Err(Error::new_own(
+                "lang is required".to_string(),
+                error::Kind::UnsatisfiedError,
+                None,
+                None,
+                None,
+            ));
+        };
+        let params = HashMap::<&str, Value>::from([
+            ("noteId", Value::String(id)),
+            ("targetLang", Value::String(lang)),
+        ]);
+        let res = self
+            .client
+            .post::<entities::Translation>("/api/notes/translate", &params, None)
+            .await?;
+        Ok(Response::<MegalodonEntities::Translation>::new(
+            res.json.into(),
+            res.status,
+            res.status_text,
+            res.header,
+        ))
+    }
+
     async fn upload_media_reader(
         &self,
         reader: Box<dyn AsyncRead + Sync + Send + Unpin>,
@@ -1852,12 +1948,14 @@ impl megalodon::Megalodon for Firefish {
             .client
             .post::<Vec<entities::Note>>("/api/notes/global-timeline", &params, None)
             .await?;
+        let pagination = Pagination::from_ids(res.json.iter().map(|i| i.id.as_str()));
         Ok(Response::<Vec<MegalodonEntities::Status>>::new(
             res.json.into_iter().map(|i| i.into()).collect(),
             res.status,
             res.status_text,
             res.header,
-        ))
+        )
+        .with_pagination(pagination))
     }
 
     async fn get_local_timeline(
@@ -1886,12 +1984,14 @@ impl megalodon::Megalodon for Firefish {
             .client
             .post::<Vec<entities::Note>>("/api/notes/local-timeline", &params, None)
             .await?;
+        let pagination = Pagination::from_ids(res.json.iter().map(|i| i.id.as_str()));
         Ok(Response::<Vec<MegalodonEntities::Status>>::new(
             res.json.into_iter().map(|i| i.into()).collect(),
             res.status,
             res.status_text,
             res.header,
-        ))
+        )
+        .with_pagination(pagination))
     }
 
     async fn get_tag_timeline(
@@ -1921,12 +2021,14 @@ impl megalodon::Megalodon for Firefish {
             .client
             .post::<Vec<entities::Note>>("/api/notes/search-by-tag", &params, None)
             .await?;
+        let pagination = Pagination::from_ids(res.json.iter().map(|i| i.id.as_str()));
         Ok(Response::<Vec<MegalodonEntities::Status>>::new(
             res.json.into_iter().map(|i| i.into()).collect(),
             res.status,
             res.status_text,
             res.header,
-        ))
+        )
+        .with_pagination(pagination))
     }
 
     async fn get_home_timeline(
@@ -1955,12 +2057,14 @@ impl megalodon::Megalodon for Firefish {
             .client
             .post::<Vec<entities::Note>>("/api/notes/timeline", &params, None)
             .await?;
+        let pagination = Pagination::from_ids(res.json.iter().map(|i| i.id.as_str()));
         Ok(Response::<Vec<MegalodonEntities::Status>>::new(
             res.json.into_iter().map(|i| i.into()).collect(),
             res.status,
             res.status_text,
             res.header,
-        ))
+        )
+        .with_pagination(pagination))
     }
 
     async fn get_list_timeline(
@@ -1987,12 +2091,14 @@ impl megalodon::Megalodon for Firefish {
             .client
             .post::<Vec<entities::Note>>("/api/notes/user-list-timeline", &params, None)
             .await?;
+        let pagination = Pagination::from_ids(res.json.iter().map(|i| i.id.as_str()));
         Ok(Response::<Vec<MegalodonEntities::Status>>::new(
             res.json.into_iter().map(|i| i.into()).collect(),
             res.status,
             res.status_text,
             res.header,
-        ))
+        )
+        .with_pagination(pagination))
     }
 
     async fn get_conversation_timeline(
@@ -2019,12 +2125,14 @@ impl megalodon::Megalodon for Firefish {
             .client
             .post::<Vec<entities::Note>>("/api/notes/mentions", &params, None)
             .await?;
+        let pagination = Pagination::from_ids(res.json.iter().map(|i| i.id.as_str()));
         Ok(Response::<Vec<MegalodonEntities::Conversation>>::new(
             res.json.into_iter().map(|i| i.into()).collect(),
             res.status,
             res.status_text,
             res.header,
-        ))
+        )
+        .with_pagination(pagination))
     }
 
     async fn delete_conversation(&self, _id: String) -> Result<Response<()>, Error> {
@@ -2236,6 +2344,7 @@ impl megalodon::Megalodon for Firefish {
             .client
             .post::<Vec<entities::Notification>>("/api/i/notifications", &params, None)
             .await?;
+        let pagination = Pagination::from_ids(res.json.iter().map(|i| i.id.as_str()));
         let notifications: Vec<MegalodonEntities::Notification> = res
             .json
             .into_iter()
@@ -2247,7 +2356,8 @@ impl megalodon::Megalodon for Firefish {
             res.status,
             res.status_text,
             res.header,
-        ))
+        )
+        .with_pagination(pagination))
     }
 
     async fn get_notification(
@@ -2397,6 +2507,21 @@ impl megalodon::Megalodon for Firefish {
         ))
     }
 
+    async fn get_instance_v2(&self) -> Result<Response<MegalodonEntities::InstanceV2>, Error> {
+        let res = self.get_instance().await?;
+        Ok(Response::<MegalodonEntities::InstanceV2>::new(
+            res.json.into(),
+            res.status,
+            res.status_text,
+            res.header,
+        ))
+    }
+
+    async fn capabilities(&self) -> Result<Capabilities, Error> {
+        let instance = self.get_instance_v2().await;
+        Ok(Capabilities::detect(SNS::Firefish, instance))
+    }
+
     async fn get_instance_peers(&self) -> Result<Response<Vec<String>>, Error> {
         Err(Error::new_own(
             "Firefish does not support get_instance_peers".to_string(),
@@ -2436,6 +2561,31 @@ impl megalodon::Megalodon for Firefish {
         ))
     }
 
+    async fn get_trending_statuses(
+        &self,
+        options: Option<&megalodon::GetTrendingStatusesInputOptions>,
+    ) -> Result<Response<Vec<MegalodonEntities::Status>>, Error> {
+        let mut params = HashMap::<&str, Value>::new();
+        if let Some(options) = options {
+            if let Some(limit) = options.limit {
+                params.insert("limit", serde_json::Number::from(limit).into());
+            }
+            if let Some(offset) = options.offset {
+                params.insert("offset", serde_json::Number::from(offset).into());
+            }
+        }
+        let res = self
+            .client
+            .post::<Vec<entities::Note>>("/api/notes/featured", &params, None)
+            .await?;
+        Ok(Response::<Vec<MegalodonEntities::Status>>::new(
+            res.json.into_iter().map(|i| i.into()).collect(),
+            res.status,
+            res.status_text,
+            res.header,
+        ))
+    }
+
     async fn get_instance_directory(
         &self,
         _options: Option<&megalodon::GetInstanceDirectoryInputOptions>,
@@ -2669,3 +2819,123 @@ impl megalodon::Megalodon for Firefish {
         Box::new(c)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::http::testing::serve;
+    use crate::megalodon::Megalodon;
+    use crate::pagination::{paginate, PaginateOptions};
+    use futures_util::StreamExt;
+
+    fn renote(id: &str, text: Option<&str>, files: Value, poll: Value) -> Value {
+        serde_json::json!({
+            "id": id,
+            "createdAt": "2024-01-01T00:00:00Z",
+            "user": {"id": "1", "username": "alice", "emojis": []},
+            "text": text,
+            "visibility": "public",
+            "renoteCount": 0,
+            "repliesCount": 0,
+            "reactions": {},
+            "files": files,
+            "poll": poll,
+            "renote": {
+                "id": "9a0",
+                "createdAt": "2024-01-01T00:00:00Z",
+                "user": {"id": "2", "username": "bob", "emojis": []},
+                "text": "original",
+                "visibility": "public",
+                "renoteCount": 4,
+                "repliesCount": 0,
+                "reactions": {}
+            }
+        })
+    }
+
+    #[tokio::test]
+    async fn test_get_status_quotes() {
+        let file = serde_json::json!([{
+            "id": "f1",
+            "type": "image/png",
+            "isSensitive": false,
+            "properties": {}
+        }]);
+        let poll = serde_json::json!({
+            "multiple": false,
+            "choices": [{"text": "yes", "votes": 0}]
+        });
+        let notes = serde_json::json!([
+            renote("9a4", Some("quote"), serde_json::json!([]), Value::Null),
+            renote("9a3", None, file, Value::Null),
+            renote("9a2", None, serde_json::json!([]), poll),
+            renote("9a1", None, serde_json::json!([]), Value::Null),
+        ]);
+        let (url, mut requests) = serve(vec![notes.to_string()]).await;
+        let client = Firefish::new(url, None, None).unwrap();
+        let options = megalodon::GetStatusQuotesInputOptions {
+            limit: Some(4),
+            max_id: Some(String::from("9a5")),
+            ..Default::default()
+        };
+        let res = client
+            .get_status_quotes(String::from("9a0"), Some(&options))
+            .await
+            .unwrap();
+
+        // The pure renote is not a quote, but it still moves the cursor.
+        let ids: Vec<&str> = res.json.iter().map(|s| s.id.as_str()).collect();
+        assert_eq!(ids, vec!["9a4", "9a3", "9a2"]);
+        assert!(res.json.iter().all(|s| s.quote.is_some()));
+        assert_eq!(res.pagination.next.unwrap().max_id.as_deref(), Some("9a1"));
+        assert_eq!(
+            res.pagination.prev.unwrap().since_id.as_deref(),
+            Some("9a4")
+        );
+        let request = requests.recv().await.unwrap();
+        assert_eq!(request.method, "POST");
+        assert_eq!(request.path, "/api/notes/renotes");
+        assert_eq!(
+            request.json(),
+            serde_json::json!({"noteId": "9a0", "limit": 4, "untilId": "9a5"})
+        );
+    }
+
+    #[tokio::test]
+    async fn test_paginate_status_quotes() {
+        let pure = |id| renote(id, None, serde_json::json!([]), Value::Null);
+        let quote = |id| renote(id, Some("quote"), serde_json::json!([]), Value::Null);
+        let (url, mut requests) = serve(vec![
+            serde_json::json!([pure("9a4"), pure("9a3")]).to_string(),
+            serde_json::json!([quote("9a2")]).to_string(),
+            String::from("[]"),
+        ])
+        .await;
+        let client = Firefish::new(url, None, None).unwrap();
+        let stream = paginate(PaginateOptions::default(), |page| {
+            let client = &client;
+            async move {
+                let options = megalodon::GetStatusQuotesInputOptions {
+                    max_id: page.max_id,
+                    ..Default::default()
+                };
+                client
+                    .get_status_quotes(String::from("9a0"), Some(&options))
+                    .await
+            }
+        });
+        let ids: Vec<String> = stream.map(|s| s.unwrap().id).collect().await;
+
+        // The page of pure renotes is empty, but paging goes on.
+        assert_eq!(ids, vec!["9a2"]);
+        let until_ids: Vec<Value> = vec![
+            requests.recv().await.unwrap().json()["untilId"].clone(),
+            requests.recv().await.unwrap().json()["untilId"].clone(),
+            requests.recv().await.unwrap().json()["untilId"].clone(),
+        ];
+        assert_eq!(
+            until_ids,
+            vec![Value::Null, Value::from("9a3"), Value::from("9a2")]
+        );
+    }
+}
@@ -1,34 +1,25 @@
-use std::fmt;
 use std::future::Future;
 use std::pin::Pin;
-use std::thread;
 use std::time::Duration;
 
 use super::entities;
 use crate::default::DEFAULT_UA;
 use crate::error::{Error, Kind};
-use crate::streaming::{Message, Streaming};
+use crate::streaming::{
+    CaptureCommand, ConnectionError, EVENT_CHANNEL_CAPACITY, EventStream, Message,
+    ReconnectPolicy, Reconnection, StreamEvent, StreamHandle, Streaming, Transport, event_stream,
+};
+use crate::web_socket::{self, Socket};
 use async_trait::async_trait;
-use futures_util::{SinkExt, StreamExt};
+use futures_util::SinkExt;
 use serde::Deserialize;
 use serde_json::json;
-use tokio::net::TcpStream;
-use tokio_tungstenite::{
-    MaybeTlsStream, WebSocketStream, connect_async_tls_with_config,
-    tungstenite::{
-        Error as WebSocketError,
-        client::IntoClientRequest,
-        http::StatusCode,
-        protocol::{Message as WebSocketMessage, frame::coding::CloseCode},
-    },
-};
-use tracing::{debug, error, info, warn};
-use url::Url;
+use tokio::sync::mpsc;
+use tokio_tungstenite::tungstenite::protocol::Message as WebSocketMessage;
+use tokio_util::sync::CancellationToken;
+use tracing::{debug, error, warn};
 use uuid::Uuid;
 
-const RECONNECT_INTERVAL: u64 = 5000;
-const READ_MESSAGE_TIMEOUT_SECONDS: u64 = 60;
-
 #[derive(Debug, Clone)]
 pub struct WebSocket {
     url: String,
@@ -37,6 +28,7 @@ pub struct WebSocket {
     access_token: Option<String>,
     user_agent: String,
     channel_id: String,
+    reconnect_policy: ReconnectPolicy,
 }
 
 #[derive(Deserialize)]
@@ -52,6 +44,20 @@ struct MessageBody {
     body: serde_json::Value,
 }
 
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct NoteReaction {
+    reaction: String,
+    user_id: String,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct NotePollVote {
+    choice: u32,
+    user_id: String,
+}
+
 impl WebSocket {
     pub fn new(
         url: String,
@@ -72,6 +78,7 @@ impl WebSocket {
             access_token,
             user_agent: ua,
             channel_id: Uuid::new_v4().to_string(),
+            reconnect_policy: ReconnectPolicy::default(),
         }
     }
 
@@ -83,6 +90,9 @@ impl WebSocket {
             let Ok(mes) = serde_json::from_str::<RawMessage>(text) else {
                 return Ok(Message::Heartbeat());
             };
+            if mes.r#type == "noteUpdated" {
+                return Self::parse_note_updated(mes.body);
+            }
             if mes.r#type != "channel" || mes.body.id != self.channel_id {
                 return Ok(Message::Heartbeat());
             }
@@ -125,8 +135,11 @@ impl WebSocket {
                     Ok(Message::Conversation(res.into()))
                 }
                 unknown => {
-                    warn!("Unknown body type message is received: {}", unknown);
-                    Ok(Message::Heartbeat())
+                    debug!("Unknown body type message is received: {}", unknown);
+                    Ok(Message::Unknown {
+                        event: unknown.to_string(),
+                        payload: mes.body.body.to_string(),
+                    })
                 }
             }
         } else {
@@ -140,34 +153,79 @@ impl WebSocket {
         }
     }
 
+    /// Parse `noteUpdated` event, whose body has the ID of the note and the type of the update.
+    fn parse_note_updated(body: MessageBody) -> Result<Message, Error> {
+        match &*body.r#type {
+            "reacted" | "unreacted" => {
+                let res =
+                    serde_json::from_value::<NoteReaction>(body.body.clone()).map_err(|e| {
+                        error!(
+                            "failed to parse reaction: {}\n{}",
+                            e.to_string(),
+                            &body.body
+                        );
+                        e
+                    })?;
+                if body.r#type == "reacted" {
+                    Ok(Message::Reacted {
+                        status_id: body.id,
+                        name: res.reaction,
+                        account_id: res.user_id,
+                    })
+                } else {
+                    Ok(Message::Unreacted {
+                        status_id: body.id,
+                        name: res.reaction,
+                        account_id: res.user_id,
+                    })
+                }
+            }
+            "pollVoted" => {
+                let res =
+                    serde_json::from_value::<NotePollVote>(body.body.clone()).map_err(|e| {
+                        error!(
+                            "failed to parse poll vote: {}\n{}",
+                            e.to_string(),
+                            &body.body
+                        );
+                        e
+                    })?;
+                Ok(Message::PollVoted {
+                    status_id: body.id,
+                    choice: res.choice,
+                    account_id: res.user_id,
+                })
+            }
+            "deleted" => Ok(Message::Delete(body.id)),
+            unknown => Ok(Message::Unknown {
+                event: format!("noteUpdated:{}", unknown),
+                payload: body.body.to_string(),
+            }),
+        }
+    }
+
     async fn connect(
         &self,
         url: &str,
-        callback: Box<
-            dyn Fn(Message) -> Pin<Box<dyn Future<Output = ()> + Send>> + Send + Sync + '_,
-        >,
+        tx: mpsc::Sender<StreamEvent>,
+        token: CancellationToken,
+        mut captures: mpsc::UnboundedReceiver<CaptureCommand>,
     ) {
+        let mut captured = Vec::<String>::new();
+        let mut reconnection = Reconnection::new(url, &self.reconnect_policy, &tx, &token);
         loop {
-            match self.do_connect(url, &callback).await {
-                Ok(()) => {
-                    warn!("connection for {} is closed, reconnecting...", url);
-                    thread::sleep(Duration::from_millis(RECONNECT_INTERVAL));
-                    continue;
-                }
-                Err(err) => match err.kind {
-                    InnerKind::ConnectionError
-                    | InnerKind::SocketReadError
-                    | InnerKind::UnusualSocketCloseError
-                    | InnerKind::TimeoutError => {
-                        thread::sleep(Duration::from_millis(RECONNECT_INTERVAL));
-                        info!("Reconnecting to {}", url);
-                        continue;
-                    }
-                    InnerKind::UnauthorizedError => {
-                        info!("Unauthorized so give up");
-                        return;
-                    }
-                },
+            let result = self
+                .do_connect(
+                    url,
+                    &tx,
+                    &token,
+                    &mut captures,
+                    &mut captured,
+                    &mut reconnection,
+                )
+                .await;
+            if !reconnection.retry(result).await {
+                return;
             }
         }
     }
@@ -175,84 +233,63 @@ impl WebSocket {
     async fn do_connect(
         &self,
         url: &str,
-        callback: &Box<
-            dyn Fn(Message) -> Pin<Box<dyn Future<Output = ()> + Send>> + Send + Sync + '_,
-        >,
-    ) -> Result<(), InnerError> {
-        let mut req = Url::parse(url)
-            .unwrap()
-            .into_client_request()
-            .map_err(|e| {
-                error!("Failed to parse url: {}", e);
-                InnerError::new(InnerKind::ConnectionError)
-            })?;
-        req.headers_mut()
-            .insert("User-Agent", self.user_agent.parse().unwrap());
-        let connector = crate::tls::build_connector();
-        let (socket, response) =
-            connect_async_tls_with_config(req, None, false, connector).await.map_err(|e| {
-            error!("Failed to connect: {}", e);
-            match e {
-                WebSocketError::Http(response) => match response.status() {
-                    StatusCode::UNAUTHORIZED => InnerError::new(InnerKind::UnauthorizedError),
-                    _ => InnerError::new(InnerKind::ConnectionError),
-                },
-                _ => InnerError::new(InnerKind::ConnectionError),
-            }
-        })?;
-
-        debug!("Connected to {}", url);
-        debug!("Response HTTP code: {}", response.status());
-        debug!("Response contains the following headers:");
-        for (ref header, _value) in response.headers() {
-            debug!("* {}", header);
-        }
+        tx: &mpsc::Sender<StreamEvent>,
+        token: &CancellationToken,
+        captures: &mut mpsc::UnboundedReceiver<CaptureCommand>,
+        captured: &mut Vec<String>,
+        reconnection: &mut Reconnection<'_, Message>,
+    ) -> Result<(), ConnectionError> {
+        let Some(socket) = web_socket::open(url, &self.user_agent, token).await? else {
+            return Ok(());
+        };
 
         let mut socket = self.connect_channel(socket).await;
+        reconnection.connected().await;
+
+        // Captures are lost with the previous connection, so send all of them again.
+        while let Ok(command) = captures.try_recv() {
+            apply_capture(captured, command);
+        }
+        for id in captured.iter() {
+            let data = capture_message(&CaptureCommand::Subscribe(id.clone()));
+            socket
+                .send(WebSocketMessage::Text(data.to_string().into()))
+                .await
+                .map_err(|e| {
+                    error!("Failed to subscribe note: {}", e);
+                    ConnectionError::Connection
+                })?;
+        }
 
         loop {
-            let res = tokio::time::timeout(
-                Duration::from_secs(READ_MESSAGE_TIMEOUT_SECONDS),
-                socket.next(),
-            )
-            .await
-            .map_err(|e| {
-                error!("Timeout reading message: {}", e);
-                InnerError::new(InnerKind::TimeoutError)
-            })?;
-            let Some(r) = res else {
-                warn!("WebSocket stream has ended");
-                return Err(InnerError::new(InnerKind::SocketReadError));
-            };
-            let msg = r.map_err(|e| {
-                error!("Failed to read message: {}", e);
-                InnerError::new(InnerKind::SocketReadError)
-            })?;
-            if msg.is_ping() {
-                let _ = socket
-                    .send(WebSocketMessage::Pong(Vec::<u8>::new().into()))
-                    .await
-                    .map_err(|e| {
-                        error!("{:#?}", e);
-                        e
-                    });
-            }
-            if msg.is_close() {
-                let _ = socket.close(None).await.map_err(|e| {
-                    error!("{:#?}", e);
-                    e
-                });
-                if let WebSocketMessage::Close(Some(close)) = msg {
-                    warn!("Connection to {} is closed because {}", url, close.code);
-                    if close.code != CloseCode::Normal {
-                        return Err(InnerError::new(InnerKind::UnusualSocketCloseError));
-                    }
+            let msg = tokio::select! {
+                _ = token.cancelled() => {
+                    web_socket::close(&mut socket).await;
+                    return Ok(());
                 }
+                Some(command) = captures.recv() => {
+                    let data = capture_message(&command);
+                    apply_capture(captured, command);
+                    socket
+                        .send(WebSocketMessage::Text(data.to_string().into()))
+                        .await
+                        .map_err(|e| {
+                            error!("Failed to send note capture: {}", e);
+                            ConnectionError::SocketRead
+                        })?;
+                    continue;
+                }
+                msg = web_socket::receive(&mut socket, url) => msg?,
+            };
+            let Some(msg) = msg else {
                 return Ok(());
-            }
+            };
             match self.parse(msg) {
                 Ok(message) => {
-                    callback(message).await;
+                    if tx.send(StreamEvent::Message(message)).await.is_err() {
+                        // The receiver has been dropped, so nobody listens this connection.
+                        token.cancel();
+                    }
                 }
                 Err(err) => {
                     warn!("{}", err);
@@ -263,8 +300,8 @@ impl WebSocket {
 
     async fn connect_channel(
         &self,
-        mut socket: WebSocketStream<MaybeTlsStream<TcpStream>>,
-    ) -> WebSocketStream<MaybeTlsStream<TcpStream>> {
+        mut socket: Socket,
+    ) -> Socket {
         match self.channel.as_ref() {
             "conversation" => {
                 let data = json!({
@@ -360,6 +397,16 @@ impl WebSocket {
         }
         socket
     }
+
+    fn streaming_url(&self) -> String {
+        let mut parameter = Vec::<String>::new();
+        if let Some(access_token) = &self.access_token {
+            parameter.push(format!("i={}", access_token));
+        }
+        let mut url = self.url.clone();
+        url = url + "?" + parameter.join("&").as_str();
+        url
+    }
 }
 
 #[async_trait]
@@ -377,48 +424,150 @@ impl Streaming for WebSocket {
                 + 'async_trait,
         >,
     ) {
-        let mut parameter = Vec::<String>::new();
-        if let Some(access_token) = &self.access_token {
-            parameter.push(format!("i={}", access_token));
-        }
-        let mut url = self.url.clone();
-        url = url + "?" + parameter.join("&").as_str();
+        let url = self.streaming_url();
+        let (tx, mut rx) = mpsc::channel(EVENT_CHANNEL_CAPACITY);
+        let token = CancellationToken::new();
+
+        // Nobody can capture notes without a handle, so the sender is dropped immediately.
+        let (_, captures) = mpsc::unbounded_channel();
 
-        self.connect(url.as_str(), callback).await;
+        tokio::join!(self.connect(url.as_str(), tx, token, captures), async {
+            while let Some(event) = rx.recv().await {
+                if let StreamEvent::Message(message) = event {
+                    callback(message).await;
+                }
+            }
+        });
     }
-}
 
-#[derive(thiserror::Error)]
-#[error("{kind}")]
-struct InnerError {
-    kind: InnerKind,
+    fn stream(&self) -> (EventStream, StreamHandle) {
+        let url = self.streaming_url();
+        let (tx, rx) = mpsc::channel(EVENT_CHANNEL_CAPACITY);
+        let token = CancellationToken::new();
+        let (captures_tx, captures) = mpsc::unbounded_channel();
+        let ws = self.clone();
+        let connection_token = token.clone();
+        tokio::spawn(async move {
+            ws.connect(url.as_str(), tx, connection_token, captures)
+                .await;
+        });
+
+        (
+            event_stream(rx),
+            StreamHandle::with_captures(token, captures_tx),
+        )
+    }
+
+    fn set_reconnect_policy(&mut self, policy: ReconnectPolicy) {
+        self.reconnect_policy = policy;
+    }
+
+    fn set_transport(&mut self, _transport: Transport) {}
+
+    fn set_poll_interval(&mut self, _interval: Duration) {}
 }
 
-#[derive(Debug, thiserror::Error)]
-enum InnerKind {
-    #[error("connection error")]
-    ConnectionError,
-    #[error("socket read error")]
-    SocketReadError,
-    #[error("unusual socket close error")]
-    UnusualSocketCloseError,
-    #[error("timeout error")]
-    TimeoutError,
-    #[error("unauthorized error")]
-    UnauthorizedError,
+fn apply_capture(captured: &mut Vec<String>, command: CaptureCommand) {
+    match command {
+        CaptureCommand::Subscribe(id) => {
+            if !captured.contains(&id) {
+                captured.push(id);
+            }
+        }
+        CaptureCommand::Unsubscribe(id) => {
+            captured.retain(|c| c != &id);
+        }
+    }
 }
 
-impl InnerError {
-    pub fn new(kind: InnerKind) -> Self {
-        Self { kind }
+fn capture_message(command: &CaptureCommand) -> serde_json::Value {
+    match command {
+        CaptureCommand::Subscribe(id) => json!({
+            "type": "subNote",
+            "body": {
+                "id": id,
+            },
+        }),
+        CaptureCommand::Unsubscribe(id) => json!({
+            "type": "unsubNote",
+            "body": {
+                "id": id,
+            },
+        }),
     }
 }
 
-impl fmt::Debug for InnerError {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        let mut builder = f.debug_struct("megalodon::mastodon::web_socket::InnerError");
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::streaming::testing;
+
+    fn new_web_socket(url: String) -> WebSocket {
+        WebSocket::new(url, String::from("user"), None, None, None)
+    }
+
+    #[test]
+    fn test_parse_note_updated() {
+        let body = MessageBody {
+            id: String::from("9abc"),
+            r#type: String::from("pollVoted"),
+            body: json!({ "choice": 1, "userId": "9xyz" }),
+        };
+        match WebSocket::parse_note_updated(body) {
+            Ok(Message::PollVoted {
+                status_id,
+                choice,
+                account_id,
+            }) => {
+                assert_eq!(status_id, "9abc");
+                assert_eq!(choice, 1);
+                assert_eq!(account_id, "9xyz");
+            }
+            other => panic!("unexpected message: {:?}", other),
+        }
+
+        let body = MessageBody {
+            id: String::from("9abc"),
+            r#type: String::from("deleted"),
+            body: json!({ "deletedAt": "2024-01-01T00:00:00.000Z" }),
+        };
+        match WebSocket::parse_note_updated(body) {
+            Ok(Message::Delete(id)) => assert_eq!(id, "9abc"),
+            other => panic!("unexpected message: {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_apply_capture() {
+        let mut captured = Vec::new();
+        apply_capture(&mut captured, CaptureCommand::Subscribe(String::from("1")));
+        apply_capture(&mut captured, CaptureCommand::Subscribe(String::from("2")));
+        apply_capture(&mut captured, CaptureCommand::Subscribe(String::from("1")));
+        assert_eq!(captured, vec![String::from("1"), String::from("2")]);
+
+        apply_capture(
+            &mut captured,
+            CaptureCommand::Unsubscribe(String::from("1")),
+        );
+        assert_eq!(captured, vec![String::from("2")]);
+        assert_eq!(
+            capture_message(&CaptureCommand::Unsubscribe(String::from("2"))),
+            json!({ "type": "unsubNote", "body": { "id": "2" } })
+        );
+    }
+
+    #[tokio::test]
+    async fn test_stream_reconnect_lifecycle() {
+        testing::assert_reconnect_lifecycle(new_web_socket).await;
+    }
+
+    #[tokio::test]
+    async fn test_stream_handle_close() {
+        testing::assert_handle_close(new_web_socket).await;
+    }
 
-        builder.field("kind", &self.kind);
-        builder.finish()
+    #[tokio::test]
+    async fn test_stream_receiver_dropped() {
+        testing::assert_receiver_dropped(new_web_socket).await;
     }
 }
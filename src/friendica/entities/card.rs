@@ -1,3 +1,4 @@
+use super::History;
 use crate::entities as MegalodonEntities;
 use serde::{Deserialize, Serialize};
 
@@ -18,6 +19,13 @@ pub struct Card {
     blurhash: Option<String>,
 }
 
+#[derive(Debug, Deserialize, Clone)]
+pub struct TrendsLink {
+    #[serde(flatten)]
+    card: Card,
+    history: Vec<History>,
+}
+
 #[derive(Debug, Clone, Deserialize, Serialize)]
 #[serde(rename_all = "lowercase")]
 pub enum CardType {
@@ -55,6 +63,16 @@ impl From<Card> for MegalodonEntities::Card {
             height: Some(val.height),
             embed_url: None,
             blurhash: val.blurhash,
+            authors: Vec::new(),
+        }
+    }
+}
+
+impl From<TrendsLink> for MegalodonEntities::TrendsLink {
+    fn from(val: TrendsLink) -> Self {
+        MegalodonEntities::TrendsLink {
+            card: val.card.into(),
+            history: val.history.into_iter().map(|h| h.into()).collect(),
         }
     }
 }
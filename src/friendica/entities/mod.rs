@@ -28,6 +28,7 @@ pub mod scheduled_status;
 pub mod source;
 pub mod stats;
 pub mod status;
+pub mod status_edit;
 pub mod status_params;
 pub mod tag;
 pub mod token;
@@ -38,7 +39,7 @@ pub use account::Account;
 pub use activity::Activity;
 pub use application::Application;
 pub use attachment::Attachment;
-pub use card::Card;
+pub use card::{Card, TrendsLink};
 pub use context::Context;
 pub use conversation::Conversation;
 pub use emoji::Emoji;
@@ -67,6 +68,7 @@ pub use scheduled_status::ScheduledStatus;
 pub use source::Source;
 pub use stats::Stats;
 pub use status::{Status, StatusVisibility};
+pub use status_edit::StatusEdit;
 pub use status_params::StatusParams;
 pub use tag::Tag;
 #[allow(unused_imports)]
@@ -168,6 +168,7 @@ impl From<Status> for MegalodonEntities::Status {
             quote,
             quote_approval: MegalodonEntities::QuoteApproval::default(),
             bookmarked: val.bookmarked,
+            filtered: Vec::new(),
         }
     }
 }
@@ -1,8 +1,11 @@
 use super::api_client::APIClient;
 use super::entities;
 use super::oauth;
-use super::web_socket::WebSocket;
+use crate::SNS;
+use crate::capabilities::Capabilities;
+use crate::http::{ClientBuilder, idempotency_header};
 use crate::megalodon::FollowRequestOutput;
+use crate::polling::{Polling, Source};
 use crate::{Streaming, error};
 use crate::{
     default, entities as MegalodonEntities, error::Error, megalodon, oauth as MegalodonOAuth,
@@ -19,6 +22,7 @@ use reqwest::header::HeaderMap;
 use serde_json::Value;
 use sha1::{Digest, Sha1};
 use std::collections::HashMap;
+use std::sync::Arc;
 use tokio::{fs::File, io::AsyncRead};
 use tokio_util::codec::{BytesCodec, FramedRead};
 
@@ -36,7 +40,17 @@ impl Friendica {
         access_token: Option<String>,
         user_agent: Option<String>,
     ) -> Result<Friendica, Error> {
-        let client = APIClient::new(base_url.clone(), access_token, user_agent)?;
+        Self::new_with_client(base_url, access_token, user_agent, ClientBuilder::default())
+    }
+
+    /// Create a new [`Friendica`] with the HTTP client settings.
+    pub fn new_with_client(
+        base_url: String,
+        access_token: Option<String>,
+        user_agent: Option<String>,
+        client: ClientBuilder,
+    ) -> Result<Friendica, Error> {
+        let client = APIClient::new(base_url.clone(), access_token, user_agent, &client)?;
         Ok(Friendica { client, base_url })
     }
 
@@ -1278,10 +1292,12 @@ impl megalodon::Megalodon for Friendica {
             }
         }
 
+        let headers = idempotency_header(options.and_then(|o| o.idempotency_key.as_deref()))?;
+
         if params.contains_key("scheduled_at") {
             let res = self
                 .client
-                .post::<entities::ScheduledStatus>("/api/v1/statuses", &params, None)
+                .post::<entities::ScheduledStatus>("/api/v1/statuses", &params, Some(headers))
                 .await?;
 
             Ok(Response::<megalodon::PostStatusOutput>::new(
@@ -1293,7 +1309,7 @@ impl megalodon::Megalodon for Friendica {
         } else {
             let res = self
                 .client
-                .post::<entities::Status>("/api/v1/statuses", &params, None)
+                .post::<entities::Status>("/api/v1/statuses", &params, Some(headers))
                 .await?;
 
             Ok(Response::<megalodon::PostStatusOutput>::new(
@@ -1333,6 +1349,26 @@ impl megalodon::Megalodon for Friendica {
         ))
     }
 
+    async fn get_status_history(
+        &self,
+        id: String,
+    ) -> Result<Response<Vec<MegalodonEntities::StatusEdit>>, Error> {
+        let res = self
+            .client
+            .get::<Vec<entities::StatusEdit>>(
+                format!("/api/v1/statuses/{}/history", id).as_str(),
+                None,
+            )
+            .await?;
+
+        Ok(Response::<Vec<MegalodonEntities::StatusEdit>>::new(
+            res.json.into_iter().map(|j| j.into()).collect(),
+            res.status,
+            res.status_text,
+            res.header,
+        ))
+    }
+
     async fn edit_status(
         &self,
         id: String,
@@ -2603,6 +2639,21 @@ impl megalodon::Megalodon for Friendica {
         ))
     }
 
+    async fn get_instance_v2(&self) -> Result<Response<MegalodonEntities::InstanceV2>, Error> {
+        let res = self.get_instance().await?;
+        Ok(Response::<MegalodonEntities::InstanceV2>::new(
+            res.json.into(),
+            res.status,
+            res.status_text,
+            res.header,
+        ))
+    }
+
+    async fn capabilities(&self) -> Result<Capabilities, Error> {
+        let instance = self.get_instance_v2().await;
+        Ok(Capabilities::detect(SNS::Friendica, instance))
+    }
+
     async fn get_instance_peers(&self) -> Result<Response<Vec<String>>, Error> {
         let res = self
             .client
@@ -2648,6 +2699,66 @@ impl megalodon::Megalodon for Friendica {
         ))
     }
 
+    async fn get_trending_statuses(
+        &self,
+        options: Option<&megalodon::GetTrendingStatusesInputOptions>,
+    ) -> Result<Response<Vec<MegalodonEntities::Status>>, Error> {
+        let mut params = Vec::<String>::new();
+        if let Some(options) = options {
+            if let Some(limit) = options.limit {
+                params.push(format!("limit={}", limit));
+            }
+            if let Some(offset) = options.offset {
+                params.push(format!("offset={}", offset));
+            }
+        }
+        let mut path = "/api/v1/trends/statuses".to_string();
+        if !params.is_empty() {
+            path = path + "?" + params.join("&").as_str();
+        }
+        let res = self
+            .client
+            .get::<Vec<entities::Status>>(path.as_str(), None)
+            .await?;
+
+        Ok(Response::<Vec<MegalodonEntities::Status>>::new(
+            res.json.into_iter().map(|j| j.into()).collect(),
+            res.status,
+            res.status_text,
+            res.header,
+        ))
+    }
+
+    async fn get_trending_links(
+        &self,
+        options: Option<&megalodon::GetTrendingLinksInputOptions>,
+    ) -> Result<Response<Vec<MegalodonEntities::TrendsLink>>, Error> {
+        let mut params = Vec::<String>::new();
+        if let Some(options) = options {
+            if let Some(limit) = options.limit {
+                params.push(format!("limit={}", limit));
+            }
+            if let Some(offset) = options.offset {
+                params.push(format!("offset={}", offset));
+            }
+        }
+        let mut path = "/api/v1/trends/links".to_string();
+        if !params.is_empty() {
+            path = path + "?" + params.join("&").as_str();
+        }
+        let res = self
+            .client
+            .get::<Vec<entities::TrendsLink>>(path.as_str(), None)
+            .await?;
+
+        Ok(Response::<Vec<MegalodonEntities::TrendsLink>>::new(
+            res.json.into_iter().map(|j| j.into()).collect(),
+            res.status,
+            res.status_text,
+            res.header,
+        ))
+    }
+
     async fn get_instance_directory(
         &self,
         options: Option<&megalodon::GetInstanceDirectoryInputOptions>,
@@ -2817,37 +2928,37 @@ impl megalodon::Megalodon for Friendica {
     }
 
     async fn user_streaming(&self) -> Box<dyn Streaming + Send + Sync> {
-        let c = WebSocket::new();
+        let c = Polling::new(Arc::new(self.clone()), Source::User);
 
         Box::new(c)
     }
 
     async fn public_streaming(&self) -> Box<dyn Streaming + Send + Sync> {
-        let c = WebSocket::new();
+        let c = Polling::new(Arc::new(self.clone()), Source::Public);
 
         Box::new(c)
     }
 
     async fn local_streaming(&self) -> Box<dyn Streaming + Send + Sync> {
-        let c = WebSocket::new();
+        let c = Polling::new(Arc::new(self.clone()), Source::Local);
 
         Box::new(c)
     }
 
     async fn direct_streaming(&self) -> Box<dyn Streaming + Send + Sync> {
-        let c = WebSocket::new();
+        let c = Polling::new(Arc::new(self.clone()), Source::Direct);
 
         Box::new(c)
     }
 
-    async fn tag_streaming(&self, _tag: String) -> Box<dyn Streaming + Send + Sync> {
-        let c = WebSocket::new();
+    async fn tag_streaming(&self, tag: String) -> Box<dyn Streaming + Send + Sync> {
+        let c = Polling::new(Arc::new(self.clone()), Source::Tag(tag));
 
         Box::new(c)
     }
 
-    async fn list_streaming(&self, _list_id: String) -> Box<dyn Streaming + Send + Sync> {
-        let c = WebSocket::new();
+    async fn list_streaming(&self, list_id: String) -> Box<dyn Streaming + Send + Sync> {
+        let c = Polling::new(Arc::new(self.clone()), Source::List(list_id));
 
         Box::new(c)
     }
@@ -4,6 +4,5 @@ mod api_client;
 mod entities;
 pub mod friendica;
 mod oauth;
-mod web_socket;
 
 pub use friendica::Friendica;
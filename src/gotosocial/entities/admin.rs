@@ -0,0 +1,188 @@
+use super::{report::Category, Role, Status};
+use crate::entities as MegalodonEntities;
+use chrono::{DateTime, Utc};
+use serde::Deserialize;
+
+#[derive(Debug, Deserialize, Clone)]
+pub struct Account {
+    id: String,
+    username: String,
+    domain: Option<String>,
+    created_at: Option<DateTime<Utc>>,
+    email: Option<String>,
+    ip: Option<String>,
+    #[serde(default)]
+    ips: Vec<Ip>,
+    locale: Option<String>,
+    invite_request: Option<String>,
+    role: Option<Role>,
+    confirmed: bool,
+    approved: bool,
+    disabled: bool,
+    silenced: bool,
+    suspended: bool,
+    account: Option<super::Account>,
+}
+
+#[derive(Debug, Deserialize, Clone)]
+pub struct Ip {
+    ip: String,
+    used_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Deserialize, Clone)]
+pub struct Report {
+    id: String,
+    action_taken: bool,
+    action_taken_at: Option<DateTime<Utc>>,
+    category: Category,
+    comment: String,
+    forwarded: bool,
+    created_at: DateTime<Utc>,
+    updated_at: Option<DateTime<Utc>>,
+    account: Account,
+    target_account: Account,
+    assigned_account: Option<Account>,
+    action_taken_by_account: Option<Account>,
+    #[serde(default)]
+    statuses: Vec<Status>,
+    #[serde(default)]
+    rules: Vec<Rule>,
+}
+
+#[derive(Debug, Deserialize, Clone)]
+pub struct Rule {
+    id: String,
+    text: String,
+}
+
+// GoToSocial blocks and allows domains with the same entity.
+#[derive(Debug, Deserialize, Clone)]
+pub struct DomainPermission {
+    id: String,
+    domain: String,
+    obfuscate: Option<bool>,
+    private_comment: Option<String>,
+    public_comment: Option<String>,
+    created_at: DateTime<Utc>,
+}
+
+impl From<Account> for MegalodonEntities::admin::Account {
+    fn from(val: Account) -> Self {
+        MegalodonEntities::admin::Account {
+            id: val.id,
+            username: val.username,
+            domain: val.domain,
+            created_at: val.created_at,
+            email: val.email,
+            ip: val.ip,
+            ips: val.ips.into_iter().map(|i| i.into()).collect(),
+            locale: val.locale,
+            invite_request: val.invite_request,
+            role: val.role.map(|r| r.into()),
+            confirmed: val.confirmed,
+            approved: val.approved,
+            disabled: val.disabled,
+            silenced: val.silenced,
+            suspended: val.suspended,
+            account: val.account.map(|a| a.into()),
+        }
+    }
+}
+
+impl From<Ip> for MegalodonEntities::admin::Ip {
+    fn from(val: Ip) -> Self {
+        MegalodonEntities::admin::Ip {
+            ip: val.ip,
+            used_at: val.used_at,
+        }
+    }
+}
+
+impl From<Report> for MegalodonEntities::admin::Report {
+    fn from(val: Report) -> Self {
+        MegalodonEntities::admin::Report {
+            id: val.id,
+            action_taken: val.action_taken,
+            action_taken_at: val.action_taken_at,
+            category: val.category.into(),
+            comment: val.comment,
+            forwarded: val.forwarded,
+            created_at: val.created_at,
+            updated_at: val.updated_at,
+            account: val.account.into(),
+            target_account: val.target_account.into(),
+            assigned_account: val.assigned_account.map(|a| a.into()),
+            action_taken_by_account: val.action_taken_by_account.map(|a| a.into()),
+            statuses: val.statuses.into_iter().map(|s| s.into()).collect(),
+            rules: val
+                .rules
+                .into_iter()
+                .map(|r| MegalodonEntities::instance::InstanceRule {
+                    id: r.id,
+                    text: r.text,
+                })
+                .collect(),
+        }
+    }
+}
+
+impl From<DomainPermission> for MegalodonEntities::admin::DomainBlock {
+    fn from(val: DomainPermission) -> Self {
+        MegalodonEntities::admin::DomainBlock {
+            id: val.id,
+            domain: val.domain,
+            digest: None,
+            created_at: val.created_at,
+            // Domain blocks of GoToSocial always suspend the domain.
+            severity: MegalodonEntities::admin::DomainBlockSeverity::Suspend,
+            reject_media: false,
+            reject_reports: false,
+            private_comment: val.private_comment,
+            public_comment: val.public_comment,
+            obfuscate: val.obfuscate.unwrap_or(false),
+        }
+    }
+}
+
+impl From<DomainPermission> for MegalodonEntities::admin::DomainAllow {
+    fn from(val: DomainPermission) -> Self {
+        MegalodonEntities::admin::DomainAllow {
+            id: val.id,
+            domain: val.domain,
+            created_at: val.created_at,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_domain_permission() {
+        let text = r#"{
+            "id": "01FBW21XJA09XYX51KV5JVBW0F",
+            "domain": "example.org",
+            "obfuscate": false,
+            "public_comment": "just absolutely the worst, wowza",
+            "private_comment": "",
+            "created_at": "2021-07-29T09:57:10.000Z",
+            "created_by": "01BPSX2MKCRVMD4YN4D71G9CP5"
+        }"#;
+
+        let block: MegalodonEntities::admin::DomainBlock =
+            serde_json::from_str::<DomainPermission>(text)
+                .unwrap()
+                .into();
+        assert_eq!(block.domain, "example.org");
+        assert_eq!(
+            block.severity,
+            MegalodonEntities::admin::DomainBlockSeverity::Suspend
+        );
+        assert_eq!(
+            block.public_comment.as_deref(),
+            Some("just absolutely the worst, wowza")
+        );
+    }
+}
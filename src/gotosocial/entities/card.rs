@@ -54,6 +54,7 @@ impl From<Card> for MegalodonEntities::Card {
             height: val.height,
             embed_url: None,
             blurhash: None,
+            authors: Vec::new(),
         }
     }
 }
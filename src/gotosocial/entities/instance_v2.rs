@@ -0,0 +1,205 @@
+use super::instance::{MediaAttachments, Polls, Statuses};
+use super::Account;
+use crate::entities as MegalodonEntities;
+use serde::Deserialize;
+use std::collections::HashMap;
+
+#[derive(Debug, Deserialize, Clone)]
+pub struct InstanceV2 {
+    domain: String,
+    title: String,
+    version: String,
+    source_url: Option<String>,
+    description: String,
+    thumbnail: Option<InstanceThumbnail>,
+    #[serde(default)]
+    languages: Vec<String>,
+    configuration: InstanceV2Config,
+    registrations: InstanceRegistrations,
+    contact: InstanceContact,
+    #[serde(default)]
+    rules: Vec<InstanceRule>,
+}
+
+#[derive(Debug, Deserialize, Clone)]
+pub struct InstanceThumbnail {
+    url: String,
+    blurhash: Option<String>,
+}
+
+#[derive(Debug, Deserialize, Clone)]
+pub struct InstanceV2Config {
+    urls: InstanceV2URLs,
+    statuses: Statuses,
+    media_attachments: Option<MediaAttachments>,
+    polls: Option<Polls>,
+    #[serde(default)]
+    translation: Translation,
+}
+
+#[derive(Debug, Deserialize, Clone)]
+pub struct InstanceV2URLs {
+    streaming: Option<String>,
+}
+
+#[derive(Debug, Deserialize, Clone, Default)]
+pub struct Translation {
+    enabled: bool,
+}
+
+#[derive(Debug, Deserialize, Clone)]
+pub struct InstanceRegistrations {
+    enabled: bool,
+    approval_required: bool,
+    message: Option<String>,
+}
+
+#[derive(Debug, Deserialize, Clone)]
+pub struct InstanceContact {
+    email: String,
+    account: Option<Account>,
+}
+
+#[derive(Debug, Deserialize, Clone)]
+pub struct InstanceRule {
+    id: String,
+    text: String,
+}
+
+impl From<InstanceV2> for MegalodonEntities::InstanceV2 {
+    fn from(val: InstanceV2) -> Self {
+        MegalodonEntities::InstanceV2 {
+            domain: val.domain,
+            title: val.title,
+            version: val.version,
+            source_url: val.source_url,
+            description: val.description,
+            thumbnail: val
+                .thumbnail
+                .map(|t| MegalodonEntities::instance_v2::InstanceThumbnail {
+                    url: t.url,
+                    blurhash: t.blurhash,
+                    versions: None,
+                }),
+            languages: val.languages,
+            configuration: val.configuration.into(),
+            registrations: MegalodonEntities::instance_v2::InstanceRegistrations {
+                enabled: val.registrations.enabled,
+                approval_required: val.registrations.approval_required,
+                message: val.registrations.message,
+                url: None,
+            },
+            api_versions: HashMap::new(),
+            contact: MegalodonEntities::instance_v2::InstanceContact {
+                email: val.contact.email,
+                account: val.contact.account.map(|a| a.into()),
+            },
+            rules: val
+                .rules
+                .into_iter()
+                .map(|r| MegalodonEntities::instance::InstanceRule {
+                    id: r.id,
+                    text: r.text,
+                })
+                .collect(),
+        }
+    }
+}
+
+impl From<InstanceV2Config> for MegalodonEntities::instance_v2::InstanceV2Config {
+    fn from(val: InstanceV2Config) -> Self {
+        MegalodonEntities::instance_v2::InstanceV2Config {
+            urls: MegalodonEntities::instance_v2::InstanceV2URLs {
+                streaming: val.urls.streaming,
+            },
+            statuses: val.statuses.into(),
+            media_attachments: val.media_attachments.map(|m| {
+                MegalodonEntities::instance_v2::MediaAttachments {
+                    supported_mime_types: m.supported_mime_types,
+                    description_limit: None,
+                    image_size_limit: m.image_size_limit,
+                    image_matrix_limit: m.image_matrix_limit,
+                    video_size_limit: m.video_size_limit,
+                    video_frame_rate_limit: m.video_frame_rate_limit,
+                    video_matrix_limit: m.video_matrix_limit,
+                }
+            }),
+            polls: val.polls.map(|p| p.into()),
+            translation: MegalodonEntities::instance_v2::Translation {
+                enabled: val.translation.enabled,
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_instance_v2() {
+        let text = r#"{
+            "domain": "gts.example",
+            "account_domain": "gts.example",
+            "title": "GoToSocial",
+            "version": "0.17.0",
+            "source_url": "https://github.com/superseriousbusiness/gotosocial",
+            "description": "",
+            "thumbnail": {
+                "url": "https://gts.example/assets/logo.png",
+                "thumbnail_type": "image/png",
+                "thumbnail_description": "Flaky the sloth"
+            },
+            "languages": ["en"],
+            "configuration": {
+                "urls": { "streaming": "wss://gts.example" },
+                "statuses": {
+                    "max_characters": 5000,
+                    "max_media_attachments": 6,
+                    "characters_reserved_per_url": 25,
+                    "supported_mime_types": ["text/plain", "text/markdown"]
+                },
+                "media_attachments": {
+                    "supported_mime_types": ["image/jpeg"],
+                    "image_size_limit": 41943040,
+                    "image_matrix_limit": 16777216,
+                    "video_size_limit": 41943040,
+                    "video_frame_rate_limit": 60,
+                    "video_matrix_limit": 16777216
+                },
+                "polls": {
+                    "max_options": 6,
+                    "max_characters_per_option": 50,
+                    "min_expiration": 300,
+                    "max_expiration": 2629746
+                },
+                "emojis": { "emoji_size_limit": 51200 }
+            },
+            "registrations": { "enabled": false, "approval_required": true, "message": null },
+            "contact": { "email": "", "account": null },
+            "rules": [{ "id": "01J0", "text": "Be nice" }]
+        }"#;
+
+        let instance: MegalodonEntities::InstanceV2 =
+            serde_json::from_str::<InstanceV2>(text).unwrap().into();
+        assert_eq!(instance.domain, "gts.example");
+        assert_eq!(
+            instance.thumbnail.unwrap().url,
+            "https://gts.example/assets/logo.png"
+        );
+        assert_eq!(
+            instance.configuration.statuses.characters_reserved_per_url,
+            Some(25)
+        );
+        assert_eq!(
+            instance
+                .configuration
+                .media_attachments
+                .unwrap()
+                .video_frame_rate_limit,
+            60
+        );
+        assert!(!instance.configuration.translation.enabled);
+        assert_eq!(instance.rules[0].text, "Be nice");
+    }
+}
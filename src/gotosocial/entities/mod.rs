@@ -1,4 +1,5 @@
 pub mod account;
+pub mod admin;
 pub mod application;
 pub mod attachment;
 pub mod card;
@@ -7,6 +8,7 @@ pub mod emoji;
 pub mod field;
 pub mod filter;
 pub mod instance;
+pub mod instance_v2;
 pub mod list;
 pub mod marker;
 pub mod mention;
@@ -22,6 +24,7 @@ pub mod scheduled_status;
 pub mod source;
 pub mod stats;
 pub mod status;
+pub mod status_edit;
 pub mod status_params;
 pub mod status_source;
 pub mod tag;
@@ -37,6 +40,7 @@ pub use emoji::Emoji;
 pub use field::Field;
 pub use filter::Filter;
 pub use instance::Instance;
+pub use instance_v2::InstanceV2;
 pub use list::List;
 pub use marker::Marker;
 pub use mention::Mention;
@@ -52,6 +56,7 @@ pub use scheduled_status::ScheduledStatus;
 pub use source::Source;
 pub use stats::Stats;
 pub use status::{Status, StatusVisibility};
+pub use status_edit::StatusEdit;
 pub use status_params::StatusParams;
 pub use status_source::StatusSource;
 pub use tag::Tag;
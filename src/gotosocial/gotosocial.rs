@@ -2,8 +2,12 @@ use super::api_client::APIClient;
 use super::entities;
 use super::oauth;
 use super::web_socket::WebSocket;
+use crate::SNS;
+use crate::capabilities::Capabilities;
+use crate::http::{ClientBuilder, idempotency_header};
 use crate::megalodon::FollowRequestOutput;
-use crate::{Streaming, error};
+use crate::web_socket::MultiplexWebSocket;
+use crate::{Streaming, error, streaming::MultiplexStreaming};
 use crate::{
     default, entities as MegalodonEntities, error::Error, megalodon, oauth as MegalodonOAuth,
     response::Response,
@@ -38,7 +42,22 @@ impl Gotosocial {
         access_token: Option<String>,
         user_agent: Option<String>,
     ) -> Result<Gotosocial, Error> {
-        let client = APIClient::new(base_url.clone(), access_token.clone(), user_agent.clone())?;
+        Self::new_with_client(base_url, access_token, user_agent, ClientBuilder::default())
+    }
+
+    /// Create a new [`Gotosocial`] with the HTTP client settings.
+    pub fn new_with_client(
+        base_url: String,
+        access_token: Option<String>,
+        user_agent: Option<String>,
+        client: ClientBuilder,
+    ) -> Result<Gotosocial, Error> {
+        let client = APIClient::new(
+            base_url.clone(),
+            access_token.clone(),
+            user_agent.clone(),
+            &client,
+        )?;
         Ok(Gotosocial {
             client,
             base_url,
@@ -1355,10 +1374,12 @@ impl megalodon::Megalodon for Gotosocial {
             }
         }
 
+        let headers = idempotency_header(options.and_then(|o| o.idempotency_key.as_deref()))?;
+
         if params.contains_key("scheduled_at") {
             let res = self
                 .client
-                .post::<entities::ScheduledStatus>("/api/v1/statuses", &params, None)
+                .post::<entities::ScheduledStatus>("/api/v1/statuses", &params, Some(headers))
                 .await?;
 
             Ok(Response::<megalodon::PostStatusOutput>::new(
@@ -1370,7 +1391,7 @@ impl megalodon::Megalodon for Gotosocial {
         } else {
             let res = self
                 .client
-                .post::<entities::Status>("/api/v1/statuses", &params, None)
+                .post::<entities::Status>("/api/v1/statuses", &params, Some(headers))
                 .await?;
 
             Ok(Response::<megalodon::PostStatusOutput>::new(
@@ -1413,6 +1434,26 @@ impl megalodon::Megalodon for Gotosocial {
         ))
     }
 
+    async fn get_status_history(
+        &self,
+        id: String,
+    ) -> Result<Response<Vec<MegalodonEntities::StatusEdit>>, Error> {
+        let res = self
+            .client
+            .get::<Vec<entities::StatusEdit>>(
+                format!("/api/v1/statuses/{}/history", id).as_str(),
+                None,
+            )
+            .await?;
+
+        Ok(Response::<Vec<MegalodonEntities::StatusEdit>>::new(
+            res.json.into_iter().map(|j| j.into()).collect(),
+            res.status,
+            res.status_text,
+            res.header,
+        ))
+    }
+
     async fn edit_status(
         &self,
         id: String,
@@ -2609,6 +2650,40 @@ impl megalodon::Megalodon for Gotosocial {
         ))
     }
 
+    async fn get_instance_v2(&self) -> Result<Response<MegalodonEntities::InstanceV2>, Error> {
+        match self
+            .client
+            .get::<entities::InstanceV2>("/api/v2/instance", None)
+            .await
+        {
+            Ok(res) => Ok(Response::<MegalodonEntities::InstanceV2>::new(
+                res.json.into(),
+                res.status,
+                res.status_text,
+                res.header,
+            )),
+            // GoToSocial older than 0.14 does not have v2 instance.
+            Err(Error::OwnError(error::OwnError {
+                kind: error::Kind::NotFoundError,
+                ..
+            })) => {
+                let res = self.get_instance().await?;
+                Ok(Response::<MegalodonEntities::InstanceV2>::new(
+                    res.json.into(),
+                    res.status,
+                    res.status_text,
+                    res.header,
+                ))
+            }
+            Err(err) => Err(err),
+        }
+    }
+
+    async fn capabilities(&self) -> Result<Capabilities, Error> {
+        let instance = self.get_instance_v2().await;
+        Ok(Capabilities::detect(SNS::Gotosocial, instance))
+    }
+
     async fn get_instance_peers(&self) -> Result<Response<Vec<String>>, Error> {
         let res = self
             .client
@@ -2775,6 +2850,298 @@ impl megalodon::Megalodon for Gotosocial {
             None,
         ))
     }
+    async fn perform_admin_account_action(
+        &self,
+        id: String,
+        action: megalodon::AdminAccountAction,
+        options: Option<&megalodon::AdminAccountActionInputOptions>,
+    ) -> Result<Response<()>, Error> {
+        if action != megalodon::AdminAccountAction::Suspend {
+            return Err(Error::new_own(
+                format!("Gotosocial does not support {} action", action),
+                error::Kind::NoImplementedError,
+                None,
+                None,
+                None,
+            ));
+        }
+        let mut params =
+            HashMap::<&str, Value>::from([("type", serde_json::Value::String(action.to_string()))]);
+        if let Some(options) = options {
+            if let Some(text) = &options.text {
+                params.insert("text", serde_json::Value::String(text.clone()));
+            }
+        }
+        let res = self
+            .client
+            .post::<Value>(
+                format!("/api/v1/admin/accounts/{}/action", id).as_str(),
+                &params,
+                None,
+            )
+            .await?;
+
+        Ok(Response::<()>::new(
+            (),
+            res.status,
+            res.status_text,
+            res.header,
+        ))
+    }
+
+    async fn get_admin_reports(
+        &self,
+        options: Option<&megalodon::GetAdminReportsInputOptions>,
+    ) -> Result<Response<Vec<MegalodonEntities::admin::Report>>, Error> {
+        let mut params = Vec::<String>::new();
+        if let Some(options) = options {
+            if let Some(resolved) = options.resolved {
+                params.push(format!("resolved={}", resolved));
+            }
+            if let Some(account_id) = &options.account_id {
+                params.push(format!("account_id={}", account_id));
+            }
+            if let Some(target_account_id) = &options.target_account_id {
+                params.push(format!("target_account_id={}", target_account_id));
+            }
+            if let Some(limit) = options.limit {
+                params.push(format!("limit={}", limit));
+            }
+            if let Some(max_id) = &options.max_id {
+                params.push(format!("max_id={}", max_id));
+            }
+            if let Some(since_id) = &options.since_id {
+                params.push(format!("since_id={}", since_id));
+            }
+            if let Some(min_id) = &options.min_id {
+                params.push(format!("min_id={}", min_id));
+            }
+        }
+        let mut path = "/api/v1/admin/reports".to_string();
+        if !params.is_empty() {
+            path = path + "?" + params.join("&").as_str();
+        }
+        let res = self
+            .client
+            .get::<Vec<entities::admin::Report>>(path.as_str(), None)
+            .await?;
+
+        Ok(Response::<Vec<MegalodonEntities::admin::Report>>::new(
+            res.json.into_iter().map(|j| j.into()).collect(),
+            res.status,
+            res.status_text,
+            res.header,
+        ))
+    }
+
+    async fn get_admin_report(
+        &self,
+        id: String,
+    ) -> Result<Response<MegalodonEntities::admin::Report>, Error> {
+        let res = self
+            .client
+            .get::<entities::admin::Report>(format!("/api/v1/admin/reports/{}", id).as_str(), None)
+            .await?;
+
+        Ok(Response::<MegalodonEntities::admin::Report>::new(
+            res.json.into(),
+            res.status,
+            res.status_text,
+            res.header,
+        ))
+    }
+
+    async fn resolve_admin_report(
+        &self,
+        id: String,
+    ) -> Result<Response<MegalodonEntities::admin::Report>, Error> {
+        let params = HashMap::<&str, Value>::new();
+        let res = self
+            .client
+            .post::<entities::admin::Report>(
+                format!("/api/v1/admin/reports/{}/resolve", id).as_str(),
+                &params,
+                None,
+            )
+            .await?;
+
+        Ok(Response::<MegalodonEntities::admin::Report>::new(
+            res.json.into(),
+            res.status,
+            res.status_text,
+            res.header,
+        ))
+    }
+
+    async fn get_admin_domain_blocks(
+        &self,
+        _options: Option<&megalodon::GetAdminDomainBlocksInputOptions>,
+    ) -> Result<Response<Vec<MegalodonEntities::admin::DomainBlock>>, Error> {
+        // GoToSocial returns all domain blocks without pagination.
+        let res = self
+            .client
+            .get::<Vec<entities::admin::DomainPermission>>("/api/v1/admin/domain_blocks", None)
+            .await?;
+
+        Ok(Response::<Vec<MegalodonEntities::admin::DomainBlock>>::new(
+            res.json.into_iter().map(|j| j.into()).collect(),
+            res.status,
+            res.status_text,
+            res.header,
+        ))
+    }
+
+    async fn get_admin_domain_block(
+        &self,
+        id: String,
+    ) -> Result<Response<MegalodonEntities::admin::DomainBlock>, Error> {
+        let res = self
+            .client
+            .get::<entities::admin::DomainPermission>(
+                format!("/api/v1/admin/domain_blocks/{}", id).as_str(),
+                None,
+            )
+            .await?;
+
+        Ok(Response::<MegalodonEntities::admin::DomainBlock>::new(
+            res.json.into(),
+            res.status,
+            res.status_text,
+            res.header,
+        ))
+    }
+
+    async fn create_admin_domain_block(
+        &self,
+        domain: String,
+        options: Option<&megalodon::AdminDomainBlockInputOptions>,
+    ) -> Result<Response<MegalodonEntities::admin::DomainBlock>, Error> {
+        // GoToSocial does not have severity, reject_media and reject_reports.
+        let mut params =
+            HashMap::<&str, Value>::from([("domain", serde_json::Value::String(domain))]);
+        if let Some(options) = options {
+            if let Some(private_comment) = &options.private_comment {
+                params.insert(
+                    "private_comment",
+                    serde_json::Value::String(private_comment.clone()),
+                );
+            }
+            if let Some(public_comment) = &options.public_comment {
+                params.insert(
+                    "public_comment",
+                    serde_json::Value::String(public_comment.clone()),
+                );
+            }
+            if let Some(obfuscate) = options.obfuscate {
+                params.insert("obfuscate", serde_json::Value::Bool(obfuscate));
+            }
+        }
+        let res = self
+            .client
+            .post::<entities::admin::DomainPermission>("/api/v1/admin/domain_blocks", &params, None)
+            .await?;
+
+        Ok(Response::<MegalodonEntities::admin::DomainBlock>::new(
+            res.json.into(),
+            res.status,
+            res.status_text,
+            res.header,
+        ))
+    }
+
+    async fn delete_admin_domain_block(&self, id: String) -> Result<Response<()>, Error> {
+        let params = HashMap::<&str, Value>::new();
+        let res = self
+            .client
+            .delete::<Value>(
+                format!("/api/v1/admin/domain_blocks/{}", id).as_str(),
+                &params,
+                None,
+            )
+            .await?;
+
+        Ok(Response::<()>::new(
+            (),
+            res.status,
+            res.status_text,
+            res.header,
+        ))
+    }
+
+    async fn get_admin_domain_allows(
+        &self,
+        _options: Option<&megalodon::GetAdminDomainAllowsInputOptions>,
+    ) -> Result<Response<Vec<MegalodonEntities::admin::DomainAllow>>, Error> {
+        // GoToSocial returns all domain allows without pagination.
+        let res = self
+            .client
+            .get::<Vec<entities::admin::DomainPermission>>("/api/v1/admin/domain_allows", None)
+            .await?;
+
+        Ok(Response::<Vec<MegalodonEntities::admin::DomainAllow>>::new(
+            res.json.into_iter().map(|j| j.into()).collect(),
+            res.status,
+            res.status_text,
+            res.header,
+        ))
+    }
+
+    async fn get_admin_domain_allow(
+        &self,
+        id: String,
+    ) -> Result<Response<MegalodonEntities::admin::DomainAllow>, Error> {
+        let res = self
+            .client
+            .get::<entities::admin::DomainPermission>(
+                format!("/api/v1/admin/domain_allows/{}", id).as_str(),
+                None,
+            )
+            .await?;
+
+        Ok(Response::<MegalodonEntities::admin::DomainAllow>::new(
+            res.json.into(),
+            res.status,
+            res.status_text,
+            res.header,
+        ))
+    }
+
+    async fn create_admin_domain_allow(
+        &self,
+        domain: String,
+    ) -> Result<Response<MegalodonEntities::admin::DomainAllow>, Error> {
+        let params = HashMap::<&str, Value>::from([("domain", serde_json::Value::String(domain))]);
+        let res = self
+            .client
+            .post::<entities::admin::DomainPermission>("/api/v1/admin/domain_allows", &params, None)
+            .await?;
+
+        Ok(Response::<MegalodonEntities::admin::DomainAllow>::new(
+            res.json.into(),
+            res.status,
+            res.status_text,
+            res.header,
+        ))
+    }
+
+    async fn delete_admin_domain_allow(&self, id: String) -> Result<Response<()>, Error> {
+        let params = HashMap::<&str, Value>::new();
+        let res = self
+            .client
+            .delete::<Value>(
+                format!("/api/v1/admin/domain_allows/{}", id).as_str(),
+                &params,
+                None,
+            )
+            .await?;
+
+        Ok(Response::<()>::new(
+            (),
+            res.status,
+            res.status_text,
+            res.header,
+        ))
+    }
 
     async fn streaming_url(&self) -> String {
         let instance = self.get_instance().await;
@@ -2871,4 +3238,161 @@ impl megalodon::Megalodon for Gotosocial {
 
         Box::new(c)
     }
+
+    async fn multiplex_streaming(
+        &self,
+    ) -> Result<Box<dyn MultiplexStreaming + Send + Sync>, Error> {
+        let streaming_url = self.streaming_url().await;
+        let c = MultiplexWebSocket::new(
+            streaming_url + "/api/v1/streaming",
+            self.access_token.clone(),
+            self.user_agent.clone(),
+            WebSocket::parse_event,
+        );
+
+        Ok(Box::new(c))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::http::testing::serve;
+    use crate::megalodon::Megalodon;
+
+    #[tokio::test]
+    async fn test_get_instance_v2() {
+        let (url, mut requests) = serve(vec![String::from(
+            r#"{
+                "domain": "gts.example",
+                "title": "GoToSocial",
+                "version": "0.17.0",
+                "source_url": "https://github.com/superseriousbusiness/gotosocial",
+                "description": "",
+                "languages": [],
+                "configuration": {
+                    "urls": {"streaming": "wss://gts.example"},
+                    "statuses": {
+                        "max_characters": 5000,
+                        "max_media_attachments": 6,
+                        "characters_reserved_per_url": 25
+                    },
+                    "translation": {"enabled": false}
+                },
+                "registrations": {"enabled": false, "approval_required": true, "message": null},
+                "contact": {"email": "", "account": null},
+                "rules": []
+            }"#,
+        )])
+        .await;
+        let client = Gotosocial::new(url, None, None).unwrap();
+        let res = client.get_instance_v2().await.unwrap();
+
+        assert_eq!(requests.recv().await.unwrap().path, "/api/v2/instance");
+        assert_eq!(res.json.domain, "gts.example");
+        assert_eq!(
+            res.json.configuration.urls.streaming.as_deref(),
+            Some("wss://gts.example")
+        );
+        assert_eq!(res.json.configuration.statuses.max_characters, 5000);
+    }
+
+    #[tokio::test]
+    async fn test_admin_domain_blocks() {
+        let block = serde_json::json!({
+            "id": "01FBW21XJA09XYX51KV5JVBW0F",
+            "domain": "example.org",
+            "obfuscate": true,
+            "public_comment": "spam",
+            "private_comment": null,
+            "created_at": "2021-07-29T09:57:10.000Z"
+        });
+        let (url, mut requests) = serve(vec![block.to_string(), block.to_string()]).await;
+        let client = Gotosocial::new(url, None, None).unwrap();
+        let options = megalodon::AdminDomainBlockInputOptions {
+            severity: Some(MegalodonEntities::admin::DomainBlockSeverity::Silence),
+            public_comment: Some(String::from("spam")),
+            obfuscate: Some(true),
+            ..Default::default()
+        };
+        let res = client
+            .create_admin_domain_block(String::from("example.org"), Some(&options))
+            .await
+            .unwrap();
+        client
+            .delete_admin_domain_block(res.json.id.clone())
+            .await
+            .unwrap();
+
+        assert!(res.json.obfuscate);
+        let request = requests.recv().await.unwrap();
+        assert_eq!(request.method, "POST");
+        assert_eq!(request.path, "/api/v1/admin/domain_blocks");
+        assert_eq!(
+            request.json(),
+            serde_json::json!({"domain": "example.org", "public_comment": "spam", "obfuscate": true})
+        );
+        let request = requests.recv().await.unwrap();
+        assert_eq!(request.method, "DELETE");
+        assert_eq!(
+            request.path,
+            "/api/v1/admin/domain_blocks/01FBW21XJA09XYX51KV5JVBW0F"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_admin_domain_allows() {
+        let (url, mut requests) = serve(vec![String::from(
+            r#"[{"id": "1", "domain": "example.org", "created_at": "2021-07-29T09:57:10.000Z"}]"#,
+        )])
+        .await;
+        let client = Gotosocial::new(url, None, None).unwrap();
+        let res = client.get_admin_domain_allows(None).await.unwrap();
+
+        assert_eq!(res.json[0].domain, "example.org");
+        assert_eq!(
+            requests.recv().await.unwrap().path,
+            "/api/v1/admin/domain_allows"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_perform_admin_account_action() {
+        let (url, mut requests) = serve(vec![String::from("{}")]).await;
+        let client = Gotosocial::new(url, None, None).unwrap();
+        let err = client
+            .perform_admin_account_action(
+                String::from("1"),
+                megalodon::AdminAccountAction::Silence,
+                None,
+            )
+            .await
+            .unwrap_err();
+        assert!(matches!(
+            err,
+            Error::OwnError(error::OwnError {
+                kind: error::Kind::NoImplementedError,
+                ..
+            })
+        ));
+
+        let options = megalodon::AdminAccountActionInputOptions {
+            text: Some(String::from("spam")),
+            ..Default::default()
+        };
+        client
+            .perform_admin_account_action(
+                String::from("1"),
+                megalodon::AdminAccountAction::Suspend,
+                Some(&options),
+            )
+            .await
+            .unwrap();
+        let request = requests.recv().await.unwrap();
+        assert_eq!(request.path, "/api/v1/admin/accounts/1/action");
+        assert_eq!(
+            request.json(),
+            serde_json::json!({"type": "suspend", "text": "spam"})
+        );
+    }
 }
@@ -0,0 +1,806 @@
+//! HTTP client configuration shared by all API clients
+use std::collections::HashMap;
+use std::fmt;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use crate::default::DEFAULT_UA;
+use crate::error::{Error, Kind};
+use crate::response::RateLimit;
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use reqwest::header::{HeaderMap, HeaderValue, RETRY_AFTER, USER_AGENT};
+use reqwest::{Certificate, IntoUrl, Method, Proxy, Request, RequestBuilder, Response, StatusCode};
+use tracing::{debug_span, warn, Instrument, Span};
+use uuid::Uuid;
+
+// Wait before retrying when the server does not tell when the limit is reset.
+const DEFAULT_RETRY_AFTER: Duration = Duration::from_secs(60);
+const IDEMPOTENCY_KEY: &str = "idempotency-key";
+
+/// Hooks which are called around every request of the API clients.
+///
+/// It can be used for logging, metrics or signing requests.
+///
+/// ```rust
+/// # use megalodon::error::Error;
+/// # use megalodon::http::Middleware;
+/// #
+/// #[derive(Debug)]
+/// struct Logger;
+///
+/// #[async_trait::async_trait]
+/// impl Middleware for Logger {
+///     async fn before_send(&self, request: &mut reqwest::Request) -> Result<(), Error> {
+///         println!("{} {}", request.method(), request.url());
+///         Ok(())
+///     }
+///
+///     async fn after_receive(&self, response: &reqwest::Response) -> Result<(), Error> {
+///         println!("{} {}", response.status(), response.url());
+///         Ok(())
+///     }
+/// }
+/// ```
+#[async_trait]
+pub trait Middleware: fmt::Debug + Send + Sync {
+    /// Called before the request is sent. The request can be modified, for example to add headers.
+    /// Returning an error aborts the request.
+    async fn before_send(&self, _request: &mut Request) -> Result<(), Error> {
+        Ok(())
+    }
+
+    /// Called after the response is received, before the body is read.
+    /// Returning an error is reported as the result of the request.
+    async fn after_receive(&self, _response: &Response) -> Result<(), Error> {
+        Ok(())
+    }
+}
+
+/// Behavior when the server limits the rate of requests.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum RateLimitMode {
+    /// Send requests as they are, and return `429 Too Many Requests` as an error.
+    #[default]
+    Ignore,
+    /// Pause until the reset time when the remaining requests of the endpoint run out,
+    /// and retry `429 Too Many Requests` after `Retry-After` up to `max_retries` times.
+    ///
+    /// Requests which have a streaming body, such as file uploads, are not retried.
+    Wait {
+        /// How many times a rate limited request is retried.
+        max_retries: u32,
+    },
+}
+
+/// Policy to retry requests which failed transiently.
+///
+/// Only idempotent requests are retried, which are `GET`, `HEAD`, `PUT`, `DELETE`, `OPTIONS`,
+/// and requests with `Idempotency-Key` header.
+/// They are retried on connection errors, timeouts, `206 Partial Content`, `502 Bad Gateway`,
+/// `503 Service Unavailable` and `504 Gateway Timeout`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RetryPolicy {
+    /// How many times a request is retried.
+    pub max_retries: u32,
+    /// Wait before the first retry.
+    pub initial_interval: Duration,
+    /// Upper limit of the wait.
+    pub max_interval: Duration,
+    /// The wait is multiplied by this value on each retry.
+    pub multiplier: f64,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_retries: 3,
+            initial_interval: Duration::from_millis(500),
+            max_interval: Duration::from_secs(10),
+            multiplier: 2.0,
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// Wait before the retry. `retry` starts from 1.
+    pub fn interval(&self, retry: u32) -> Duration {
+        let exponent = retry.saturating_sub(1).min(i32::MAX as u32) as i32;
+        let interval = self.initial_interval.as_secs_f64() * self.multiplier.powi(exponent);
+        if !interval.is_finite() || interval >= self.max_interval.as_secs_f64() {
+            return self.max_interval;
+        }
+        Duration::from_secs_f64(interval.max(0.0))
+    }
+}
+
+/// Builder of the HTTP client which is used by the API clients.
+///
+/// Pass it to [`crate::generator_with_client`] to use the same settings for any SNS.
+///
+/// ```rust
+/// # use megalodon;
+/// # use megalodon::http::ClientBuilder;
+/// # use std::time::Duration;
+/// #
+/// # fn run() -> Result<(), megalodon::error::Error> {
+/// let builder = ClientBuilder::new()
+///     .timeout(Duration::from_secs(10))
+///     .proxy(reqwest::Proxy::all("http://proxy.example:8080")?);
+/// let client = megalodon::generator_with_client(
+///   megalodon::SNS::Mastodon,
+///   String::from("https://mastodon.social"),
+///   None,
+///   None,
+///   builder,
+/// )?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct ClientBuilder {
+    client: Option<reqwest::Client>,
+    timeout: Option<Duration>,
+    connect_timeout: Option<Duration>,
+    proxy: Option<Proxy>,
+    root_certificates: Vec<Certificate>,
+    default_headers: HeaderMap,
+    middlewares: Vec<Arc<dyn Middleware>>,
+    rate_limit_mode: RateLimitMode,
+    retry_policy: Option<RetryPolicy>,
+}
+
+impl ClientBuilder {
+    /// Create a new [`ClientBuilder`] with the default settings.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Use the given client instead of building a new one.
+    /// The timeouts, proxy, root certificates and default headers of this builder are ignored,
+    /// because they are already fixed in the client.
+    pub fn client(mut self, client: reqwest::Client) -> Self {
+        self.client = Some(client);
+        self
+    }
+
+    /// Set a timeout for the whole request. It is not applied to streaming over Server-Sent Events.
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// Set a timeout for connecting to the server.
+    pub fn connect_timeout(mut self, timeout: Duration) -> Self {
+        self.connect_timeout = Some(timeout);
+        self
+    }
+
+    /// Send requests through the proxy.
+    pub fn proxy(mut self, proxy: Proxy) -> Self {
+        self.proxy = Some(proxy);
+        self
+    }
+
+    /// Trust the certificate in addition to the default root certificates.
+    pub fn add_root_certificate(mut self, certificate: Certificate) -> Self {
+        self.root_certificates.push(certificate);
+        self
+    }
+
+    /// Set headers which are sent with every request.
+    pub fn default_headers(mut self, headers: HeaderMap) -> Self {
+        self.default_headers = headers;
+        self
+    }
+
+    /// Add a middleware. Middlewares are called in the order they are added.
+    pub fn middleware(mut self, middleware: impl Middleware + 'static) -> Self {
+        self.middlewares.push(Arc::new(middleware));
+        self
+    }
+
+    /// Set the behavior when the server limits the rate of requests.
+    pub fn rate_limit_mode(mut self, mode: RateLimitMode) -> Self {
+        self.rate_limit_mode = mode;
+        self
+    }
+
+    /// Retry idempotent requests which failed transiently with the policy.
+    pub fn retry_policy(mut self, policy: RetryPolicy) -> Self {
+        self.retry_policy = Some(policy);
+        self
+    }
+
+    pub(crate) fn build(&self, user_agent: Option<String>) -> Result<HttpClient, Error> {
+        let (client, stream_client, user_agent) = match &self.client {
+            Some(client) => {
+                // The given client may have its own User-Agent, so override it only when it is specified.
+                let user_agent = match user_agent {
+                    Some(ua) => Some(HeaderValue::from_str(&ua).map_err(|e| {
+                        Error::new_own(e.to_string(), Kind::ParseError, None, None, None)
+                    })?),
+                    None => None,
+                };
+                (client.clone(), client.clone(), user_agent)
+            }
+            None => {
+                let ua = user_agent.unwrap_or(DEFAULT_UA.to_string());
+                let builder = || {
+                    let mut builder = reqwest::Client::builder()
+                        .user_agent(&ua)
+                        .default_headers(self.default_headers.clone());
+                    if let Some(timeout) = self.connect_timeout {
+                        builder = builder.connect_timeout(timeout);
+                    }
+                    if let Some(proxy) = &self.proxy {
+                        builder = builder.proxy(proxy.clone());
+                    }
+                    for certificate in self.root_certificates.iter() {
+                        builder = builder.add_root_certificate(certificate.clone());
+                    }
+                    builder
+                };
+                // Streams are kept open for a long time, so the total timeout is not applied to them.
+                let stream_client = builder().build()?;
+                let mut builder = builder();
+                if let Some(timeout) = self.timeout {
+                    builder = builder.timeout(timeout);
+                }
+                (builder.build()?, stream_client, None)
+            }
+        };
+        Ok(HttpClient {
+            client,
+            stream_client,
+            user_agent,
+            middlewares: self.middlewares.clone(),
+            rate_limit_mode: self.rate_limit_mode,
+            rate_limit: Arc::new(Mutex::new(HashMap::new())),
+            retry_policy: self.retry_policy.clone(),
+        })
+    }
+}
+
+/// HTTP client which applies middlewares to every request.
+#[derive(Debug, Clone)]
+pub(crate) struct HttpClient {
+    client: reqwest::Client,
+    stream_client: reqwest::Client,
+    user_agent: Option<HeaderValue>,
+    middlewares: Vec<Arc<dyn Middleware>>,
+    rate_limit_mode: RateLimitMode,
+    // The last rate limit of each bucket, which is shared by clones of the client.
+    rate_limit: Arc<Mutex<HashMap<RateLimitBucket, RateLimit>>>,
+    retry_policy: Option<RetryPolicy>,
+}
+
+impl HttpClient {
+    pub(crate) fn get<U: IntoUrl>(&self, url: U) -> RequestBuilder {
+        self.client.get(url)
+    }
+
+    pub(crate) fn post<U: IntoUrl>(&self, url: U) -> RequestBuilder {
+        self.client.post(url)
+    }
+
+    pub(crate) fn put<U: IntoUrl>(&self, url: U) -> RequestBuilder {
+        self.client.put(url)
+    }
+
+    pub(crate) fn patch<U: IntoUrl>(&self, url: U) -> RequestBuilder {
+        self.client.patch(url)
+    }
+
+    pub(crate) fn delete<U: IntoUrl>(&self, url: U) -> RequestBuilder {
+        self.client.delete(url)
+    }
+
+    /// Build a GET request of a long-lived stream, such as Server-Sent Events.
+    pub(crate) fn get_stream<U: IntoUrl>(&self, url: U) -> RequestBuilder {
+        self.stream_client.get(url)
+    }
+
+    /// Send a request of a long-lived stream, which is built by [`HttpClient::get_stream`].
+    /// Middlewares are applied, but it is neither retried nor limited by the timeout.
+    pub(crate) async fn send_stream(&self, req: RequestBuilder) -> Result<Response, Error> {
+        let mut request = req.build()?;
+        if let Some(ua) = &self.user_agent {
+            request.headers_mut().insert(USER_AGENT, ua.clone());
+        }
+        self.execute(&self.stream_client, request).await
+    }
+
+    pub(crate) async fn send(&self, req: RequestBuilder) -> Result<Response, Error> {
+        let mut request = req.build()?;
+        if let Some(ua) = &self.user_agent {
+            request.headers_mut().insert(USER_AGENT, ua.clone());
+        }
+        let span = debug_span!(
+            "request",
+            method = %request.method(),
+            url = %request.url(),
+            retries = 0u32,
+        );
+        self.send_with_retry(request).instrument(span).await
+    }
+
+    async fn send_with_retry(&self, mut request: Request) -> Result<Response, Error> {
+        let retry_policy = self
+            .retry_policy
+            .as_ref()
+            .filter(|_| is_idempotent(&request));
+        let bucket = RateLimitBucket::of(&request);
+        let mut rate_limit_retries: u32 = 0;
+        let mut retries: u32 = 0;
+        loop {
+            let retry_rate_limit = match self.rate_limit_mode {
+                RateLimitMode::Wait { max_retries } => {
+                    self.wait_for_rate_limit(bucket).await;
+                    rate_limit_retries < max_retries
+                }
+                RateLimitMode::Ignore => false,
+            };
+            let retry_failure = retry_policy.is_some_and(|policy| retries < policy.max_retries);
+            // Keep a copy to retry. Streaming bodies can not be copied, so they are sent only once.
+            let next = match retry_rate_limit || retry_failure {
+                true => request.try_clone(),
+                false => None,
+            };
+
+            let result = self.execute(&self.client, request).await;
+            if let Ok(response) = &result {
+                if let Some(rate_limit) = RateLimit::from_header(response.headers()) {
+                    if let Ok(mut last) = self.rate_limit.lock() {
+                        last.insert(bucket, rate_limit);
+                    }
+                }
+            }
+            let Some(next) = next else {
+                return result;
+            };
+            let wait = match (&result, retry_policy) {
+                (Ok(response), _)
+                    if retry_rate_limit && response.status() == StatusCode::TOO_MANY_REQUESTS =>
+                {
+                    rate_limit_retries += 1;
+                    let wait = retry_after(response.headers(), Utc::now());
+                    warn!("Rate limited, retrying after {:?}", wait);
+                    wait
+                }
+                (Ok(response), Some(policy))
+                    if retry_failure && is_transient_status(response.status()) =>
+                {
+                    retries += 1;
+                    let wait = policy.interval(retries);
+                    warn!(
+                        "Server responded {}, retrying after {:?}",
+                        response.status(),
+                        wait
+                    );
+                    wait
+                }
+                (Err(Error::RequestError(err)), Some(policy))
+                    if retry_failure && is_transient_error(err) =>
+                {
+                    retries += 1;
+                    let wait = policy.interval(retries);
+                    warn!("Request failed: {}, retrying after {:?}", err, wait);
+                    wait
+                }
+                _ => return result,
+            };
+            Span::current().record("retries", retries + rate_limit_retries);
+            tokio::time::sleep(wait).await;
+            request = next;
+        }
+    }
+
+    async fn execute(
+        &self,
+        client: &reqwest::Client,
+        mut request: Request,
+    ) -> Result<Response, Error> {
+        for middleware in self.middlewares.iter() {
+            middleware.before_send(&mut request).await?;
+        }
+        let response = client.execute(request).await?;
+        for middleware in self.middlewares.iter() {
+            middleware.after_receive(&response).await?;
+        }
+        Ok(response)
+    }
+
+    /// Pause until the reset time if no request remains in the bucket.
+    async fn wait_for_rate_limit(&self, bucket: RateLimitBucket) {
+        let reset = match self.rate_limit.lock() {
+            Ok(last) => match last.get(&bucket) {
+                Some(rate_limit) if rate_limit.is_exhausted() => rate_limit.reset,
+                _ => None,
+            },
+            Err(_) => None,
+        };
+        if let Some(wait) = reset.and_then(|reset| (reset - Utc::now()).to_std().ok()) {
+            warn!("Rate limit is exhausted, waiting {:?}", wait);
+            tokio::time::sleep(wait).await;
+        }
+    }
+}
+
+/// Build `Idempotency-Key` header with the key, or a random key if it is not specified.
+pub(crate) fn idempotency_header(key: Option<&str>) -> Result<HeaderMap, Error> {
+    let key = match key {
+        Some(key) => HeaderValue::from_str(key)
+            .map_err(|e| Error::new_own(e.to_string(), Kind::ParseError, None, None, None))?,
+        None => HeaderValue::from_str(&Uuid::new_v4().to_string())
+            .expect("UUID is a valid header value"),
+    };
+    let mut headers = HeaderMap::new();
+    headers.insert(IDEMPOTENCY_KEY, key);
+    Ok(headers)
+}
+
+/// Group of endpoints which share a rate limit.
+/// Mastodon limits media uploads and status deletions separately from the other endpoints.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+enum RateLimitBucket {
+    Default,
+    Media,
+    StatusDeletion,
+}
+
+impl RateLimitBucket {
+    fn of(request: &Request) -> Self {
+        let path = request.url().path();
+        match *request.method() {
+            Method::POST
+                if path.starts_with("/api/v1/media") || path.starts_with("/api/v2/media") =>
+            {
+                Self::Media
+            }
+            Method::DELETE if path.starts_with("/api/v1/statuses/") => Self::StatusDeletion,
+            Method::POST
+                if path.starts_with("/api/v1/statuses/") && path.ends_with("/unreblog") =>
+            {
+                Self::StatusDeletion
+            }
+            _ => Self::Default,
+        }
+    }
+}
+
+fn is_idempotent(request: &Request) -> bool {
+    match *request.method() {
+        Method::GET | Method::HEAD | Method::PUT | Method::DELETE | Method::OPTIONS => true,
+        _ => request.headers().contains_key(IDEMPOTENCY_KEY),
+    }
+}
+
+fn is_transient_status(status: StatusCode) -> bool {
+    matches!(
+        status,
+        StatusCode::PARTIAL_CONTENT
+            | StatusCode::BAD_GATEWAY
+            | StatusCode::SERVICE_UNAVAILABLE
+            | StatusCode::GATEWAY_TIMEOUT
+    )
+}
+
+fn is_transient_error(err: &reqwest::Error) -> bool {
+    if err.is_timeout() || err.is_connect() {
+        return true;
+    }
+    let mut source = std::error::Error::source(err);
+    while let Some(e) = source {
+        if let Some(io) = e.downcast_ref::<std::io::Error>() {
+            return matches!(
+                io.kind(),
+                std::io::ErrorKind::ConnectionReset
+                    | std::io::ErrorKind::ConnectionAborted
+                    | std::io::ErrorKind::BrokenPipe
+                    | std::io::ErrorKind::UnexpectedEof
+            );
+        }
+        source = e.source();
+    }
+    false
+}
+
+/// Read how long to wait from `Retry-After`, which is seconds or HTTP date, or `X-RateLimit-Reset`.
+fn retry_after(header: &HeaderMap, now: DateTime<Utc>) -> Duration {
+    let until = |time: DateTime<Utc>| (time - now).to_std().unwrap_or(Duration::ZERO);
+    if let Some(value) = header.get(RETRY_AFTER).and_then(|v| v.to_str().ok()) {
+        if let Ok(seconds) = value.trim().parse::<u64>() {
+            return Duration::from_secs(seconds);
+        }
+        if let Ok(date) = DateTime::parse_from_rfc2822(value.trim()) {
+            return until(date.with_timezone(&Utc));
+        }
+    }
+    match RateLimit::from_header(header).and_then(|r| r.reset) {
+        Some(reset) => until(reset),
+        None => DEFAULT_RETRY_AFTER,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    #[derive(Debug, Default)]
+    struct Signer {
+        called: AtomicUsize,
+    }
+
+    #[async_trait]
+    impl Middleware for Signer {
+        async fn before_send(&self, request: &mut Request) -> Result<(), Error> {
+            self.called.fetch_add(1, Ordering::SeqCst);
+            request
+                .headers_mut()
+                .insert("signature", HeaderValue::from_static("signed"));
+            Ok(())
+        }
+    }
+
+    #[tokio::test]
+    async fn test_before_send() {
+        let signer = Arc::new(Signer::default());
+        let client = HttpClient {
+            client: reqwest::Client::new(),
+            stream_client: reqwest::Client::new(),
+            user_agent: Some(HeaderValue::from_static("test")),
+            middlewares: vec![signer.clone()],
+            rate_limit_mode: RateLimitMode::Ignore,
+            rate_limit: Arc::new(Mutex::new(HashMap::new())),
+            retry_policy: None,
+        };
+        // Nothing listens on the port, so the request fails after the middleware is called.
+        let res = client.send(client.get("http://127.0.0.1:1/")).await;
+        assert!(res.is_err());
+        assert_eq!(signer.called.load(Ordering::SeqCst), 1);
+    }
+
+    #[test]
+    fn test_retry_after() {
+        let now = Utc.with_ymd_and_hms(2024, 5, 1, 10, 0, 0).unwrap();
+        let mut header = HeaderMap::new();
+        assert_eq!(retry_after(&header, now), DEFAULT_RETRY_AFTER);
+
+        header.insert("x-ratelimit-limit", HeaderValue::from_static("300"));
+        header.insert("x-ratelimit-remaining", HeaderValue::from_static("0"));
+        header.insert(
+            "x-ratelimit-reset",
+            HeaderValue::from_static("2024-05-01T10:05:00Z"),
+        );
+        assert_eq!(retry_after(&header, now), Duration::from_secs(300));
+
+        header.insert(
+            RETRY_AFTER,
+            HeaderValue::from_static("Wed, 01 May 2024 10:00:30 GMT"),
+        );
+        assert_eq!(retry_after(&header, now), Duration::from_secs(30));
+
+        header.insert(RETRY_AFTER, HeaderValue::from_static("10"));
+        assert_eq!(retry_after(&header, now), Duration::from_secs(10));
+    }
+
+    #[test]
+    fn test_retry_policy_interval() {
+        let policy = RetryPolicy::default();
+        assert_eq!(policy.interval(1), Duration::from_millis(500));
+        assert_eq!(policy.interval(2), Duration::from_secs(1));
+        assert_eq!(policy.interval(3), Duration::from_secs(2));
+        assert_eq!(policy.interval(10), Duration::from_secs(10));
+    }
+
+    #[test]
+    fn test_is_idempotent() {
+        let client = reqwest::Client::new();
+        let request = client.get("http://localhost/").build().unwrap();
+        assert!(is_idempotent(&request));
+        let request = client.post("http://localhost/").build().unwrap();
+        assert!(!is_idempotent(&request));
+        let request = client
+            .post("http://localhost/")
+            .header("Idempotency-Key", "key")
+            .build()
+            .unwrap();
+        assert!(is_idempotent(&request));
+    }
+
+    #[test]
+    fn test_rate_limit_bucket() {
+        let client = reqwest::Client::new();
+        let request = client
+            .post("http://localhost/api/v2/media")
+            .build()
+            .unwrap();
+        assert_eq!(RateLimitBucket::of(&request), RateLimitBucket::Media);
+        let request = client
+            .get("http://localhost/api/v1/media/1")
+            .build()
+            .unwrap();
+        assert_eq!(RateLimitBucket::of(&request), RateLimitBucket::Default);
+        let request = client
+            .delete("http://localhost/api/v1/statuses/1")
+            .build()
+            .unwrap();
+        assert_eq!(
+            RateLimitBucket::of(&request),
+            RateLimitBucket::StatusDeletion
+        );
+        let request = client
+            .post("http://localhost/api/v1/statuses/1/unreblog")
+            .build()
+            .unwrap();
+        assert_eq!(
+            RateLimitBucket::of(&request),
+            RateLimitBucket::StatusDeletion
+        );
+        let request = client
+            .post("http://localhost/api/v1/statuses")
+            .build()
+            .unwrap();
+        assert_eq!(RateLimitBucket::of(&request), RateLimitBucket::Default);
+    }
+
+    #[tokio::test]
+    async fn test_wait_for_rate_limit_per_bucket() {
+        let client = HttpClient {
+            client: reqwest::Client::new(),
+            stream_client: reqwest::Client::new(),
+            user_agent: None,
+            middlewares: vec![],
+            rate_limit_mode: RateLimitMode::Wait { max_retries: 0 },
+            rate_limit: Arc::new(Mutex::new(HashMap::new())),
+            retry_policy: None,
+        };
+        client.rate_limit.lock().unwrap().insert(
+            RateLimitBucket::Media,
+            RateLimit {
+                limit: 30,
+                remaining: 0,
+                reset: Some(Utc::now() + chrono::Duration::hours(1)),
+            },
+        );
+        // Exhausted media uploads must not block the other endpoints.
+        let wait = client.wait_for_rate_limit(RateLimitBucket::Default);
+        assert!(tokio::time::timeout(Duration::from_secs(1), wait)
+            .await
+            .is_ok());
+        let wait = client.wait_for_rate_limit(RateLimitBucket::Media);
+        assert!(tokio::time::timeout(Duration::from_millis(100), wait)
+            .await
+            .is_err());
+    }
+
+    #[test]
+    fn test_idempotency_header() {
+        let headers = idempotency_header(Some("my-key")).unwrap();
+        assert_eq!(headers.get(IDEMPOTENCY_KEY).unwrap(), "my-key");
+
+        let first = idempotency_header(None).unwrap();
+        let second = idempotency_header(None).unwrap();
+        assert_ne!(first.get(IDEMPOTENCY_KEY), second.get(IDEMPOTENCY_KEY));
+    }
+
+    #[tokio::test]
+    async fn test_retry_transient_status() {
+        use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move {
+            for status in ["503 Service Unavailable", "200 OK"] {
+                let (mut socket, _) = listener.accept().await.unwrap();
+                let mut buf = [0; 1024];
+                let _ = socket.read(&mut buf).await;
+                let res = format!(
+                    "HTTP/1.1 {}\r\nContent-Length: 0\r\nConnection: close\r\n\r\n",
+                    status
+                );
+                socket.write_all(res.as_bytes()).await.unwrap();
+            }
+        });
+
+        let client = ClientBuilder::new()
+            .retry_policy(RetryPolicy {
+                initial_interval: Duration::from_millis(1),
+                ..Default::default()
+            })
+            .build(None)
+            .unwrap();
+        let res = client
+            .send(client.get(format!("http://{}/", addr)))
+            .await
+            .unwrap();
+        assert_eq!(res.status(), StatusCode::OK);
+    }
+}
+
+/// Local HTTP server to test requests of the API clients.
+#[cfg(test)]
+pub(crate) mod testing {
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+    use tokio::net::{TcpListener, TcpStream};
+    use tokio::sync::mpsc;
+
+    /// Request which is received by the server.
+    #[derive(Debug)]
+    pub(crate) struct ReceivedRequest {
+        pub method: String,
+        pub path: String,
+        pub body: String,
+    }
+
+    impl ReceivedRequest {
+        /// Parse the body as JSON.
+        pub fn json(&self) -> serde_json::Value {
+            serde_json::from_str(&self.body).unwrap()
+        }
+    }
+
+    /// Respond with the JSON bodies in order, and return the base URL and the received requests.
+    pub(crate) async fn serve(
+        responses: Vec<String>,
+    ) -> (String, mpsc::UnboundedReceiver<ReceivedRequest>) {
+        serve_with_status(responses.into_iter().map(|body| (200, body)).collect()).await
+    }
+
+    /// Respond with the status codes and the JSON bodies in order.
+    pub(crate) async fn serve_with_status(
+        responses: Vec<(u16, String)>,
+    ) -> (String, mpsc::UnboundedReceiver<ReceivedRequest>) {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let (tx, rx) = mpsc::unbounded_channel();
+        tokio::spawn(async move {
+            for (status, body) in responses {
+                let (mut socket, _) = listener.accept().await.unwrap();
+                let _ = tx.send(read_request(&mut socket).await);
+                let res = format!(
+                    "HTTP/1.1 {} Status\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                    status,
+                    body.len(),
+                    body
+                );
+                socket.write_all(res.as_bytes()).await.unwrap();
+            }
+        });
+        (format!("http://{}", addr), rx)
+    }
+
+    async fn read_request(socket: &mut TcpStream) -> ReceivedRequest {
+        let mut buf = Vec::new();
+        let mut chunk = [0; 4096];
+        let header_end = loop {
+            if let Some(pos) = buf.windows(4).position(|w| w == b"\r\n\r\n") {
+                break pos + 4;
+            }
+            let n = socket.read(&mut chunk).await.unwrap();
+            assert!(n > 0, "connection is closed before headers");
+            buf.extend_from_slice(&chunk[..n]);
+        };
+        let head = String::from_utf8_lossy(&buf[..header_end]).to_string();
+        let length = head
+            .lines()
+            .filter_map(|line| line.split_once(':'))
+            .find(|(name, _)| name.eq_ignore_ascii_case("content-length"))
+            .and_then(|(_, value)| value.trim().parse::<usize>().ok())
+            .unwrap_or(0);
+        while buf.len() < header_end + length {
+            let n = socket.read(&mut chunk).await.unwrap();
+            assert!(n > 0, "connection is closed before body");
+            buf.extend_from_slice(&chunk[..n]);
+        }
+        let mut request_line = head.split_whitespace();
+        ReceivedRequest {
+            method: request_line.next().unwrap_or_default().to_string(),
+            path: request_line.next().unwrap_or_default().to_string(),
+            body: String::from_utf8_lossy(&buf[header_end..header_end + length]).to_string(),
+        }
+    }
+}
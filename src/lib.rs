@@ -45,25 +45,33 @@
 
 use std::{fmt, str::FromStr};
 
+pub mod capabilities;
 pub mod default;
 pub mod detector;
+pub mod diff;
 pub mod entities;
 pub mod error;
+pub mod filtering;
 pub mod firefish;
 pub mod friendica;
 pub mod gotosocial;
+pub mod http;
 pub mod mastodon;
 pub mod megalodon;
 pub mod oauth;
+pub mod pagination;
 pub mod pixelfed;
 pub mod pleroma;
+pub mod polling;
 pub mod response;
+pub(crate) mod sse;
 pub mod streaming;
 pub(crate) mod tls;
+pub(crate) mod web_socket;
 
 pub use self::megalodon::Megalodon;
 use crate::error::Error;
-pub use detector::detector;
+pub use detector::{detector, detector_with_client};
 use serde::{Deserialize, Serialize};
 pub use streaming::Streaming;
 
@@ -119,30 +127,62 @@ pub fn generator(
     base_url: String,
     access_token: Option<String>,
     user_agent: Option<String>,
+) -> Result<Box<dyn Megalodon + Send + Sync>, Error> {
+    generator_with_client(
+        sns,
+        base_url,
+        access_token,
+        user_agent,
+        http::ClientBuilder::default(),
+    )
+}
+
+/// Generate an API client which satisfies megalodon trait with the HTTP client settings.
+///
+/// The same [`http::ClientBuilder`] can be used for any SNS.
+/// Proxies, certificates, timeouts and middlewares are applied to the REST API and Server-Sent Events streaming,
+/// except that the timeout of the whole request is not applied to Server-Sent Events.
+/// WebSocket streaming does not use them, and sends only the user agent.
+pub fn generator_with_client(
+    sns: SNS,
+    base_url: String,
+    access_token: Option<String>,
+    user_agent: Option<String>,
+    client: http::ClientBuilder,
 ) -> Result<Box<dyn Megalodon + Send + Sync>, Error> {
     match sns {
         SNS::Pleroma => {
-            let pleroma = pleroma::Pleroma::new(base_url, access_token, user_agent)?;
+            let pleroma =
+                pleroma::Pleroma::new_with_client(base_url, access_token, user_agent, client)?;
             Ok(Box::new(pleroma))
         }
         SNS::Friendica => {
-            let friendica = friendica::Friendica::new(base_url, access_token, user_agent)?;
+            let friendica =
+                friendica::Friendica::new_with_client(base_url, access_token, user_agent, client)?;
             Ok(Box::new(friendica))
         }
         SNS::Mastodon => {
-            let mastodon = mastodon::Mastodon::new(base_url, access_token, user_agent)?;
+            let mastodon =
+                mastodon::Mastodon::new_with_client(base_url, access_token, user_agent, client)?;
             Ok(Box::new(mastodon))
         }
         SNS::Firefish => {
-            let firefish = firefish::Firefish::new(base_url, access_token, user_agent)?;
+            let firefish =
+                firefish::Firefish::new_with_client(base_url, access_token, user_agent, client)?;
             Ok(Box::new(firefish))
         }
         SNS::Gotosocial => {
-            let gotosocial = gotosocial::Gotosocial::new(base_url, access_token, user_agent)?;
+            let gotosocial = gotosocial::Gotosocial::new_with_client(
+                base_url,
+                access_token,
+                user_agent,
+                client,
+            )?;
             Ok(Box::new(gotosocial))
         }
         SNS::Pixelfed => {
-            let pixelfed = pixelfed::Pixelfed::new(base_url, access_token, user_agent)?;
+            let pixelfed =
+                pixelfed::Pixelfed::new_with_client(base_url, access_token, user_agent, client)?;
             Ok(Box::new(pixelfed))
         }
     }
@@ -0,0 +1,403 @@
+use super::{instance::InstanceRule, report::Category, History, Role, Status};
+use crate::entities as MegalodonEntities;
+use chrono::{DateTime, Utc};
+use serde::Deserialize;
+
+#[derive(Debug, Deserialize, Clone)]
+pub struct Account {
+    id: String,
+    username: String,
+    domain: Option<String>,
+    created_at: DateTime<Utc>,
+    email: String,
+    ip: Option<String>,
+    ips: Vec<Ip>,
+    locale: Option<String>,
+    invite_request: Option<String>,
+    role: Option<Role>,
+    confirmed: bool,
+    approved: bool,
+    disabled: bool,
+    silenced: bool,
+    suspended: bool,
+    account: super::Account,
+}
+
+#[derive(Debug, Deserialize, Clone)]
+pub struct Ip {
+    ip: String,
+    used_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Deserialize, Clone)]
+pub struct Report {
+    id: String,
+    action_taken: bool,
+    action_taken_at: Option<DateTime<Utc>>,
+    category: Category,
+    comment: String,
+    forwarded: bool,
+    created_at: DateTime<Utc>,
+    updated_at: Option<DateTime<Utc>>,
+    account: Account,
+    target_account: Account,
+    assigned_account: Option<Account>,
+    action_taken_by_account: Option<Account>,
+    statuses: Vec<Status>,
+    rules: Vec<InstanceRule>,
+}
+
+#[derive(Debug, Deserialize, Clone)]
+pub struct DomainBlock {
+    id: String,
+    domain: String,
+    digest: Option<String>,
+    created_at: DateTime<Utc>,
+    severity: DomainBlockSeverity,
+    reject_media: bool,
+    reject_reports: bool,
+    private_comment: Option<String>,
+    public_comment: Option<String>,
+    obfuscate: bool,
+}
+
+#[derive(Debug, Deserialize, Clone)]
+#[serde(rename_all = "lowercase")]
+pub enum DomainBlockSeverity {
+    Silence,
+    Suspend,
+    Noop,
+}
+
+#[derive(Debug, Deserialize, Clone)]
+pub struct DomainAllow {
+    id: String,
+    domain: String,
+    created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Deserialize, Clone)]
+pub struct EmailDomainBlock {
+    id: String,
+    domain: String,
+    created_at: DateTime<Utc>,
+    history: Vec<History>,
+}
+
+#[derive(Debug, Deserialize, Clone)]
+pub struct IpBlock {
+    id: String,
+    ip: String,
+    severity: IpBlockSeverity,
+    comment: String,
+    created_at: DateTime<Utc>,
+    expires_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Deserialize, Clone)]
+#[serde(rename_all = "snake_case")]
+pub enum IpBlockSeverity {
+    SignUpRequiresApproval,
+    SignUpBlock,
+    NoAccess,
+}
+
+#[derive(Debug, Deserialize, Clone)]
+pub struct CanonicalEmailBlock {
+    id: String,
+    canonical_email_hash: String,
+}
+
+#[derive(Debug, Deserialize, Clone)]
+pub struct Measure {
+    key: String,
+    unit: Option<String>,
+    total: String,
+    human_value: Option<String>,
+    previous_total: Option<String>,
+    data: Vec<MeasureData>,
+}
+
+#[derive(Debug, Deserialize, Clone)]
+pub struct MeasureData {
+    date: DateTime<Utc>,
+    value: String,
+}
+
+#[derive(Debug, Deserialize, Clone)]
+pub struct Dimension {
+    key: String,
+    data: Vec<DimensionData>,
+}
+
+#[derive(Debug, Deserialize, Clone)]
+pub struct DimensionData {
+    key: String,
+    human_key: String,
+    value: String,
+    unit: Option<String>,
+    human_value: Option<String>,
+}
+
+#[derive(Debug, Deserialize, Clone)]
+pub struct Cohort {
+    period: DateTime<Utc>,
+    frequency: CohortFrequency,
+    data: Vec<CohortData>,
+}
+
+#[derive(Debug, Deserialize, Clone)]
+#[serde(rename_all = "lowercase")]
+pub enum CohortFrequency {
+    Day,
+    Month,
+}
+
+#[derive(Debug, Deserialize, Clone)]
+pub struct CohortData {
+    date: DateTime<Utc>,
+    rate: f64,
+    value: u64,
+}
+
+impl From<Account> for MegalodonEntities::admin::Account {
+    fn from(val: Account) -> Self {
+        MegalodonEntities::admin::Account {
+            id: val.id,
+            username: val.username,
+            domain: val.domain,
+            created_at: Some(val.created_at),
+            email: Some(val.email),
+            ip: val.ip,
+            ips: val.ips.into_iter().map(|i| i.into()).collect(),
+            locale: val.locale,
+            invite_request: val.invite_request,
+            role: val.role.map(|r| r.into()),
+            confirmed: val.confirmed,
+            approved: val.approved,
+            disabled: val.disabled,
+            silenced: val.silenced,
+            suspended: val.suspended,
+            account: Some(val.account.into()),
+        }
+    }
+}
+
+impl From<Ip> for MegalodonEntities::admin::Ip {
+    fn from(val: Ip) -> Self {
+        MegalodonEntities::admin::Ip {
+            ip: val.ip,
+            used_at: val.used_at,
+        }
+    }
+}
+
+impl From<Report> for MegalodonEntities::admin::Report {
+    fn from(val: Report) -> Self {
+        MegalodonEntities::admin::Report {
+            id: val.id,
+            action_taken: val.action_taken,
+            action_taken_at: val.action_taken_at,
+            category: val.category.into(),
+            comment: val.comment,
+            forwarded: val.forwarded,
+            created_at: val.created_at,
+            updated_at: val.updated_at,
+            account: val.account.into(),
+            target_account: val.target_account.into(),
+            assigned_account: val.assigned_account.map(|a| a.into()),
+            action_taken_by_account: val.action_taken_by_account.map(|a| a.into()),
+            statuses: val.statuses.into_iter().map(|s| s.into()).collect(),
+            rules: val.rules.into_iter().map(|r| r.into()).collect(),
+        }
+    }
+}
+
+impl From<DomainBlockSeverity> for MegalodonEntities::admin::DomainBlockSeverity {
+    fn from(val: DomainBlockSeverity) -> Self {
+        match val {
+            DomainBlockSeverity::Silence => MegalodonEntities::admin::DomainBlockSeverity::Silence,
+            DomainBlockSeverity::Suspend => MegalodonEntities::admin::DomainBlockSeverity::Suspend,
+            DomainBlockSeverity::Noop => MegalodonEntities::admin::DomainBlockSeverity::Noop,
+        }
+    }
+}
+
+impl From<DomainBlock> for MegalodonEntities::admin::DomainBlock {
+    fn from(val: DomainBlock) -> Self {
+        MegalodonEntities::admin::DomainBlock {
+            id: val.id,
+            domain: val.domain,
+            digest: val.digest,
+            created_at: val.created_at,
+            severity: val.severity.into(),
+            reject_media: val.reject_media,
+            reject_reports: val.reject_reports,
+            private_comment: val.private_comment,
+            public_comment: val.public_comment,
+            obfuscate: val.obfuscate,
+        }
+    }
+}
+
+impl From<DomainAllow> for MegalodonEntities::admin::DomainAllow {
+    fn from(val: DomainAllow) -> Self {
+        MegalodonEntities::admin::DomainAllow {
+            id: val.id,
+            domain: val.domain,
+            created_at: val.created_at,
+        }
+    }
+}
+
+impl From<EmailDomainBlock> for MegalodonEntities::admin::EmailDomainBlock {
+    fn from(val: EmailDomainBlock) -> Self {
+        MegalodonEntities::admin::EmailDomainBlock {
+            id: val.id,
+            domain: val.domain,
+            created_at: val.created_at,
+            history: val.history.into_iter().map(|h| h.into()).collect(),
+        }
+    }
+}
+
+impl From<IpBlockSeverity> for MegalodonEntities::admin::IpBlockSeverity {
+    fn from(val: IpBlockSeverity) -> Self {
+        match val {
+            IpBlockSeverity::SignUpRequiresApproval => {
+                MegalodonEntities::admin::IpBlockSeverity::SignUpRequiresApproval
+            }
+            IpBlockSeverity::SignUpBlock => MegalodonEntities::admin::IpBlockSeverity::SignUpBlock,
+            IpBlockSeverity::NoAccess => MegalodonEntities::admin::IpBlockSeverity::NoAccess,
+        }
+    }
+}
+
+impl From<IpBlock> for MegalodonEntities::admin::IpBlock {
+    fn from(val: IpBlock) -> Self {
+        MegalodonEntities::admin::IpBlock {
+            id: val.id,
+            ip: val.ip,
+            severity: val.severity.into(),
+            comment: val.comment,
+            created_at: val.created_at,
+            expires_at: val.expires_at,
+        }
+    }
+}
+
+impl From<CanonicalEmailBlock> for MegalodonEntities::admin::CanonicalEmailBlock {
+    fn from(val: CanonicalEmailBlock) -> Self {
+        MegalodonEntities::admin::CanonicalEmailBlock {
+            id: val.id,
+            canonical_email_hash: val.canonical_email_hash,
+        }
+    }
+}
+
+impl From<Measure> for MegalodonEntities::admin::Measure {
+    fn from(val: Measure) -> Self {
+        MegalodonEntities::admin::Measure {
+            key: val.key,
+            unit: val.unit,
+            total: val.total,
+            human_value: val.human_value,
+            previous_total: val.previous_total,
+            data: val
+                .data
+                .into_iter()
+                .map(|d| MegalodonEntities::admin::MeasureData {
+                    date: d.date,
+                    value: d.value,
+                })
+                .collect(),
+        }
+    }
+}
+
+impl From<Dimension> for MegalodonEntities::admin::Dimension {
+    fn from(val: Dimension) -> Self {
+        MegalodonEntities::admin::Dimension {
+            key: val.key,
+            data: val
+                .data
+                .into_iter()
+                .map(|d| MegalodonEntities::admin::DimensionData {
+                    key: d.key,
+                    human_key: d.human_key,
+                    value: d.value,
+                    unit: d.unit,
+                    human_value: d.human_value,
+                })
+                .collect(),
+        }
+    }
+}
+
+impl From<Cohort> for MegalodonEntities::admin::Cohort {
+    fn from(val: Cohort) -> Self {
+        MegalodonEntities::admin::Cohort {
+            period: val.period,
+            frequency: match val.frequency {
+                CohortFrequency::Day => MegalodonEntities::admin::CohortFrequency::Day,
+                CohortFrequency::Month => MegalodonEntities::admin::CohortFrequency::Month,
+            },
+            data: val
+                .data
+                .into_iter()
+                .map(|d| MegalodonEntities::admin::CohortData {
+                    date: d.date,
+                    rate: d.rate,
+                    value: d.value,
+                })
+                .collect(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_measure() {
+        let text = r#"[{
+            "key": "active_users",
+            "unit": null,
+            "total": "2",
+            "previous_total": "0",
+            "data": [
+                { "date": "2022-09-14T00:00:00Z", "value": "0" },
+                { "date": "2022-09-15T00:00:00Z", "value": "2" }
+            ]
+        }]"#;
+
+        let measures: Vec<MegalodonEntities::admin::Measure> =
+            serde_json::from_str::<Vec<Measure>>(text)
+                .unwrap()
+                .into_iter()
+                .map(|m| m.into())
+                .collect();
+        assert_eq!(measures[0].key, "active_users");
+        assert_eq!(measures[0].data[1].value, "2");
+    }
+
+    #[test]
+    fn test_parse_ip_block() {
+        let text = r#"{
+            "id": "1",
+            "ip": "8.8.8.8/32",
+            "severity": "no_access",
+            "comment": "",
+            "created_at": "2022-11-16T07:22:00.501Z",
+            "expires_at": null
+        }"#;
+
+        let block: MegalodonEntities::admin::IpBlock =
+            serde_json::from_str::<IpBlock>(text).unwrap().into();
+        assert_eq!(
+            block.severity,
+            MegalodonEntities::admin::IpBlockSeverity::NoAccess
+        );
+    }
+}
@@ -1,3 +1,4 @@
+use super::{Account, History};
 use crate::entities as MegalodonEntities;
 use serde::{Deserialize, Serialize};
 
@@ -17,6 +18,21 @@ pub struct Card {
     height: u32,
     embed_url: String,
     blurhash: Option<String>,
+    authors: Option<Vec<CardAuthor>>,
+}
+
+#[derive(Debug, Deserialize, Clone)]
+pub struct CardAuthor {
+    name: String,
+    url: String,
+    account: Option<Account>,
+}
+
+#[derive(Debug, Deserialize, Clone)]
+pub struct TrendsLink {
+    #[serde(flatten)]
+    card: Card,
+    history: Vec<History>,
 }
 
 #[derive(Debug, Clone, Deserialize, Serialize)]
@@ -56,6 +72,75 @@ impl From<Card> for MegalodonEntities::Card {
             height: Some(val.height),
             embed_url: Some(val.embed_url),
             blurhash: val.blurhash,
+            authors: val
+                .authors
+                .unwrap_or_default()
+                .into_iter()
+                .map(|a| a.into())
+                .collect(),
+        }
+    }
+}
+
+impl From<CardAuthor> for MegalodonEntities::CardAuthor {
+    fn from(val: CardAuthor) -> Self {
+        MegalodonEntities::CardAuthor {
+            name: val.name,
+            url: val.url,
+            account: val.account.map(|a| a.into()),
+        }
+    }
+}
+
+impl From<TrendsLink> for MegalodonEntities::TrendsLink {
+    fn from(val: TrendsLink) -> Self {
+        MegalodonEntities::TrendsLink {
+            card: val.card.into(),
+            history: val.history.into_iter().map(|h| h.into()).collect(),
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_trends_link() {
+        let json = r#"{
+  "url": "https://www.nbcnews.com/specials/plan-your-vote-2022-elections/index.html",
+  "title": "Plan Your Vote: 2022 Elections",
+  "description": "Everything you need to know about the voting rules where you live.",
+  "type": "link",
+  "author_name": "NBC News",
+  "author_url": "",
+  "provider_name": "NBC News",
+  "provider_url": "",
+  "html": "",
+  "width": 400,
+  "height": 225,
+  "image": "https://files.mastodon.social/cache/preview_cards/images/045/027/478/original/0783d5e91a14fd49.jpeg",
+  "embed_url": "",
+  "blurhash": "UcQmF#ay~qofj[WBj[j[~qof9Fayofofayay",
+  "authors": [
+    {
+      "name": "NBC News",
+      "url": "https://www.nbcnews.com/",
+      "account": null
+    }
+  ],
+  "history": [
+    {
+      "day": "1661817600",
+      "accounts": "7",
+      "uses": "7"
+    }
+  ]
+}"#;
+        let link: TrendsLink = serde_json::from_str(json).unwrap();
+        let link: MegalodonEntities::TrendsLink = link.into();
+        assert_eq!(link.card.title, "Plan Your Vote: 2022 Elections");
+        assert_eq!(link.card.authors[0].name, "NBC News");
+        assert_eq!(link.history[0].uses, 7);
+    }
+}
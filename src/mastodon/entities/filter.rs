@@ -19,6 +19,7 @@ pub enum FilterContext {
     Notifications,
     Public,
     Thread,
+    Account,
 }
 
 impl From<FilterContext> for MegalodonEntities::filter::FilterContext {
@@ -28,6 +29,7 @@ impl From<FilterContext> for MegalodonEntities::filter::FilterContext {
             FilterContext::Notifications => MegalodonEntities::filter::FilterContext::Notifications,
             FilterContext::Public => MegalodonEntities::filter::FilterContext::Public,
             FilterContext::Thread => MegalodonEntities::filter::FilterContext::Thread,
+            FilterContext::Account => MegalodonEntities::filter::FilterContext::Account,
         }
     }
 }
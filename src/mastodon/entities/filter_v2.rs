@@ -0,0 +1,191 @@
+use super::filter::FilterContext;
+use crate::entities as MegalodonEntities;
+use chrono::{DateTime, Utc};
+use serde::Deserialize;
+
+#[derive(Debug, Deserialize, Clone)]
+pub struct FilterV2 {
+    id: String,
+    title: String,
+    context: Vec<FilterContext>,
+    expires_at: Option<DateTime<Utc>>,
+    filter_action: FilterAction,
+    // Filters in FilterResult do not have rules.
+    #[serde(default)]
+    keywords: Vec<FilterKeyword>,
+    #[serde(default)]
+    statuses: Vec<FilterStatus>,
+}
+
+#[derive(Debug, Deserialize, Clone)]
+#[serde(rename_all = "lowercase")]
+pub enum FilterAction {
+    Warn,
+    Hide,
+    Blur,
+    #[serde(other)]
+    Unknown,
+}
+
+#[derive(Debug, Deserialize, Clone)]
+pub struct FilterKeyword {
+    id: String,
+    keyword: String,
+    whole_word: bool,
+}
+
+#[derive(Debug, Deserialize, Clone)]
+pub struct FilterStatus {
+    id: String,
+    status_id: String,
+}
+
+#[derive(Debug, Deserialize, Clone)]
+pub struct FilterResult {
+    filter: FilterV2,
+    keyword_matches: Option<Vec<String>>,
+    status_matches: Option<Vec<String>>,
+}
+
+impl From<FilterAction> for MegalodonEntities::FilterAction {
+    fn from(val: FilterAction) -> Self {
+        match val {
+            FilterAction::Warn => MegalodonEntities::FilterAction::Warn,
+            FilterAction::Hide => MegalodonEntities::FilterAction::Hide,
+            FilterAction::Blur => MegalodonEntities::FilterAction::Blur,
+            // Warning is the mildest action, so the status is still shown.
+            FilterAction::Unknown => MegalodonEntities::FilterAction::Warn,
+        }
+    }
+}
+
+impl From<FilterKeyword> for MegalodonEntities::FilterKeyword {
+    fn from(val: FilterKeyword) -> Self {
+        MegalodonEntities::FilterKeyword {
+            id: val.id,
+            keyword: val.keyword,
+            whole_word: val.whole_word,
+        }
+    }
+}
+
+impl From<FilterStatus> for MegalodonEntities::FilterStatus {
+    fn from(val: FilterStatus) -> Self {
+        MegalodonEntities::FilterStatus {
+            id: val.id,
+            status_id: val.status_id,
+        }
+    }
+}
+
+impl From<FilterV2> for MegalodonEntities::FilterV2 {
+    fn from(val: FilterV2) -> Self {
+        MegalodonEntities::FilterV2 {
+            id: val.id,
+            title: val.title,
+            context: val.context.into_iter().map(|i| i.into()).collect(),
+            expires_at: val.expires_at,
+            filter_action: val.filter_action.into(),
+            keywords: val.keywords.into_iter().map(|i| i.into()).collect(),
+            statuses: val.statuses.into_iter().map(|i| i.into()).collect(),
+        }
+    }
+}
+
+impl From<FilterResult> for MegalodonEntities::FilterResult {
+    fn from(val: FilterResult) -> Self {
+        MegalodonEntities::FilterResult {
+            filter: val.filter.into(),
+            keyword_matches: val.keyword_matches,
+            status_matches: val.status_matches,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_filter_result() {
+        let json = r#"{
+            "filter": {
+                "id": "3",
+                "title": "Spoilers",
+                "context": ["home", "public", "account"],
+                "expires_at": null,
+                "filter_action": "blur"
+            },
+            "keyword_matches": ["finale"],
+            "status_matches": null
+        }"#;
+        let result: FilterResult = serde_json::from_str(json).unwrap();
+        let result: MegalodonEntities::FilterResult = result.into();
+        assert_eq!(
+            result.filter.filter_action,
+            MegalodonEntities::FilterAction::Blur
+        );
+        assert_eq!(
+            result.filter.context[2],
+            MegalodonEntities::filter::FilterContext::Account
+        );
+        assert!(result.filter.keywords.is_empty());
+        assert_eq!(result.keyword_matches, Some(vec![String::from("finale")]));
+    }
+
+    #[test]
+    fn test_parse_unknown_filter_action() {
+        let json = r#"{
+            "id": "4",
+            "title": "Future",
+            "context": ["home"],
+            "expires_at": null,
+            "filter_action": "dim"
+        }"#;
+        let filter: FilterV2 = serde_json::from_str(json).unwrap();
+        let filter: MegalodonEntities::FilterV2 = filter.into();
+        assert_eq!(filter.filter_action, MegalodonEntities::FilterAction::Warn);
+    }
+
+    #[test]
+    fn test_parse_filter_v2() {
+        let json = r#"{
+            "id": "19972",
+            "title": "Test filter",
+            "context": ["home"],
+            "expires_at": "2022-09-20T17:27:39.296Z",
+            "filter_action": "warn",
+            "keywords": [
+                {"id": "1197", "keyword": "bad word", "whole_word": false}
+            ],
+            "statuses": [
+                {"id": "1", "status_id": "109031743575371913"}
+            ]
+        }"#;
+        let filter: FilterV2 = serde_json::from_str(json).unwrap();
+        let filter: MegalodonEntities::FilterV2 = filter.into();
+        assert_eq!(filter.id, "19972");
+        assert_eq!(filter.title, "Test filter");
+        assert_eq!(
+            filter.context,
+            vec![MegalodonEntities::filter::FilterContext::Home]
+        );
+        assert!(filter.expires_at.is_some());
+        assert_eq!(filter.filter_action, MegalodonEntities::FilterAction::Warn);
+        assert_eq!(
+            filter.keywords,
+            vec![MegalodonEntities::FilterKeyword {
+                id: String::from("1197"),
+                keyword: String::from("bad word"),
+                whole_word: false,
+            }]
+        );
+        assert_eq!(
+            filter.statuses,
+            vec![MegalodonEntities::FilterStatus {
+                id: String::from("1"),
+                status_id: String::from("109031743575371913"),
+            }]
+        );
+    }
+}
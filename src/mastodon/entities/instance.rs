@@ -33,6 +33,7 @@ pub struct InstanceConfig {
 pub struct Statuses {
     pub max_characters: u32,
     pub max_media_attachments: u32,
+    #[serde(default)]
     pub characters_reserved_per_url: u32,
 }
 
@@ -0,0 +1,263 @@
+use super::instance::{InstanceRule, Polls, Statuses};
+use super::Account;
+use crate::entities as MegalodonEntities;
+use serde::Deserialize;
+use std::collections::HashMap;
+
+#[derive(Debug, Deserialize, Clone)]
+pub struct InstanceV2 {
+    domain: String,
+    title: String,
+    version: String,
+    source_url: Option<String>,
+    description: String,
+    thumbnail: Option<InstanceThumbnail>,
+    #[serde(default)]
+    languages: Vec<String>,
+    configuration: InstanceV2Config,
+    registrations: InstanceRegistrations,
+    #[serde(default)]
+    api_versions: HashMap<String, u32>,
+    contact: InstanceContact,
+    #[serde(default)]
+    rules: Vec<InstanceRule>,
+}
+
+#[derive(Debug, Deserialize, Clone)]
+pub struct InstanceThumbnail {
+    url: String,
+    blurhash: Option<String>,
+    versions: Option<ThumbnailVersions>,
+}
+
+#[derive(Debug, Deserialize, Clone)]
+pub struct ThumbnailVersions {
+    #[serde(rename = "@1x")]
+    at_1x: Option<String>,
+    #[serde(rename = "@2x")]
+    at_2x: Option<String>,
+}
+
+#[derive(Debug, Deserialize, Clone)]
+pub struct InstanceV2Config {
+    urls: InstanceV2URLs,
+    statuses: Statuses,
+    media_attachments: Option<MediaAttachments>,
+    polls: Option<Polls>,
+    #[serde(default)]
+    translation: Translation,
+}
+
+#[derive(Debug, Deserialize, Clone)]
+pub struct InstanceV2URLs {
+    streaming: Option<String>,
+}
+
+#[derive(Debug, Deserialize, Clone)]
+pub struct MediaAttachments {
+    supported_mime_types: Vec<String>,
+    description_limit: Option<u32>,
+    image_size_limit: u32,
+    image_matrix_limit: u32,
+    video_size_limit: u32,
+    video_frame_rate_limit: u32,
+    video_matrix_limit: u32,
+}
+
+#[derive(Debug, Deserialize, Clone, Default)]
+pub struct Translation {
+    enabled: bool,
+}
+
+#[derive(Debug, Deserialize, Clone)]
+pub struct InstanceRegistrations {
+    enabled: bool,
+    approval_required: bool,
+    message: Option<String>,
+    url: Option<String>,
+}
+
+#[derive(Debug, Deserialize, Clone)]
+pub struct InstanceContact {
+    email: String,
+    account: Option<Account>,
+}
+
+impl From<InstanceV2> for MegalodonEntities::InstanceV2 {
+    fn from(val: InstanceV2) -> Self {
+        MegalodonEntities::InstanceV2 {
+            domain: val.domain,
+            title: val.title,
+            version: val.version,
+            source_url: val.source_url,
+            description: val.description,
+            thumbnail: val.thumbnail.map(|t| t.into()),
+            languages: val.languages,
+            configuration: val.configuration.into(),
+            registrations: val.registrations.into(),
+            api_versions: val.api_versions,
+            contact: MegalodonEntities::instance_v2::InstanceContact {
+                email: val.contact.email,
+                account: val.contact.account.map(|a| a.into()),
+            },
+            rules: val.rules.into_iter().map(|r| r.into()).collect(),
+        }
+    }
+}
+
+impl From<InstanceThumbnail> for MegalodonEntities::instance_v2::InstanceThumbnail {
+    fn from(val: InstanceThumbnail) -> Self {
+        MegalodonEntities::instance_v2::InstanceThumbnail {
+            url: val.url,
+            blurhash: val.blurhash,
+            versions: val
+                .versions
+                .map(|v| MegalodonEntities::instance_v2::ThumbnailVersions {
+                    at_1x: v.at_1x,
+                    at_2x: v.at_2x,
+                }),
+        }
+    }
+}
+
+impl From<InstanceV2Config> for MegalodonEntities::instance_v2::InstanceV2Config {
+    fn from(val: InstanceV2Config) -> Self {
+        MegalodonEntities::instance_v2::InstanceV2Config {
+            urls: MegalodonEntities::instance_v2::InstanceV2URLs {
+                streaming: val.urls.streaming,
+            },
+            statuses: val.statuses.into(),
+            media_attachments: val.media_attachments.map(|m| m.into()),
+            polls: val.polls.map(|p| p.into()),
+            translation: MegalodonEntities::instance_v2::Translation {
+                enabled: val.translation.enabled,
+            },
+        }
+    }
+}
+
+impl From<MediaAttachments> for MegalodonEntities::instance_v2::MediaAttachments {
+    fn from(val: MediaAttachments) -> Self {
+        MegalodonEntities::instance_v2::MediaAttachments {
+            supported_mime_types: val.supported_mime_types,
+            description_limit: val.description_limit,
+            image_size_limit: val.image_size_limit,
+            image_matrix_limit: val.image_matrix_limit,
+            video_size_limit: val.video_size_limit,
+            video_frame_rate_limit: val.video_frame_rate_limit,
+            video_matrix_limit: val.video_matrix_limit,
+        }
+    }
+}
+
+impl From<InstanceRegistrations> for MegalodonEntities::instance_v2::InstanceRegistrations {
+    fn from(val: InstanceRegistrations) -> Self {
+        MegalodonEntities::instance_v2::InstanceRegistrations {
+            enabled: val.enabled,
+            approval_required: val.approval_required,
+            message: val.message,
+            url: val.url,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_instance_v2() {
+        let text = r#"{
+            "domain": "mastodon.social",
+            "title": "Mastodon",
+            "version": "4.3.0",
+            "source_url": "https://github.com/mastodon/mastodon",
+            "description": "The original server operated by the Mastodon gGmbH non-profit",
+            "usage": { "users": { "active_month": 123122 } },
+            "thumbnail": {
+                "url": "https://files.mastodon.social/site_uploads/files/000/000/001/@1x/57c12f441d083cde.png",
+                "blurhash": "UeKUpFxuo~R%0nW;WCnhF6RjaJt757oJodS$",
+                "versions": {
+                    "@1x": "https://files.mastodon.social/site_uploads/files/000/000/001/@1x/57c12f441d083cde.png",
+                    "@2x": "https://files.mastodon.social/site_uploads/files/000/000/001/@2x/57c12f441d083cde.png"
+                }
+            },
+            "languages": ["en"],
+            "configuration": {
+                "urls": { "streaming": "wss://streaming.mastodon.social" },
+                "accounts": { "max_featured_tags": 10 },
+                "statuses": {
+                    "max_characters": 500,
+                    "max_media_attachments": 4,
+                    "characters_reserved_per_url": 23
+                },
+                "media_attachments": {
+                    "supported_mime_types": ["image/jpeg", "video/mp4"],
+                    "image_size_limit": 16777216,
+                    "image_matrix_limit": 33177600,
+                    "video_size_limit": 103809024,
+                    "video_frame_rate_limit": 120,
+                    "video_matrix_limit": 8294400
+                },
+                "polls": {
+                    "max_options": 4,
+                    "max_characters_per_option": 50,
+                    "min_expiration": 300,
+                    "max_expiration": 2629746
+                },
+                "translation": { "enabled": true }
+            },
+            "registrations": {
+                "enabled": false,
+                "approval_required": false,
+                "message": null
+            },
+            "api_versions": { "mastodon": 2 },
+            "contact": { "email": "staff@mastodon.social", "account": null },
+            "rules": [{ "id": "1", "text": "Sexually explicit or violent media must be marked as sensitive when posting" }]
+        }"#;
+
+        let instance: MegalodonEntities::InstanceV2 =
+            serde_json::from_str::<InstanceV2>(text).unwrap().into();
+        assert_eq!(instance.domain, "mastodon.social");
+        assert_eq!(
+            instance.configuration.urls.streaming.as_deref(),
+            Some("wss://streaming.mastodon.social")
+        );
+        assert!(instance.configuration.translation.enabled);
+        assert_eq!(instance.api_versions.get("mastodon"), Some(&2));
+        assert!(instance
+            .thumbnail
+            .unwrap()
+            .versions
+            .unwrap()
+            .at_2x
+            .is_some());
+    }
+
+    #[test]
+    fn test_parse_instance_v2_without_optional_fields() {
+        let text = r#"{
+            "domain": "akkoma.example",
+            "title": "Akkoma",
+            "version": "2.7.2 (compatible; Akkoma 3.13.2)",
+            "description": "",
+            "configuration": {
+                "urls": {},
+                "statuses": { "max_characters": 5000, "max_media_attachments": 1000 }
+            },
+            "registrations": { "enabled": true, "approval_required": false },
+            "contact": { "email": "admin@akkoma.example", "account": null }
+        }"#;
+
+        let instance: MegalodonEntities::InstanceV2 =
+            serde_json::from_str::<InstanceV2>(text).unwrap().into();
+        assert!(instance.source_url.is_none());
+        assert!(instance.thumbnail.is_none());
+        assert!(instance.configuration.urls.streaming.is_none());
+        assert!(instance.configuration.media_attachments.is_none());
+        assert!(instance.configuration.polls.is_none());
+        assert!(!instance.configuration.translation.enabled);
+        assert!(instance.rules.is_empty());
+    }
+}
@@ -1,6 +1,7 @@
 use super::{Account, Status};
 use crate::entities as MegalodonEntities;
 use chrono::{DateTime, Utc};
+use core::fmt;
 use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Deserialize, Clone)]
@@ -30,6 +31,69 @@ pub enum NotificationType {
     AdminReport,
     Quote,
     QuotedUpdate,
+    #[serde(other)]
+    Unknown,
+}
+
+impl fmt::Display for NotificationType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            NotificationType::Follow => write!(f, "follow"),
+            NotificationType::FollowRequest => write!(f, "follow_request"),
+            NotificationType::Mention => write!(f, "mention"),
+            NotificationType::Reblog => write!(f, "reblog"),
+            NotificationType::Favourite => write!(f, "favourite"),
+            NotificationType::Poll => write!(f, "poll"),
+            NotificationType::Status => write!(f, "status"),
+            NotificationType::Update => write!(f, "update"),
+            NotificationType::AdminSignup => write!(f, "admin.sign_up"),
+            NotificationType::AdminReport => write!(f, "admin.report"),
+            NotificationType::Quote => write!(f, "quote"),
+            NotificationType::QuotedUpdate => write!(f, "quoted_update"),
+            NotificationType::Unknown => write!(f, "unknown"),
+        }
+    }
+}
+
+impl From<MegalodonEntities::notification::NotificationType> for NotificationType {
+    fn from(value: MegalodonEntities::notification::NotificationType) -> Self {
+        match value {
+            MegalodonEntities::notification::NotificationType::Follow => NotificationType::Follow,
+            MegalodonEntities::notification::NotificationType::FollowRequest => {
+                NotificationType::FollowRequest
+            }
+            MegalodonEntities::notification::NotificationType::Mention => NotificationType::Mention,
+            MegalodonEntities::notification::NotificationType::Reblog => NotificationType::Reblog,
+            MegalodonEntities::notification::NotificationType::Favourite => {
+                NotificationType::Favourite
+            }
+            MegalodonEntities::notification::NotificationType::PollVote => NotificationType::Poll,
+            MegalodonEntities::notification::NotificationType::PollExpired => {
+                NotificationType::Poll
+            }
+            MegalodonEntities::notification::NotificationType::Status => NotificationType::Status,
+            MegalodonEntities::notification::NotificationType::Update => NotificationType::Update,
+            MegalodonEntities::notification::NotificationType::AdminSignup => {
+                NotificationType::AdminSignup
+            }
+            MegalodonEntities::notification::NotificationType::AdminReport => {
+                NotificationType::AdminReport
+            }
+            MegalodonEntities::notification::NotificationType::Quote => NotificationType::Quote,
+            MegalodonEntities::notification::NotificationType::QuotedUpdate => {
+                NotificationType::QuotedUpdate
+            }
+            MegalodonEntities::notification::NotificationType::Reaction => {
+                NotificationType::Unknown
+            }
+            MegalodonEntities::notification::NotificationType::Move => NotificationType::Unknown,
+            MegalodonEntities::notification::NotificationType::GroupInvited => {
+                NotificationType::Unknown
+            }
+            MegalodonEntities::notification::NotificationType::App => NotificationType::Unknown,
+            MegalodonEntities::notification::NotificationType::Unknown => NotificationType::Unknown,
+        }
+    }
 }
 
 impl From<NotificationType> for MegalodonEntities::notification::NotificationType {
@@ -59,6 +123,7 @@ impl From<NotificationType> for MegalodonEntities::notification::NotificationTyp
             NotificationType::QuotedUpdate => {
                 MegalodonEntities::notification::NotificationType::QuotedUpdate
             }
+            NotificationType::Unknown => MegalodonEntities::notification::NotificationType::Unknown,
         }
     }
 }
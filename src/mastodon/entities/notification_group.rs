@@ -0,0 +1,204 @@
+use super::{notification::NotificationType, Account, Report, Status};
+use crate::entities as MegalodonEntities;
+use chrono::{DateTime, Utc};
+use serde::Deserialize;
+
+#[derive(Debug, Deserialize, Clone)]
+pub struct GroupedNotificationsResults {
+    accounts: Vec<Account>,
+    partial_accounts: Option<Vec<PartialAccountWithAvatar>>,
+    statuses: Vec<Status>,
+    notification_groups: Vec<NotificationGroup>,
+}
+
+#[derive(Debug, Deserialize, Clone)]
+pub struct NotificationGroup {
+    group_key: String,
+    notifications_count: u32,
+    r#type: NotificationType,
+    most_recent_notification_id: String,
+    page_min_id: Option<String>,
+    page_max_id: Option<String>,
+    latest_page_notification_at: Option<DateTime<Utc>>,
+    sample_account_ids: Vec<String>,
+    status_id: Option<String>,
+    report: Option<Report>,
+}
+
+#[derive(Debug, Deserialize, Clone)]
+pub struct PartialAccountWithAvatar {
+    id: String,
+    acct: String,
+    url: String,
+    avatar: String,
+    avatar_static: String,
+    locked: bool,
+    bot: bool,
+}
+
+#[derive(Debug, Deserialize, Clone)]
+pub struct UnreadCount {
+    count: u32,
+}
+
+impl From<GroupedNotificationsResults> for MegalodonEntities::GroupedNotificationsResults {
+    fn from(val: GroupedNotificationsResults) -> Self {
+        MegalodonEntities::GroupedNotificationsResults {
+            accounts: val.accounts.into_iter().map(|i| i.into()).collect(),
+            partial_accounts: val
+                .partial_accounts
+                .map(|i| i.into_iter().map(|j| j.into()).collect()),
+            statuses: val.statuses.into_iter().map(|i| i.into()).collect(),
+            notification_groups: val
+                .notification_groups
+                .into_iter()
+                .map(|i| i.into())
+                .collect(),
+        }
+    }
+}
+
+impl From<NotificationGroup> for MegalodonEntities::NotificationGroup {
+    fn from(val: NotificationGroup) -> Self {
+        MegalodonEntities::NotificationGroup {
+            group_key: val.group_key,
+            notifications_count: val.notifications_count,
+            r#type: val.r#type.into(),
+            most_recent_notification_id: val.most_recent_notification_id,
+            page_min_id: val.page_min_id,
+            page_max_id: val.page_max_id,
+            latest_page_notification_at: val.latest_page_notification_at,
+            sample_account_ids: val.sample_account_ids,
+            status_id: val.status_id,
+            report: val.report.map(|i| i.into()),
+        }
+    }
+}
+
+impl From<PartialAccountWithAvatar> for MegalodonEntities::PartialAccountWithAvatar {
+    fn from(val: PartialAccountWithAvatar) -> Self {
+        MegalodonEntities::PartialAccountWithAvatar {
+            id: val.id,
+            acct: val.acct,
+            url: val.url,
+            avatar: val.avatar,
+            avatar_static: val.avatar_static,
+            locked: val.locked,
+            bot: val.bot,
+        }
+    }
+}
+
+impl From<UnreadCount> for MegalodonEntities::UnreadCount {
+    fn from(val: UnreadCount) -> Self {
+        MegalodonEntities::UnreadCount { count: val.count }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_notification_group() {
+        let text = r#"{
+            "group_key": "favourite-113010503322889311-479000",
+            "notifications_count": 2,
+            "type": "favourite",
+            "most_recent_notification_id": "23",
+            "page_min_id": "22",
+            "page_max_id": "23",
+            "latest_page_notification_at": "2024-08-23T08:50:32.000Z",
+            "sample_account_ids": ["1", "2"],
+            "status_id": "113010503322889311"
+        }"#;
+
+        let group: MegalodonEntities::NotificationGroup =
+            serde_json::from_str::<NotificationGroup>(text)
+                .unwrap()
+                .into();
+        assert_eq!(
+            group.r#type,
+            MegalodonEntities::notification::NotificationType::Favourite
+        );
+        assert_eq!(group.notifications_count, 2);
+        assert_eq!(group.sample_account_ids, vec!["1", "2"]);
+        assert!(group.report.is_none());
+    }
+
+    fn account(id: &str) -> serde_json::Value {
+        serde_json::json!({
+            "id": id,
+            "username": "h3poteto",
+            "acct": "h3poteto",
+            "display_name": "h3poteto",
+            "locked": false,
+            "created_at": "2023-01-01T00:00:00Z",
+            "followers_count": 0,
+            "following_count": 0,
+            "statuses_count": 0,
+            "note": "",
+            "url": "https://example.com/@h3poteto",
+            "avatar": "",
+            "avatar_static": "",
+            "header": "",
+            "header_static": "",
+            "emojis": [],
+            "fields": [],
+            "bot": false,
+        })
+    }
+
+    #[test]
+    fn test_grouped_notifications_results_helpers() {
+        let results: GroupedNotificationsResults = serde_json::from_value(serde_json::json!({
+            "accounts": [account("1"), account("2")],
+            "statuses": [{
+                "id": "113010503322889311",
+                "uri": "https://example.com/statuses/113010503322889311",
+                "account": account("1"),
+                "content": "",
+                "created_at": "2024-08-23T08:00:00Z",
+                "emojis": [],
+                "replies_count": 0,
+                "reblogs_count": 0,
+                "favourites_count": 2,
+                "sensitive": false,
+                "spoiler_text": "",
+                "visibility": "public",
+                "media_attachments": [],
+                "mentions": [],
+                "tags": [],
+            }],
+            "notification_groups": [{
+                "group_key": "favourite-113010503322889311-479000",
+                "notifications_count": 3,
+                "type": "favourite",
+                "most_recent_notification_id": "23",
+                "sample_account_ids": ["2", "3", "1"],
+                "status_id": "113010503322889311"
+            }]
+        }))
+        .unwrap();
+        let results: MegalodonEntities::GroupedNotificationsResults = results.into();
+
+        assert_eq!(results.account("2").unwrap().id, "2");
+        assert!(results.account("3").is_none());
+        assert_eq!(
+            results
+                .status("113010503322889311")
+                .unwrap()
+                .favourites_count,
+            2
+        );
+        assert!(results.status("1").is_none());
+        let group = &results.notification_groups[0];
+        let ids: Vec<&str> = results
+            .sample_accounts(group)
+            .iter()
+            .map(|a| a.id.as_str())
+            .collect();
+        // Accounts which are not included in the results are skipped.
+        assert_eq!(ids, vec!["2", "1"]);
+    }
+}
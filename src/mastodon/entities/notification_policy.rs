@@ -0,0 +1,90 @@
+use crate::entities as MegalodonEntities;
+use serde::Deserialize;
+
+#[derive(Debug, Deserialize, Clone)]
+pub struct NotificationPolicy {
+    for_not_following: NotificationFilteringPolicy,
+    for_not_followers: NotificationFilteringPolicy,
+    for_new_accounts: NotificationFilteringPolicy,
+    for_private_mentions: NotificationFilteringPolicy,
+    for_limited_accounts: NotificationFilteringPolicy,
+    summary: NotificationPolicySummary,
+}
+
+#[derive(Debug, Deserialize, Clone)]
+#[serde(rename_all = "lowercase")]
+pub enum NotificationFilteringPolicy {
+    Accept,
+    Filter,
+    Drop,
+}
+
+#[derive(Debug, Deserialize, Clone)]
+pub struct NotificationPolicySummary {
+    pending_requests_count: u32,
+    pending_notifications_count: u32,
+}
+
+impl From<NotificationFilteringPolicy>
+    for MegalodonEntities::notification_policy::NotificationFilteringPolicy
+{
+    fn from(val: NotificationFilteringPolicy) -> Self {
+        match val {
+            NotificationFilteringPolicy::Accept => {
+                MegalodonEntities::notification_policy::NotificationFilteringPolicy::Accept
+            }
+            NotificationFilteringPolicy::Filter => {
+                MegalodonEntities::notification_policy::NotificationFilteringPolicy::Filter
+            }
+            NotificationFilteringPolicy::Drop => {
+                MegalodonEntities::notification_policy::NotificationFilteringPolicy::Drop
+            }
+        }
+    }
+}
+
+impl From<NotificationPolicy> for MegalodonEntities::NotificationPolicy {
+    fn from(val: NotificationPolicy) -> Self {
+        MegalodonEntities::NotificationPolicy {
+            for_not_following: val.for_not_following.into(),
+            for_not_followers: val.for_not_followers.into(),
+            for_new_accounts: val.for_new_accounts.into(),
+            for_private_mentions: val.for_private_mentions.into(),
+            for_limited_accounts: val.for_limited_accounts.into(),
+            summary: MegalodonEntities::notification_policy::NotificationPolicySummary {
+                pending_requests_count: val.summary.pending_requests_count,
+                pending_notifications_count: val.summary.pending_notifications_count,
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_notification_policy() {
+        let text = r#"{
+            "for_not_following": "accept",
+            "for_not_followers": "filter",
+            "for_new_accounts": "drop",
+            "for_private_mentions": "filter",
+            "for_limited_accounts": "filter",
+            "summary": {
+                "pending_requests_count": 5,
+                "pending_notifications_count": 13
+            }
+        }"#;
+
+        let policy: MegalodonEntities::NotificationPolicy =
+            serde_json::from_str::<NotificationPolicy>(text)
+                .unwrap()
+                .into();
+        assert_eq!(
+            policy.for_new_accounts,
+            MegalodonEntities::notification_policy::NotificationFilteringPolicy::Drop
+        );
+        assert_eq!(policy.summary.pending_requests_count, 5);
+    }
+}
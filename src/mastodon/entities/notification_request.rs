@@ -0,0 +1,33 @@
+use super::{Account, Status};
+use crate::entities as MegalodonEntities;
+use chrono::{DateTime, Utc};
+use serde::Deserialize;
+
+#[derive(Debug, Deserialize, Clone)]
+pub struct NotificationRequest {
+    id: String,
+    created_at: DateTime<Utc>,
+    updated_at: DateTime<Utc>,
+    account: Account,
+    #[serde(deserialize_with = "MegalodonEntities::history::parse_from_string")]
+    notifications_count: u32,
+    last_status: Option<Status>,
+}
+
+#[derive(Debug, Deserialize, Clone)]
+pub struct NotificationRequestsMerged {
+    pub merged: bool,
+}
+
+impl From<NotificationRequest> for MegalodonEntities::NotificationRequest {
+    fn from(val: NotificationRequest) -> Self {
+        MegalodonEntities::NotificationRequest {
+            id: val.id,
+            created_at: val.created_at,
+            updated_at: val.updated_at,
+            account: val.account.into(),
+            notifications_count: val.notifications_count,
+            last_status: val.last_status.map(|i| i.into()),
+        }
+    }
+}
@@ -21,6 +21,7 @@ pub struct Report {
 pub enum Category {
     Spam,
     Violation,
+    Legal,
     Other,
 }
 
@@ -29,6 +30,7 @@ impl From<Category> for MegalodonEntities::report::Category {
         match val {
             Category::Spam => MegalodonEntities::report::Category::Spam,
             Category::Violation => MegalodonEntities::report::Category::Violation,
+            Category::Legal => MegalodonEntities::report::Category::Legal,
             Category::Other => MegalodonEntities::report::Category::Other,
         }
     }
@@ -2,7 +2,8 @@ use core::fmt;
 use std::str::FromStr;
 
 use super::{
-    Account, Application, Attachment, Card, Emoji, Mention, Poll, QuoteApproval, QuotedStatus,
+    Account, Application, Attachment, Card, Emoji, FilterResult, Mention, Poll, QuoteApproval,
+    QuotedStatus,
 };
 use crate::error::{Error, Kind};
 use crate::{entities as MegalodonEntities, megalodon};
@@ -42,6 +43,7 @@ pub struct Status {
     quote: Option<QuotedStatus>,
     quote_approval: Option<QuoteApproval>,
     bookmarked: Option<bool>,
+    filtered: Option<Vec<FilterResult>>,
 }
 
 #[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
@@ -171,6 +173,10 @@ impl From<Status> for MegalodonEntities::Status {
                 .map(|i| i.into())
                 .unwrap_or_default(),
             bookmarked: val.bookmarked,
+            filtered: val
+                .filtered
+                .map(|i| i.into_iter().map(|j| j.into()).collect())
+                .unwrap_or_default(),
         }
     }
 }
@@ -180,3 +186,70 @@ impl From<Status> for megalodon::PostStatusOutput {
         megalodon::PostStatusOutput::Status(val.into())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_status_filtered() {
+        let status: Status = serde_json::from_value(json!({
+            "id": "1",
+            "uri": "https://example.com/statuses/1",
+            "account": {
+                "id": "1",
+                "username": "h3poteto",
+                "acct": "h3poteto",
+                "display_name": "h3poteto",
+                "locked": false,
+                "created_at": "2023-01-01T00:00:00Z",
+                "followers_count": 0,
+                "following_count": 0,
+                "statuses_count": 0,
+                "note": "",
+                "url": "https://example.com/@h3poteto",
+                "avatar": "",
+                "avatar_static": "",
+                "header": "",
+                "header_static": "",
+                "emojis": [],
+                "fields": [],
+                "bot": false,
+            },
+            "content": "",
+            "created_at": "2023-01-01T00:00:00Z",
+            "emojis": [],
+            "replies_count": 0,
+            "reblogs_count": 0,
+            "favourites_count": 0,
+            "sensitive": false,
+            "spoiler_text": "",
+            "visibility": "public",
+            "media_attachments": [],
+            "mentions": [],
+            "tags": [],
+            "filtered": [{
+                "filter": {
+                    "id": "3",
+                    "title": "Spoilers",
+                    "context": ["home"],
+                    "expires_at": null,
+                    "filter_action": "hide"
+                },
+                "keyword_matches": null,
+                "status_matches": ["1"]
+            }],
+        }))
+        .unwrap();
+        let status: MegalodonEntities::Status = status.into();
+        let filtered = status.filtered;
+        assert_eq!(filtered.len(), 1);
+        assert_eq!(filtered[0].filter.id, "3");
+        assert_eq!(
+            filtered[0].filter.filter_action,
+            MegalodonEntities::FilterAction::Hide
+        );
+        assert_eq!(filtered[0].status_matches, Some(vec![String::from("1")]));
+    }
+}
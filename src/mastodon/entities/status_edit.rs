@@ -0,0 +1,57 @@
+use super::{Account, Attachment, Emoji};
+use crate::entities as MegalodonEntities;
+use chrono::{DateTime, Utc};
+use serde::Deserialize;
+
+#[derive(Debug, Deserialize, Clone)]
+pub struct StatusEdit {
+    content: String,
+    spoiler_text: String,
+    sensitive: bool,
+    created_at: DateTime<Utc>,
+    account: Account,
+    poll: Option<StatusEditPoll>,
+    media_attachments: Vec<Attachment>,
+    emojis: Vec<Emoji>,
+}
+
+#[derive(Debug, Deserialize, Clone)]
+pub struct StatusEditPoll {
+    options: Vec<StatusEditPollOption>,
+}
+
+#[derive(Debug, Deserialize, Clone)]
+pub struct StatusEditPollOption {
+    title: String,
+}
+
+impl From<StatusEdit> for MegalodonEntities::StatusEdit {
+    fn from(val: StatusEdit) -> Self {
+        MegalodonEntities::StatusEdit {
+            content: val.content,
+            spoiler_text: val.spoiler_text,
+            sensitive: val.sensitive,
+            created_at: val.created_at,
+            account: val.account.into(),
+            poll: val.poll.map(|p| p.into()),
+            media_attachments: val
+                .media_attachments
+                .into_iter()
+                .map(|a| a.into())
+                .collect(),
+            emojis: val.emojis.into_iter().map(|e| e.into()).collect(),
+        }
+    }
+}
+
+impl From<StatusEditPoll> for MegalodonEntities::status_edit::StatusEditPoll {
+    fn from(val: StatusEditPoll) -> Self {
+        MegalodonEntities::status_edit::StatusEditPoll {
+            options: val
+                .options
+                .into_iter()
+                .map(|o| MegalodonEntities::status_edit::StatusEditPollOption { title: o.title })
+                .collect(),
+        }
+    }
+}
@@ -0,0 +1,117 @@
+use crate::entities as MegalodonEntities;
+use serde::Deserialize;
+
+#[derive(Debug, Deserialize, Clone)]
+pub struct Translation {
+    content: String,
+    // Mastodon 4.0 and 4.1 translate only the content.
+    spoiler_text: Option<String>,
+    poll: Option<TranslationPoll>,
+    media_attachments: Option<Vec<TranslationAttachment>>,
+    detected_source_language: String,
+    provider: String,
+}
+
+#[derive(Debug, Deserialize, Clone)]
+pub struct TranslationPoll {
+    id: String,
+    options: Vec<TranslationPollOption>,
+}
+
+#[derive(Debug, Deserialize, Clone)]
+pub struct TranslationPollOption {
+    title: String,
+}
+
+#[derive(Debug, Deserialize, Clone)]
+pub struct TranslationAttachment {
+    id: String,
+    description: String,
+}
+
+impl From<Translation> for MegalodonEntities::Translation {
+    fn from(val: Translation) -> Self {
+        MegalodonEntities::Translation {
+            content: val.content,
+            spoiler_text: val.spoiler_text.unwrap_or_default(),
+            poll: val.poll.map(|p| p.into()),
+            media_attachments: val
+                .media_attachments
+                .unwrap_or_default()
+                .into_iter()
+                .map(|a| a.into())
+                .collect(),
+            detected_source_language: val.detected_source_language,
+            provider: val.provider,
+        }
+    }
+}
+
+impl From<TranslationPoll> for MegalodonEntities::translation::TranslationPoll {
+    fn from(val: TranslationPoll) -> Self {
+        MegalodonEntities::translation::TranslationPoll {
+            id: val.id,
+            options: val
+                .options
+                .into_iter()
+                .map(|o| MegalodonEntities::translation::TranslationPollOption { title: o.title })
+                .collect(),
+        }
+    }
+}
+
+impl From<TranslationAttachment> for MegalodonEntities::translation::TranslationAttachment {
+    fn from(val: TranslationAttachment) -> Self {
+        MegalodonEntities::translation::TranslationAttachment {
+            id: val.id,
+            description: val.description,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_translation() {
+        let json = r#"{
+  "content": "<p>Hello world</p>",
+  "spoiler_text": "Greeting",
+  "poll": {
+    "id": "34858",
+    "options": [
+      {
+        "title": "Yes"
+      },
+      {
+        "title": "No"
+      }
+    ]
+  },
+  "media_attachments": [
+    {
+      "id": "105",
+      "description": "A cat"
+    }
+  ],
+  "detected_source_language": "de",
+  "provider": "DeepL.com"
+}"#;
+        let translation: Translation = serde_json::from_str(json).unwrap();
+        let translation: MegalodonEntities::Translation = translation.into();
+        assert_eq!(translation.spoiler_text, "Greeting");
+        assert_eq!(translation.poll.unwrap().options[1].title, "No");
+        assert_eq!(translation.media_attachments[0].description, "A cat");
+
+        let json = r#"{
+  "content": "<p>Hello world</p>",
+  "detected_source_language": "de",
+  "provider": "DeepL.com"
+}"#;
+        let translation: Translation = serde_json::from_str(json).unwrap();
+        let translation: MegalodonEntities::Translation = translation.into();
+        assert_eq!(translation.spoiler_text, "");
+        assert!(translation.media_attachments.is_empty());
+    }
+}
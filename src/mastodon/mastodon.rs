@@ -2,8 +2,12 @@ use super::api_client::APIClient;
 use super::entities;
 use super::oauth;
 use super::web_socket::WebSocket;
+use crate::SNS;
+use crate::capabilities::Capabilities;
+use crate::http::{ClientBuilder, idempotency_header};
 use crate::megalodon::FollowRequestOutput;
-use crate::{Streaming, error};
+use crate::web_socket::MultiplexWebSocket;
+use crate::{Streaming, error, streaming::MultiplexStreaming};
 use crate::{
     default, entities as MegalodonEntities, error::Error, megalodon, oauth as MegalodonOAuth,
     response::Response,
@@ -38,7 +42,22 @@ impl Mastodon {
         access_token: Option<String>,
         user_agent: Option<String>,
     ) -> Result<Mastodon, Error> {
-        let client = APIClient::new(base_url.clone(), access_token.clone(), user_agent.clone())?;
+        Self::new_with_client(base_url, access_token, user_agent, ClientBuilder::default())
+    }
+
+    /// Create a new [`Mastodon`] with the HTTP client settings.
+    pub fn new_with_client(
+        base_url: String,
+        access_token: Option<String>,
+        user_agent: Option<String>,
+        client: ClientBuilder,
+    ) -> Result<Mastodon, Error> {
+        let client = APIClient::new(
+            base_url.clone(),
+            access_token.clone(),
+            user_agent.clone(),
+            &client,
+        )?;
         Ok(Mastodon {
             client,
             base_url,
@@ -1157,6 +1176,331 @@ impl megalodon::Megalodon for Mastodon {
         Ok(res)
     }
 
+    async fn get_filters_v2(&self) -> Result<Response<Vec<MegalodonEntities::FilterV2>>, Error> {
+        let res = self
+            .client
+            .get::<Vec<entities::FilterV2>>("/api/v2/filters", None)
+            .await?;
+
+        Ok(Response::<Vec<MegalodonEntities::FilterV2>>::new(
+            res.json.into_iter().map(|j| j.into()).collect(),
+            res.status,
+            res.status_text,
+            res.header,
+        ))
+    }
+
+    async fn get_filter_v2(
+        &self,
+        id: String,
+    ) -> Result<Response<MegalodonEntities::FilterV2>, Error> {
+        let res = self
+            .client
+            .get::<entities::FilterV2>(format!("/api/v2/filters/{}", id).as_str(), None)
+            .await?;
+
+        Ok(Response::<MegalodonEntities::FilterV2>::new(
+            res.json.into(),
+            res.status,
+            res.status_text,
+            res.header,
+        ))
+    }
+
+    async fn create_filter_v2(
+        &self,
+        title: String,
+        context: Vec<MegalodonEntities::filter::FilterContext>,
+        options: Option<&megalodon::CreateFilterV2InputOptions>,
+    ) -> Result<Response<MegalodonEntities::FilterV2>, Error> {
+        let mut params = HashMap::<&str, Value>::from([
+            ("title", serde_json::Value::String(title)),
+            (
+                "context",
+                serde_json::to_value(&context).ok().unwrap_or_default(),
+            ),
+        ]);
+        if let Some(options) = options {
+            if let Some(filter_action) = &options.filter_action {
+                params.insert(
+                    "filter_action",
+                    serde_json::Value::String(filter_action.to_string()),
+                );
+            }
+            if let Some(expires_in) = options.expires_in {
+                params.insert(
+                    "expires_in",
+                    serde_json::Value::String(expires_in.to_string()),
+                );
+            }
+            if let Some(keywords_attributes) = &options.keywords_attributes {
+                params.insert(
+                    "keywords_attributes",
+                    serde_json::to_value(keywords_attributes)
+                        .ok()
+                        .unwrap_or_default(),
+                );
+            }
+        }
+        let res = self
+            .client
+            .post::<entities::FilterV2>("/api/v2/filters", &params, None)
+            .await?;
+
+        Ok(Response::<MegalodonEntities::FilterV2>::new(
+            res.json.into(),
+            res.status,
+            res.status_text,
+            res.header,
+        ))
+    }
+
+    async fn update_filter_v2(
+        &self,
+        id: String,
+        options: &megalodon::UpdateFilterV2InputOptions,
+    ) -> Result<Response<MegalodonEntities::FilterV2>, Error> {
+        let mut params = HashMap::<&str, Value>::new();
+        if let Some(title) = &options.title {
+            params.insert("title", serde_json::Value::String(title.clone()));
+        }
+        if let Some(context) = &options.context {
+            params.insert(
+                "context",
+                serde_json::to_value(context).ok().unwrap_or_default(),
+            );
+        }
+        if let Some(filter_action) = &options.filter_action {
+            params.insert(
+                "filter_action",
+                serde_json::Value::String(filter_action.to_string()),
+            );
+        }
+        if let Some(expires_in) = options.expires_in {
+            params.insert(
+                "expires_in",
+                serde_json::Value::String(expires_in.to_string()),
+            );
+        }
+        if let Some(keywords_attributes) = &options.keywords_attributes {
+            params.insert(
+                "keywords_attributes",
+                serde_json::to_value(keywords_attributes)
+                    .ok()
+                    .unwrap_or_default(),
+            );
+        }
+        let res = self
+            .client
+            .put::<entities::FilterV2>(format!("/api/v2/filters/{}", id).as_str(), &params, None)
+            .await?;
+
+        Ok(Response::<MegalodonEntities::FilterV2>::new(
+            res.json.into(),
+            res.status,
+            res.status_text,
+            res.header,
+        ))
+    }
+
+    async fn delete_filter_v2(&self, id: String) -> Result<Response<()>, Error> {
+        let params = HashMap::<&str, Value>::new();
+        let res = self
+            .client
+            .delete::<()>(format!("/api/v2/filters/{}", id).as_str(), &params, None)
+            .await?;
+
+        Ok(res)
+    }
+
+    async fn get_filter_keywords(
+        &self,
+        filter_id: String,
+    ) -> Result<Response<Vec<MegalodonEntities::FilterKeyword>>, Error> {
+        let res = self
+            .client
+            .get::<Vec<entities::FilterKeyword>>(
+                format!("/api/v2/filters/{}/keywords", filter_id).as_str(),
+                None,
+            )
+            .await?;
+
+        Ok(Response::<Vec<MegalodonEntities::FilterKeyword>>::new(
+            res.json.into_iter().map(|j| j.into()).collect(),
+            res.status,
+            res.status_text,
+            res.header,
+        ))
+    }
+
+    async fn add_filter_keyword(
+        &self,
+        filter_id: String,
+        keyword: String,
+        whole_word: Option<bool>,
+    ) -> Result<Response<MegalodonEntities::FilterKeyword>, Error> {
+        let mut params =
+            HashMap::<&str, Value>::from([("keyword", serde_json::Value::String(keyword))]);
+        if let Some(whole_word) = whole_word {
+            params.insert("whole_word", serde_json::Value::Bool(whole_word));
+        }
+        let res = self
+            .client
+            .post::<entities::FilterKeyword>(
+                format!("/api/v2/filters/{}/keywords", filter_id).as_str(),
+                &params,
+                None,
+            )
+            .await?;
+
+        Ok(Response::<MegalodonEntities::FilterKeyword>::new(
+            res.json.into(),
+            res.status,
+            res.status_text,
+            res.header,
+        ))
+    }
+
+    async fn get_filter_keyword(
+        &self,
+        id: String,
+    ) -> Result<Response<MegalodonEntities::FilterKeyword>, Error> {
+        let res = self
+            .client
+            .get::<entities::FilterKeyword>(
+                format!("/api/v2/filters/keywords/{}", id).as_str(),
+                None,
+            )
+            .await?;
+
+        Ok(Response::<MegalodonEntities::FilterKeyword>::new(
+            res.json.into(),
+            res.status,
+            res.status_text,
+            res.header,
+        ))
+    }
+
+    async fn update_filter_keyword(
+        &self,
+        id: String,
+        keyword: String,
+        whole_word: Option<bool>,
+    ) -> Result<Response<MegalodonEntities::FilterKeyword>, Error> {
+        let mut params =
+            HashMap::<&str, Value>::from([("keyword", serde_json::Value::String(keyword))]);
+        if let Some(whole_word) = whole_word {
+            params.insert("whole_word", serde_json::Value::Bool(whole_word));
+        }
+        let res = self
+            .client
+            .put::<entities::FilterKeyword>(
+                format!("/api/v2/filters/keywords/{}", id).as_str(),
+                &params,
+                None,
+            )
+            .await?;
+
+        Ok(Response::<MegalodonEntities::FilterKeyword>::new(
+            res.json.into(),
+            res.status,
+            res.status_text,
+            res.header,
+        ))
+    }
+
+    async fn delete_filter_keyword(&self, id: String) -> Result<Response<()>, Error> {
+        let params = HashMap::<&str, Value>::new();
+        let res = self
+            .client
+            .delete::<()>(
+                format!("/api/v2/filters/keywords/{}", id).as_str(),
+                &params,
+                None,
+            )
+            .await?;
+
+        Ok(res)
+    }
+
+    async fn get_filter_statuses(
+        &self,
+        filter_id: String,
+    ) -> Result<Response<Vec<MegalodonEntities::FilterStatus>>, Error> {
+        let res = self
+            .client
+            .get::<Vec<entities::FilterStatus>>(
+                format!("/api/v2/filters/{}/statuses", filter_id).as_str(),
+                None,
+            )
+            .await?;
+
+        Ok(Response::<Vec<MegalodonEntities::FilterStatus>>::new(
+            res.json.into_iter().map(|j| j.into()).collect(),
+            res.status,
+            res.status_text,
+            res.header,
+        ))
+    }
+
+    async fn add_filter_status(
+        &self,
+        filter_id: String,
+        status_id: String,
+    ) -> Result<Response<MegalodonEntities::FilterStatus>, Error> {
+        let params =
+            HashMap::<&str, Value>::from([("status_id", serde_json::Value::String(status_id))]);
+        let res = self
+            .client
+            .post::<entities::FilterStatus>(
+                format!("/api/v2/filters/{}/statuses", filter_id).as_str(),
+                &params,
+                None,
+            )
+            .await?;
+
+        Ok(Response::<MegalodonEntities::FilterStatus>::new(
+            res.json.into(),
+            res.status,
+            res.status_text,
+            res.header,
+        ))
+    }
+
+    async fn get_filter_status(
+        &self,
+        id: String,
+    ) -> Result<Response<MegalodonEntities::FilterStatus>, Error> {
+        let res = self
+            .client
+            .get::<entities::FilterStatus>(
+                format!("/api/v2/filters/statuses/{}", id).as_str(),
+                None,
+            )
+            .await?;
+
+        Ok(Response::<MegalodonEntities::FilterStatus>::new(
+            res.json.into(),
+            res.status,
+            res.status_text,
+            res.header,
+        ))
+    }
+
+    async fn delete_filter_status(&self, id: String) -> Result<Response<()>, Error> {
+        let params = HashMap::<&str, Value>::new();
+        let res = self
+            .client
+            .delete::<()>(
+                format!("/api/v2/filters/statuses/{}", id).as_str(),
+                &params,
+                None,
+            )
+            .await?;
+
+        Ok(res)
+    }
+
     async fn report(
         &self,
         account_id: String,
@@ -1529,15 +1873,23 @@ impl megalodon::Megalodon for Mastodon {
                     serde_json::Value::String(quote_id.clone()),
                 );
             }
+            if let Some(quote_approval_policy) = &options.quote_approval_policy {
+                params.insert(
+                    "quote_approval_policy",
+                    serde_json::Value::String(quote_approval_policy.to_string()),
+                );
+            }
             if let Some(poll) = &options.poll {
                 params.insert("poll", serde_json::to_value(&poll).unwrap());
             }
         }
 
+        let headers = idempotency_header(options.and_then(|o| o.idempotency_key.as_deref()))?;
+
         if is_scheduled {
             let res = self
                 .client
-                .post::<entities::ScheduledStatus>("/api/v1/statuses", &params, None)
+                .post::<entities::ScheduledStatus>("/api/v1/statuses", &params, Some(headers))
                 .await?;
 
             Ok(Response::<megalodon::PostStatusOutput>::new(
@@ -1549,7 +1901,7 @@ impl megalodon::Megalodon for Mastodon {
         } else {
             let res = self
                 .client
-                .post::<entities::Status>("/api/v1/statuses", &params, None)
+                .post::<entities::Status>("/api/v1/statuses", &params, Some(headers))
                 .await?;
 
             Ok(Response::<megalodon::PostStatusOutput>::new(
@@ -1592,6 +1944,26 @@ impl megalodon::Megalodon for Mastodon {
         ))
     }
 
+    async fn get_status_history(
+        &self,
+        id: String,
+    ) -> Result<Response<Vec<MegalodonEntities::StatusEdit>>, Error> {
+        let res = self
+            .client
+            .get::<Vec<entities::StatusEdit>>(
+                format!("/api/v1/statuses/{}/history", id).as_str(),
+                None,
+            )
+            .await?;
+
+        Ok(Response::<Vec<MegalodonEntities::StatusEdit>>::new(
+            res.json.into_iter().map(|j| j.into()).collect(),
+            res.status,
+            res.status_text,
+            res.header,
+        ))
+    }
+
     async fn edit_status(
         &self,
         id: String,
@@ -1624,6 +1996,12 @@ impl megalodon::Megalodon for Mastodon {
         if let Some(poll) = &options.poll {
             params.insert("poll", serde_json::to_value(&poll).unwrap());
         }
+        if let Some(quote_approval_policy) = &options.quote_approval_policy {
+            params.insert(
+                "quote_approval_policy",
+                serde_json::Value::String(quote_approval_policy.to_string()),
+            );
+        }
 
         let res = self
             .client
@@ -1645,6 +2023,7 @@ impl megalodon::Megalodon for Mastodon {
             status,
             status_text,
             header,
+            ..
         } = self
             .client
             .delete::<Value>(format!("/api/v1/statuses/{}", id).as_str(), &params, None)
@@ -1727,10 +2106,71 @@ impl megalodon::Megalodon for Mastodon {
         ))
     }
 
-    async fn favourite_status(
+    async fn get_status_quotes(
         &self,
         id: String,
-    ) -> Result<Response<MegalodonEntities::Status>, Error> {
+        options: Option<&megalodon::GetStatusQuotesInputOptions>,
+    ) -> Result<Response<Vec<MegalodonEntities::Status>>, Error> {
+        let mut params = Vec::<String>::new();
+        if let Some(options) = options {
+            if let Some(limit) = options.limit {
+                params.push(format!("limit={}", limit));
+            }
+            if let Some(max_id) = &options.max_id {
+                params.push(format!("max_id={}", max_id));
+            }
+            if let Some(since_id) = &options.since_id {
+                params.push(format!("since_id={}", since_id));
+            }
+        }
+        let mut path = format!("/api/v1/statuses/{}/quotes", id);
+        if !params.is_empty() {
+            path = path + "?" + params.join("&").as_str();
+        }
+        let res = self
+            .client
+            .get::<Vec<entities::Status>>(path.as_str(), None)
+            .await?;
+
+        Ok(Response::<Vec<MegalodonEntities::Status>>::new(
+            res.json.into_iter().map(|j| j.into()).collect(),
+            res.status,
+            res.status_text,
+            res.header,
+        ))
+    }
+
+    async fn revoke_quote(
+        &self,
+        id: String,
+        quoting_status_id: String,
+    ) -> Result<Response<MegalodonEntities::Status>, Error> {
+        let params = HashMap::new();
+        let res = self
+            .client
+            .post::<entities::Status>(
+                format!(
+                    "/api/v1/statuses/{}/quotes/{}/revoke",
+                    id, quoting_status_id
+                )
+                .as_str(),
+                &params,
+                None,
+            )
+            .await?;
+
+        Ok(Response::<MegalodonEntities::Status>::new(
+            res.json.into(),
+            res.status,
+            res.status_text,
+            res.header,
+        ))
+    }
+
+    async fn favourite_status(
+        &self,
+        id: String,
+    ) -> Result<Response<MegalodonEntities::Status>, Error> {
         let params = HashMap::new();
         let res = self
             .client
@@ -1938,6 +2378,32 @@ impl megalodon::Megalodon for Mastodon {
         ))
     }
 
+    async fn translate_status(
+        &self,
+        id: String,
+        lang: Option<String>,
+    ) -> Result<Response<MegalodonEntities::Translation>, Error> {
+        let mut params = HashMap::<&str, Value>::new();
+        if let Some(lang) = lang {
+            params.insert("lang", Value::String(lang));
+        }
+        let res = self
+            .client
+            .post::<entities::Translation>(
+                format!("/api/v1/statuses/{}/translate", id).as_str(),
+                &params,
+                None,
+            )
+            .await?;
+
+        Ok(Response::<MegalodonEntities::Translation>::new(
+            res.json.into(),
+            res.status,
+            res.status_text,
+            res.header,
+        ))
+    }
+
     async fn upload_media_reader(
         &self,
         reader: Box<dyn AsyncRead + Sync + Send + Unpin>,
@@ -2783,127 +3249,154 @@ impl megalodon::Megalodon for Mastodon {
         ))
     }
 
-    async fn subscribe_push_notification(
+    async fn get_grouped_notifications(
         &self,
-        subscription: &megalodon::SubscribePushNotificationInputSubscription,
-        data: Option<&megalodon::SubscribePushNotificationInputData>,
-    ) -> Result<Response<MegalodonEntities::PushSubscription>, Error> {
-        let mut params = HashMap::<&str, Value>::from([(
-            "subscription",
-            serde_json::to_value(&subscription).ok().unwrap(),
-        )]);
-        if let Some(data) = data {
-            if let Some(json_data) = serde_json::to_value(&data).ok() {
-                params.insert("data", json_data);
+        options: Option<&megalodon::GetGroupedNotificationsInputOptions>,
+    ) -> Result<Response<MegalodonEntities::GroupedNotificationsResults>, Error> {
+        let mut params = Vec::<String>::new();
+        if let Some(options) = options {
+            if let Some(limit) = options.limit {
+                params.push(format!("limit={}", limit));
+            }
+            if let Some(max_id) = &options.max_id {
+                params.push(format!("max_id={}", max_id));
+            }
+            if let Some(since_id) = &options.since_id {
+                params.push(format!("since_id={}", since_id));
+            }
+            if let Some(min_id) = &options.min_id {
+                params.push(format!("min_id={}", min_id));
+            }
+            if let Some(types) = &options.types {
+                params.extend(notification_type_params("types", types));
+            }
+            if let Some(exclude_types) = &options.exclude_types {
+                params.extend(notification_type_params("exclude_types", exclude_types));
+            }
+            if let Some(account_id) = &options.account_id {
+                params.push(format!("account_id={}", account_id));
+            }
+            if let Some(expand_accounts) = &options.expand_accounts {
+                params.push(format!("expand_accounts={}", expand_accounts));
+            }
+            if let Some(grouped_types) = &options.grouped_types {
+                params.extend(notification_type_params("grouped_types", grouped_types));
+            }
+            if let Some(include_filtered) = options.include_filtered {
+                params.push(format!("include_filtered={}", include_filtered));
             }
         }
+        let mut path = "/api/v2/notifications".to_string();
+        if !params.is_empty() {
+            path = path + "?" + params.join("&").as_str();
+        }
         let res = self
             .client
-            .post::<entities::PushSubscription>("/api/v1/push/subscription", &params, None)
+            .get::<entities::GroupedNotificationsResults>(path.as_str(), None)
             .await?;
 
-        Ok(Response::<MegalodonEntities::PushSubscription>::new(
-            res.json.into(),
-            res.status,
-            res.status_text,
-            res.header,
-        ))
+        Ok(
+            Response::<MegalodonEntities::GroupedNotificationsResults>::new(
+                res.json.into(),
+                res.status,
+                res.status_text,
+                res.header,
+            ),
+        )
     }
 
-    async fn get_push_subscription(
+    async fn get_notification_group(
         &self,
-    ) -> Result<Response<MegalodonEntities::PushSubscription>, Error> {
+        group_key: String,
+    ) -> Result<Response<MegalodonEntities::GroupedNotificationsResults>, Error> {
         let res = self
             .client
-            .get::<entities::PushSubscription>("/api/v1/push/subscription", None)
+            .get::<entities::GroupedNotificationsResults>(
+                format!("/api/v2/notifications/{}", group_key).as_str(),
+                None,
+            )
             .await?;
 
-        Ok(Response::<MegalodonEntities::PushSubscription>::new(
-            res.json.into(),
-            res.status,
-            res.status_text,
-            res.header,
-        ))
+        Ok(
+            Response::<MegalodonEntities::GroupedNotificationsResults>::new(
+                res.json.into(),
+                res.status,
+                res.status_text,
+                res.header,
+            ),
+        )
     }
 
-    async fn update_push_subscription(
-        &self,
-        data: Option<&megalodon::SubscribePushNotificationInputData>,
-    ) -> Result<Response<MegalodonEntities::PushSubscription>, Error> {
-        let mut params = HashMap::<&str, Value>::new();
-        if let Some(data) = data {
-            if let Some(json_data) = serde_json::to_value(&data).ok() {
-                params.insert("data", json_data);
-            }
-        }
+    async fn dismiss_notification_group(&self, group_key: String) -> Result<Response<()>, Error> {
+        let params = HashMap::new();
         let res = self
             .client
-            .put::<entities::PushSubscription>("/api/v1/push/subscription", &params, None)
+            .post::<Value>(
+                format!("/api/v2/notifications/{}/dismiss", group_key).as_str(),
+                &params,
+                None,
+            )
             .await?;
-
-        Ok(Response::<MegalodonEntities::PushSubscription>::new(
-            res.json.into(),
+        Ok(Response::<()>::new(
+            (),
             res.status,
             res.status_text,
             res.header,
         ))
     }
 
-    async fn delete_push_subscription(&self) -> Result<Response<()>, Error> {
-        let params = HashMap::new();
+    async fn get_notification_group_accounts(
+        &self,
+        group_key: String,
+    ) -> Result<Response<Vec<MegalodonEntities::Account>>, Error> {
         let res = self
             .client
-            .delete::<()>("/api/v1/push/subscription", &params, None)
+            .get::<Vec<entities::Account>>(
+                format!("/api/v2/notifications/{}/accounts", group_key).as_str(),
+                None,
+            )
             .await?;
 
-        Ok(res)
+        Ok(Response::<Vec<MegalodonEntities::Account>>::new(
+            res.json.into_iter().map(|j| j.into()).collect(),
+            res.status,
+            res.status_text,
+            res.header,
+        ))
     }
 
-    async fn search(
+    async fn get_unread_notification_count(
         &self,
-        q: String,
-        options: Option<&megalodon::SearchInputOptions>,
-    ) -> Result<Response<MegalodonEntities::Results>, Error> {
-        let mut params = Vec::<String>::from([format!("q={}", q)]);
+        options: Option<&megalodon::GetUnreadNotificationCountInputOptions>,
+    ) -> Result<Response<MegalodonEntities::UnreadCount>, Error> {
+        let mut params = Vec::<String>::new();
         if let Some(options) = options {
-            if let Some(t) = &options.r#type {
-                params.push(format!("type={}", t));
-            }
             if let Some(limit) = options.limit {
                 params.push(format!("limit={}", limit));
             }
-            if let Some(max_id) = &options.max_id {
-                params.push(format!("max_id={}", max_id));
-            }
-            if let Some(min_id) = &options.min_id {
-                params.push(format!("min_id={}", min_id));
-            }
-            if let Some(resolve) = options.resolve {
-                params.push(format!("resolve={}", resolve));
-            }
-            if let Some(offset) = options.offset {
-                params.push(format!("offset={}", offset));
+            if let Some(types) = &options.types {
+                params.extend(notification_type_params("types", types));
             }
-            if let Some(following) = options.following {
-                params.push(format!("following={}", following));
+            if let Some(exclude_types) = &options.exclude_types {
+                params.extend(notification_type_params("exclude_types", exclude_types));
             }
             if let Some(account_id) = &options.account_id {
                 params.push(format!("account_id={}", account_id));
             }
-            if let Some(exclude_unreviewed) = options.exclude_unreviewed {
-                params.push(format!("exclude_unreviewed={}", exclude_unreviewed));
+            if let Some(grouped_types) = &options.grouped_types {
+                params.extend(notification_type_params("grouped_types", grouped_types));
             }
         }
-        let mut path = "/api/v2/search".to_string();
-        if params.len() > 0 {
+        let mut path = "/api/v2/notifications/unread_count".to_string();
+        if !params.is_empty() {
             path = path + "?" + params.join("&").as_str();
         }
         let res = self
             .client
-            .get::<entities::Results>(path.as_str(), None)
+            .get::<entities::UnreadCount>(path.as_str(), None)
             .await?;
 
-        Ok(Response::<MegalodonEntities::Results>::new(
+        Ok(Response::<MegalodonEntities::UnreadCount>::new(
             res.json.into(),
             res.status,
             res.status_text,
@@ -2911,13 +3404,15 @@ impl megalodon::Megalodon for Mastodon {
         ))
     }
 
-    async fn get_instance(&self) -> Result<Response<MegalodonEntities::Instance>, Error> {
+    async fn get_notification_policy(
+        &self,
+    ) -> Result<Response<MegalodonEntities::NotificationPolicy>, Error> {
         let res = self
             .client
-            .get::<entities::Instance>("/api/v1/instance", None)
+            .get::<entities::NotificationPolicy>("/api/v2/notifications/policy", None)
             .await?;
 
-        Ok(Response::<MegalodonEntities::Instance>::new(
+        Ok(Response::<MegalodonEntities::NotificationPolicy>::new(
             res.json.into(),
             res.status,
             res.status_text,
@@ -2925,84 +3420,1485 @@ impl megalodon::Megalodon for Mastodon {
         ))
     }
 
-    async fn get_instance_peers(&self) -> Result<Response<Vec<String>>, Error> {
-        let res = self
-            .client
-            .get::<Vec<String>>("/api/v1/instance/peers", None)
-            .await?;
-        Ok(res)
-    }
-
-    async fn get_instance_activity(
+    async fn update_notification_policy(
         &self,
-    ) -> Result<Response<Vec<MegalodonEntities::Activity>>, Error> {
+        options: &megalodon::UpdateNotificationPolicyInputOptions,
+    ) -> Result<Response<MegalodonEntities::NotificationPolicy>, Error> {
+        let mut params = HashMap::<&str, Value>::new();
+        if let Some(for_not_following) = &options.for_not_following {
+            params.insert(
+                "for_not_following",
+                serde_json::Value::String(for_not_following.to_string()),
+            );
+        }
+        if let Some(for_not_followers) = &options.for_not_followers {
+            params.insert(
+                "for_not_followers",
+                serde_json::Value::String(for_not_followers.to_string()),
+            );
+        }
+        if let Some(for_new_accounts) = &options.for_new_accounts {
+            params.insert(
+                "for_new_accounts",
+                serde_json::Value::String(for_new_accounts.to_string()),
+            );
+        }
+        if let Some(for_private_mentions) = &options.for_private_mentions {
+            params.insert(
+                "for_private_mentions",
+                serde_json::Value::String(for_private_mentions.to_string()),
+            );
+        }
+        if let Some(for_limited_accounts) = &options.for_limited_accounts {
+            params.insert(
+                "for_limited_accounts",
+                serde_json::Value::String(for_limited_accounts.to_string()),
+            );
+        }
         let res = self
             .client
-            .get::<Vec<entities::Activity>>("/api/v1/instance/activity", None)
+            .patch::<entities::NotificationPolicy>("/api/v2/notifications/policy", &params, None)
             .await?;
 
-        Ok(Response::<Vec<MegalodonEntities::Activity>>::new(
-            res.json.into_iter().map(|j| j.into()).collect(),
+        Ok(Response::<MegalodonEntities::NotificationPolicy>::new(
+            res.json.into(),
             res.status,
             res.status_text,
             res.header,
         ))
     }
 
-    async fn get_instance_trends(
+    async fn get_notification_requests(
         &self,
-        limit: Option<u32>,
-    ) -> Result<Response<Vec<MegalodonEntities::Tag>>, Error> {
+        options: Option<&megalodon::GetNotificationRequestsInputOptions>,
+    ) -> Result<Response<Vec<MegalodonEntities::NotificationRequest>>, Error> {
         let mut params = Vec::<String>::new();
-        if let Some(limit) = limit {
-            params.push(format!("limit={}", limit));
+        if let Some(options) = options {
+            if let Some(limit) = options.limit {
+                params.push(format!("limit={}", limit));
+            }
+            if let Some(max_id) = &options.max_id {
+                params.push(format!("max_id={}", max_id));
+            }
+            if let Some(since_id) = &options.since_id {
+                params.push(format!("since_id={}", since_id));
+            }
+            if let Some(min_id) = &options.min_id {
+                params.push(format!("min_id={}", min_id));
+            }
         }
-        let mut path = "/api/v1/trends".to_string();
-        if params.len() > 0 {
+        let mut path = "/api/v1/notifications/requests".to_string();
+        if !params.is_empty() {
             path = path + "?" + params.join("&").as_str();
         }
         let res = self
             .client
-            .get::<Vec<entities::Tag>>(path.as_str(), None)
+            .get::<Vec<entities::NotificationRequest>>(path.as_str(), None)
             .await?;
 
-        Ok(Response::<Vec<MegalodonEntities::Tag>>::new(
-            res.json.into_iter().map(|j| j.into()).collect(),
+        Ok(
+            Response::<Vec<MegalodonEntities::NotificationRequest>>::new(
+                res.json.into_iter().map(|j| j.into()).collect(),
+                res.status,
+                res.status_text,
+                res.header,
+            ),
+        )
+    }
+
+    async fn get_notification_request(
+        &self,
+        id: String,
+    ) -> Result<Response<MegalodonEntities::NotificationRequest>, Error> {
+        let res = self
+            .client
+            .get::<entities::NotificationRequest>(
+                format!("/api/v1/notifications/requests/{}", id).as_str(),
+                None,
+            )
+            .await?;
+
+        Ok(Response::<MegalodonEntities::NotificationRequest>::new(
+            res.json.into(),
             res.status,
             res.status_text,
             res.header,
         ))
     }
 
-    async fn get_instance_directory(
+    async fn accept_notification_request(&self, id: String) -> Result<Response<()>, Error> {
+        let params = HashMap::new();
+        let res = self
+            .client
+            .post::<Value>(
+                format!("/api/v1/notifications/requests/{}/accept", id).as_str(),
+                &params,
+                None,
+            )
+            .await?;
+        Ok(Response::<()>::new(
+            (),
+            res.status,
+            res.status_text,
+            res.header,
+        ))
+    }
+
+    async fn dismiss_notification_request(&self, id: String) -> Result<Response<()>, Error> {
+        let params = HashMap::new();
+        let res = self
+            .client
+            .post::<Value>(
+                format!("/api/v1/notifications/requests/{}/dismiss", id).as_str(),
+                &params,
+                None,
+            )
+            .await?;
+        Ok(Response::<()>::new(
+            (),
+            res.status,
+            res.status_text,
+            res.header,
+        ))
+    }
+
+    async fn accept_notification_requests(&self, ids: Vec<String>) -> Result<Response<()>, Error> {
+        let params = HashMap::<&str, Value>::from([("id", Value::from(ids))]);
+        let res = self
+            .client
+            .post::<Value>("/api/v1/notifications/requests/accept", &params, None)
+            .await?;
+        Ok(Response::<()>::new(
+            (),
+            res.status,
+            res.status_text,
+            res.header,
+        ))
+    }
+
+    async fn dismiss_notification_requests(&self, ids: Vec<String>) -> Result<Response<()>, Error> {
+        let params = HashMap::<&str, Value>::from([("id", Value::from(ids))]);
+        let res = self
+            .client
+            .post::<Value>("/api/v1/notifications/requests/dismiss", &params, None)
+            .await?;
+        Ok(Response::<()>::new(
+            (),
+            res.status,
+            res.status_text,
+            res.header,
+        ))
+    }
+
+    async fn get_notification_requests_merged(&self) -> Result<Response<bool>, Error> {
+        let res = self
+            .client
+            .get::<entities::NotificationRequestsMerged>(
+                "/api/v1/notifications/requests/merged",
+                None,
+            )
+            .await?;
+
+        Ok(Response::<bool>::new(
+            res.json.merged,
+            res.status,
+            res.status_text,
+            res.header,
+        ))
+    }
+
+    async fn subscribe_push_notification(
         &self,
-        options: Option<&megalodon::GetInstanceDirectoryInputOptions>,
-    ) -> Result<Response<Vec<MegalodonEntities::Account>>, Error> {
-        let mut params = Vec::<String>::new();
+        subscription: &megalodon::SubscribePushNotificationInputSubscription,
+        data: Option<&megalodon::SubscribePushNotificationInputData>,
+    ) -> Result<Response<MegalodonEntities::PushSubscription>, Error> {
+        let mut params = HashMap::<&str, Value>::from([(
+            "subscription",
+            serde_json::to_value(&subscription).ok().unwrap(),
+        )]);
+        if let Some(data) = data {
+            if let Some(json_data) = serde_json::to_value(&data).ok() {
+                params.insert("data", json_data);
+            }
+        }
+        let res = self
+            .client
+            .post::<entities::PushSubscription>("/api/v1/push/subscription", &params, None)
+            .await?;
+
+        Ok(Response::<MegalodonEntities::PushSubscription>::new(
+            res.json.into(),
+            res.status,
+            res.status_text,
+            res.header,
+        ))
+    }
+
+    async fn get_push_subscription(
+        &self,
+    ) -> Result<Response<MegalodonEntities::PushSubscription>, Error> {
+        let res = self
+            .client
+            .get::<entities::PushSubscription>("/api/v1/push/subscription", None)
+            .await?;
+
+        Ok(Response::<MegalodonEntities::PushSubscription>::new(
+            res.json.into(),
+            res.status,
+            res.status_text,
+            res.header,
+        ))
+    }
+
+    async fn update_push_subscription(
+        &self,
+        data: Option<&megalodon::SubscribePushNotificationInputData>,
+    ) -> Result<Response<MegalodonEntities::PushSubscription>, Error> {
+        let mut params = HashMap::<&str, Value>::new();
+        if let Some(data) = data {
+            if let Some(json_data) = serde_json::to_value(&data).ok() {
+                params.insert("data", json_data);
+            }
+        }
+        let res = self
+            .client
+            .put::<entities::PushSubscription>("/api/v1/push/subscription", &params, None)
+            .await?;
+
+        Ok(Response::<MegalodonEntities::PushSubscription>::new(
+            res.json.into(),
+            res.status,
+            res.status_text,
+            res.header,
+        ))
+    }
+
+    async fn delete_push_subscription(&self) -> Result<Response<()>, Error> {
+        let params = HashMap::new();
+        let res = self
+            .client
+            .delete::<()>("/api/v1/push/subscription", &params, None)
+            .await?;
+
+        Ok(res)
+    }
+
+    async fn search(
+        &self,
+        q: String,
+        options: Option<&megalodon::SearchInputOptions>,
+    ) -> Result<Response<MegalodonEntities::Results>, Error> {
+        let mut params = Vec::<String>::from([format!("q={}", q)]);
         if let Some(options) = options {
+            if let Some(t) = &options.r#type {
+                params.push(format!("type={}", t));
+            }
             if let Some(limit) = options.limit {
                 params.push(format!("limit={}", limit));
             }
+            if let Some(max_id) = &options.max_id {
+                params.push(format!("max_id={}", max_id));
+            }
+            if let Some(min_id) = &options.min_id {
+                params.push(format!("min_id={}", min_id));
+            }
+            if let Some(resolve) = options.resolve {
+                params.push(format!("resolve={}", resolve));
+            }
             if let Some(offset) = options.offset {
                 params.push(format!("offset={}", offset));
             }
-            if let Some(order) = &options.order {
-                params.push(format!("order={}", order));
+            if let Some(following) = options.following {
+                params.push(format!("following={}", following));
             }
-            if let Some(local) = options.local {
-                params.push(format!("local={}", local));
+            if let Some(account_id) = &options.account_id {
+                params.push(format!("account_id={}", account_id));
+            }
+            if let Some(exclude_unreviewed) = options.exclude_unreviewed {
+                params.push(format!("exclude_unreviewed={}", exclude_unreviewed));
+            }
+        }
+        let mut path = "/api/v2/search".to_string();
+        if params.len() > 0 {
+            path = path + "?" + params.join("&").as_str();
+        }
+        let res = self
+            .client
+            .get::<entities::Results>(path.as_str(), None)
+            .await?;
+
+        Ok(Response::<MegalodonEntities::Results>::new(
+            res.json.into(),
+            res.status,
+            res.status_text,
+            res.header,
+        ))
+    }
+
+    async fn get_instance(&self) -> Result<Response<MegalodonEntities::Instance>, Error> {
+        let res = self
+            .client
+            .get::<entities::Instance>("/api/v1/instance", None)
+            .await?;
+
+        Ok(Response::<MegalodonEntities::Instance>::new(
+            res.json.into(),
+            res.status,
+            res.status_text,
+            res.header,
+        ))
+    }
+
+    async fn get_instance_v2(&self) -> Result<Response<MegalodonEntities::InstanceV2>, Error> {
+        match self
+            .client
+            .get::<entities::InstanceV2>("/api/v2/instance", None)
+            .await
+        {
+            Ok(res) => Ok(Response::<MegalodonEntities::InstanceV2>::new(
+                res.json.into(),
+                res.status,
+                res.status_text,
+                res.header,
+            )),
+            // Mastodon older than 4.0 does not have v2 instance.
+            Err(Error::OwnError(error::OwnError {
+                kind: error::Kind::NotFoundError,
+                ..
+            })) => {
+                let res = self.get_instance().await?;
+                Ok(Response::<MegalodonEntities::InstanceV2>::new(
+                    res.json.into(),
+                    res.status,
+                    res.status_text,
+                    res.header,
+                ))
+            }
+            Err(err) => Err(err),
+        }
+    }
+
+    async fn capabilities(&self) -> Result<Capabilities, Error> {
+        let instance = self.get_instance_v2().await;
+        Ok(Capabilities::detect(SNS::Mastodon, instance))
+    }
+
+    async fn get_instance_peers(&self) -> Result<Response<Vec<String>>, Error> {
+        let res = self
+            .client
+            .get::<Vec<String>>("/api/v1/instance/peers", None)
+            .await?;
+        Ok(res)
+    }
+
+    async fn get_instance_activity(
+        &self,
+    ) -> Result<Response<Vec<MegalodonEntities::Activity>>, Error> {
+        let res = self
+            .client
+            .get::<Vec<entities::Activity>>("/api/v1/instance/activity", None)
+            .await?;
+
+        Ok(Response::<Vec<MegalodonEntities::Activity>>::new(
+            res.json.into_iter().map(|j| j.into()).collect(),
+            res.status,
+            res.status_text,
+            res.header,
+        ))
+    }
+
+    async fn get_instance_translation_languages(
+        &self,
+    ) -> Result<Response<HashMap<String, Vec<String>>>, Error> {
+        let res = self
+            .client
+            .get::<HashMap<String, Vec<String>>>("/api/v1/instance/translation_languages", None)
+            .await?;
+        Ok(res)
+    }
+
+    async fn get_instance_trends(
+        &self,
+        limit: Option<u32>,
+    ) -> Result<Response<Vec<MegalodonEntities::Tag>>, Error> {
+        let mut params = Vec::<String>::new();
+        if let Some(limit) = limit {
+            params.push(format!("limit={}", limit));
+        }
+        let mut path = "/api/v1/trends".to_string();
+        if params.len() > 0 {
+            path = path + "?" + params.join("&").as_str();
+        }
+        let res = self
+            .client
+            .get::<Vec<entities::Tag>>(path.as_str(), None)
+            .await?;
+
+        Ok(Response::<Vec<MegalodonEntities::Tag>>::new(
+            res.json.into_iter().map(|j| j.into()).collect(),
+            res.status,
+            res.status_text,
+            res.header,
+        ))
+    }
+
+    async fn get_trending_statuses(
+        &self,
+        options: Option<&megalodon::GetTrendingStatusesInputOptions>,
+    ) -> Result<Response<Vec<MegalodonEntities::Status>>, Error> {
+        let mut params = Vec::<String>::new();
+        if let Some(options) = options {
+            if let Some(limit) = options.limit {
+                params.push(format!("limit={}", limit));
+            }
+            if let Some(offset) = options.offset {
+                params.push(format!("offset={}", offset));
+            }
+        }
+        let mut path = "/api/v1/trends/statuses".to_string();
+        if !params.is_empty() {
+            path = path + "?" + params.join("&").as_str();
+        }
+        let res = self
+            .client
+            .get::<Vec<entities::Status>>(path.as_str(), None)
+            .await?;
+
+        Ok(Response::<Vec<MegalodonEntities::Status>>::new(
+            res.json.into_iter().map(|j| j.into()).collect(),
+            res.status,
+            res.status_text,
+            res.header,
+        ))
+    }
+
+    async fn get_trending_links(
+        &self,
+        options: Option<&megalodon::GetTrendingLinksInputOptions>,
+    ) -> Result<Response<Vec<MegalodonEntities::TrendsLink>>, Error> {
+        let mut params = Vec::<String>::new();
+        if let Some(options) = options {
+            if let Some(limit) = options.limit {
+                params.push(format!("limit={}", limit));
+            }
+            if let Some(offset) = options.offset {
+                params.push(format!("offset={}", offset));
+            }
+        }
+        let mut path = "/api/v1/trends/links".to_string();
+        if !params.is_empty() {
+            path = path + "?" + params.join("&").as_str();
+        }
+        let res = self
+            .client
+            .get::<Vec<entities::TrendsLink>>(path.as_str(), None)
+            .await?;
+
+        Ok(Response::<Vec<MegalodonEntities::TrendsLink>>::new(
+            res.json.into_iter().map(|j| j.into()).collect(),
+            res.status,
+            res.status_text,
+            res.header,
+        ))
+    }
+
+    async fn get_instance_directory(
+        &self,
+        options: Option<&megalodon::GetInstanceDirectoryInputOptions>,
+    ) -> Result<Response<Vec<MegalodonEntities::Account>>, Error> {
+        let mut params = Vec::<String>::new();
+        if let Some(options) = options {
+            if let Some(limit) = options.limit {
+                params.push(format!("limit={}", limit));
+            }
+            if let Some(offset) = options.offset {
+                params.push(format!("offset={}", offset));
+            }
+            if let Some(order) = &options.order {
+                params.push(format!("order={}", order));
+            }
+            if let Some(local) = options.local {
+                params.push(format!("local={}", local));
+            }
+        }
+        let mut path = "/api/v1/directory".to_string();
+        if params.len() > 0 {
+            path = path + "?" + params.join("&").as_str();
+        }
+        let res = self
+            .client
+            .get::<Vec<entities::Account>>(path.as_str(), None)
+            .await?;
+
+        Ok(Response::<Vec<MegalodonEntities::Account>>::new(
+            res.json.into_iter().map(|j| j.into()).collect(),
+            res.status,
+            res.status_text,
+            res.header,
+        ))
+    }
+
+    async fn get_instance_custom_emojis(
+        &self,
+    ) -> Result<Response<Vec<MegalodonEntities::Emoji>>, Error> {
+        let res = self
+            .client
+            .get::<Vec<entities::Emoji>>("/api/v1/custom_emojis", None)
+            .await?;
+
+        Ok(Response::<Vec<MegalodonEntities::Emoji>>::new(
+            res.json.into_iter().map(|j| j.into()).collect(),
+            res.status,
+            res.status_text,
+            res.header,
+        ))
+    }
+
+    async fn get_instance_announcements(
+        &self,
+    ) -> Result<Response<Vec<MegalodonEntities::Announcement>>, Error> {
+        let res = self
+            .client
+            .get::<Vec<entities::Announcement>>("/api/v1/announcements", None)
+            .await?;
+
+        Ok(Response::<Vec<MegalodonEntities::Announcement>>::new(
+            res.json.into_iter().map(|j| j.into()).collect(),
+            res.status,
+            res.status_text,
+            res.header,
+        ))
+    }
+
+    async fn dismiss_instance_announcement(&self, id: String) -> Result<Response<()>, Error> {
+        let params = HashMap::<&str, Value>::new();
+        let res = self
+            .client
+            .post::<()>(
+                format!("/api/v1/announcements/{}/dismiss", id).as_str(),
+                &params,
+                None,
+            )
+            .await?;
+
+        Ok(Response::<()>::new(
+            (),
+            res.status,
+            res.status_text,
+            res.header,
+        ))
+    }
+
+    async fn add_reaction_to_announcement(
+        &self,
+        id: String,
+        name: String,
+    ) -> Result<Response<()>, Error> {
+        let params = HashMap::<&str, Value>::new();
+        let res = self
+            .client
+            .put::<()>(
+                format!("/api/v1/announcements/{}/reactions/{}", id, name).as_str(),
+                &params,
+                None,
+            )
+            .await?;
+
+        Ok(Response::<()>::new(
+            (),
+            res.status,
+            res.status_text,
+            res.header,
+        ))
+    }
+
+    async fn remove_reaction_from_announcement(
+        &self,
+        id: String,
+        name: String,
+    ) -> Result<Response<()>, Error> {
+        let params = HashMap::<&str, Value>::new();
+        let res = self
+            .client
+            .delete::<()>(
+                format!("/api/v1/announcements/{}/reactions/{}", id, name).as_str(),
+                &params,
+                None,
+            )
+            .await?;
+
+        Ok(Response::<()>::new(
+            (),
+            res.status,
+            res.status_text,
+            res.header,
+        ))
+    }
+
+    async fn create_emoji_reaction(
+        &self,
+        _id: String,
+        _emoji: String,
+    ) -> Result<Response<MegalodonEntities::Status>, Error> {
+        Err(Error::new_own(
+            "Mastodon doest not support".to_string(),
+            error::Kind::NoImplementedError,
+            None,
+            None,
+            None,
+        ))
+    }
+
+    async fn delete_emoji_reaction(
+        &self,
+        _id: String,
+        _emoji: String,
+    ) -> Result<Response<MegalodonEntities::Status>, Error> {
+        Err(Error::new_own(
+            "Mastodon doest not support".to_string(),
+            error::Kind::NoImplementedError,
+            None,
+            None,
+            None,
+        ))
+    }
+
+    async fn get_emoji_reactions(
+        &self,
+        _id: String,
+    ) -> Result<Response<Vec<MegalodonEntities::Reaction>>, Error> {
+        Err(Error::new_own(
+            "Mastodon doest not support".to_string(),
+            error::Kind::NoImplementedError,
+            None,
+            None,
+            None,
+        ))
+    }
+
+    async fn get_emoji_reaction(
+        &self,
+        _id: String,
+        _emoji: String,
+    ) -> Result<Response<MegalodonEntities::Reaction>, Error> {
+        Err(Error::new_own(
+            "Mastodon doest not support".to_string(),
+            error::Kind::NoImplementedError,
+            None,
+            None,
+            None,
+        ))
+    }
+
+    async fn get_admin_accounts(
+        &self,
+        options: Option<&megalodon::GetAdminAccountsInputOptions>,
+    ) -> Result<Response<Vec<MegalodonEntities::admin::Account>>, Error> {
+        let mut params = Vec::<String>::new();
+        if let Some(options) = options {
+            if let Some(origin) = &options.origin {
+                params.push(format!("origin={}", origin));
+            }
+            if let Some(status) = &options.status {
+                params.push(format!("status={}", status));
+            }
+            if let Some(permissions) = &options.permissions {
+                params.push(format!("permissions={}", permissions));
+            }
+            if let Some(role_ids) = &options.role_ids {
+                params.extend(role_ids.iter().map(|id| format!("role_ids[]={}", id)));
+            }
+            if let Some(invited_by) = &options.invited_by {
+                params.push(format!("invited_by={}", invited_by));
+            }
+            if let Some(username) = &options.username {
+                params.push(format!("username={}", username));
+            }
+            if let Some(display_name) = &options.display_name {
+                params.push(format!("display_name={}", display_name));
+            }
+            if let Some(by_domain) = &options.by_domain {
+                params.push(format!("by_domain={}", by_domain));
+            }
+            if let Some(email) = &options.email {
+                params.push(format!("email={}", email));
+            }
+            if let Some(ip) = &options.ip {
+                params.push(format!("ip={}", ip));
+            }
+            if let Some(limit) = options.limit {
+                params.push(format!("limit={}", limit));
+            }
+            if let Some(max_id) = &options.max_id {
+                params.push(format!("max_id={}", max_id));
+            }
+            if let Some(since_id) = &options.since_id {
+                params.push(format!("since_id={}", since_id));
+            }
+            if let Some(min_id) = &options.min_id {
+                params.push(format!("min_id={}", min_id));
+            }
+        }
+        let mut path = "/api/v2/admin/accounts".to_string();
+        if !params.is_empty() {
+            path = path + "?" + params.join("&").as_str();
+        }
+        let res = self
+            .client
+            .get::<Vec<entities::admin::Account>>(path.as_str(), None)
+            .await?;
+
+        Ok(Response::<Vec<MegalodonEntities::admin::Account>>::new(
+            res.json.into_iter().map(|j| j.into()).collect(),
+            res.status,
+            res.status_text,
+            res.header,
+        ))
+    }
+
+    async fn get_admin_account(
+        &self,
+        id: String,
+    ) -> Result<Response<MegalodonEntities::admin::Account>, Error> {
+        let res = self
+            .client
+            .get::<entities::admin::Account>(
+                format!("/api/v1/admin/accounts/{}", id).as_str(),
+                None,
+            )
+            .await?;
+
+        Ok(Response::<MegalodonEntities::admin::Account>::new(
+            res.json.into(),
+            res.status,
+            res.status_text,
+            res.header,
+        ))
+    }
+
+    async fn approve_admin_account(
+        &self,
+        id: String,
+    ) -> Result<Response<MegalodonEntities::admin::Account>, Error> {
+        let params = HashMap::<&str, Value>::new();
+        let res = self
+            .client
+            .post::<entities::admin::Account>(
+                format!("/api/v1/admin/accounts/{}/approve", id).as_str(),
+                &params,
+                None,
+            )
+            .await?;
+
+        Ok(Response::<MegalodonEntities::admin::Account>::new(
+            res.json.into(),
+            res.status,
+            res.status_text,
+            res.header,
+        ))
+    }
+
+    async fn reject_admin_account(
+        &self,
+        id: String,
+    ) -> Result<Response<MegalodonEntities::admin::Account>, Error> {
+        let params = HashMap::<&str, Value>::new();
+        let res = self
+            .client
+            .post::<entities::admin::Account>(
+                format!("/api/v1/admin/accounts/{}/reject", id).as_str(),
+                &params,
+                None,
+            )
+            .await?;
+
+        Ok(Response::<MegalodonEntities::admin::Account>::new(
+            res.json.into(),
+            res.status,
+            res.status_text,
+            res.header,
+        ))
+    }
+
+    async fn perform_admin_account_action(
+        &self,
+        id: String,
+        action: megalodon::AdminAccountAction,
+        options: Option<&megalodon::AdminAccountActionInputOptions>,
+    ) -> Result<Response<()>, Error> {
+        let mut params =
+            HashMap::<&str, Value>::from([("type", serde_json::Value::String(action.to_string()))]);
+        if let Some(options) = options {
+            if let Some(report_id) = &options.report_id {
+                params.insert("report_id", serde_json::Value::String(report_id.clone()));
+            }
+            if let Some(warning_preset_id) = &options.warning_preset_id {
+                params.insert(
+                    "warning_preset_id",
+                    serde_json::Value::String(warning_preset_id.clone()),
+                );
+            }
+            if let Some(text) = &options.text {
+                params.insert("text", serde_json::Value::String(text.clone()));
+            }
+            if let Some(send_email_notification) = options.send_email_notification {
+                params.insert(
+                    "send_email_notification",
+                    serde_json::Value::Bool(send_email_notification),
+                );
+            }
+        }
+        let res = self
+            .client
+            .post::<Value>(
+                format!("/api/v1/admin/accounts/{}/action", id).as_str(),
+                &params,
+                None,
+            )
+            .await?;
+
+        Ok(Response::<()>::new(
+            (),
+            res.status,
+            res.status_text,
+            res.header,
+        ))
+    }
+
+    async fn enable_admin_account(
+        &self,
+        id: String,
+    ) -> Result<Response<MegalodonEntities::admin::Account>, Error> {
+        let params = HashMap::<&str, Value>::new();
+        let res = self
+            .client
+            .post::<entities::admin::Account>(
+                format!("/api/v1/admin/accounts/{}/enable", id).as_str(),
+                &params,
+                None,
+            )
+            .await?;
+
+        Ok(Response::<MegalodonEntities::admin::Account>::new(
+            res.json.into(),
+            res.status,
+            res.status_text,
+            res.header,
+        ))
+    }
+
+    async fn unsilence_admin_account(
+        &self,
+        id: String,
+    ) -> Result<Response<MegalodonEntities::admin::Account>, Error> {
+        let params = HashMap::<&str, Value>::new();
+        let res = self
+            .client
+            .post::<entities::admin::Account>(
+                format!("/api/v1/admin/accounts/{}/unsilence", id).as_str(),
+                &params,
+                None,
+            )
+            .await?;
+
+        Ok(Response::<MegalodonEntities::admin::Account>::new(
+            res.json.into(),
+            res.status,
+            res.status_text,
+            res.header,
+        ))
+    }
+
+    async fn unsuspend_admin_account(
+        &self,
+        id: String,
+    ) -> Result<Response<MegalodonEntities::admin::Account>, Error> {
+        let params = HashMap::<&str, Value>::new();
+        let res = self
+            .client
+            .post::<entities::admin::Account>(
+                format!("/api/v1/admin/accounts/{}/unsuspend", id).as_str(),
+                &params,
+                None,
+            )
+            .await?;
+
+        Ok(Response::<MegalodonEntities::admin::Account>::new(
+            res.json.into(),
+            res.status,
+            res.status_text,
+            res.header,
+        ))
+    }
+
+    async fn unsensitive_admin_account(
+        &self,
+        id: String,
+    ) -> Result<Response<MegalodonEntities::admin::Account>, Error> {
+        let params = HashMap::<&str, Value>::new();
+        let res = self
+            .client
+            .post::<entities::admin::Account>(
+                format!("/api/v1/admin/accounts/{}/unsensitive", id).as_str(),
+                &params,
+                None,
+            )
+            .await?;
+
+        Ok(Response::<MegalodonEntities::admin::Account>::new(
+            res.json.into(),
+            res.status,
+            res.status_text,
+            res.header,
+        ))
+    }
+
+    async fn delete_admin_account(
+        &self,
+        id: String,
+    ) -> Result<Response<MegalodonEntities::admin::Account>, Error> {
+        let params = HashMap::<&str, Value>::new();
+        let res = self
+            .client
+            .delete::<entities::admin::Account>(
+                format!("/api/v1/admin/accounts/{}", id).as_str(),
+                &params,
+                None,
+            )
+            .await?;
+
+        Ok(Response::<MegalodonEntities::admin::Account>::new(
+            res.json.into(),
+            res.status,
+            res.status_text,
+            res.header,
+        ))
+    }
+
+    async fn get_admin_reports(
+        &self,
+        options: Option<&megalodon::GetAdminReportsInputOptions>,
+    ) -> Result<Response<Vec<MegalodonEntities::admin::Report>>, Error> {
+        let mut params = Vec::<String>::new();
+        if let Some(options) = options {
+            if let Some(resolved) = options.resolved {
+                params.push(format!("resolved={}", resolved));
+            }
+            if let Some(account_id) = &options.account_id {
+                params.push(format!("account_id={}", account_id));
+            }
+            if let Some(target_account_id) = &options.target_account_id {
+                params.push(format!("target_account_id={}", target_account_id));
+            }
+            if let Some(limit) = options.limit {
+                params.push(format!("limit={}", limit));
+            }
+            if let Some(max_id) = &options.max_id {
+                params.push(format!("max_id={}", max_id));
+            }
+            if let Some(since_id) = &options.since_id {
+                params.push(format!("since_id={}", since_id));
+            }
+            if let Some(min_id) = &options.min_id {
+                params.push(format!("min_id={}", min_id));
+            }
+        }
+        let mut path = "/api/v1/admin/reports".to_string();
+        if !params.is_empty() {
+            path = path + "?" + params.join("&").as_str();
+        }
+        let res = self
+            .client
+            .get::<Vec<entities::admin::Report>>(path.as_str(), None)
+            .await?;
+
+        Ok(Response::<Vec<MegalodonEntities::admin::Report>>::new(
+            res.json.into_iter().map(|j| j.into()).collect(),
+            res.status,
+            res.status_text,
+            res.header,
+        ))
+    }
+
+    async fn get_admin_report(
+        &self,
+        id: String,
+    ) -> Result<Response<MegalodonEntities::admin::Report>, Error> {
+        let res = self
+            .client
+            .get::<entities::admin::Report>(format!("/api/v1/admin/reports/{}", id).as_str(), None)
+            .await?;
+
+        Ok(Response::<MegalodonEntities::admin::Report>::new(
+            res.json.into(),
+            res.status,
+            res.status_text,
+            res.header,
+        ))
+    }
+
+    async fn assign_admin_report_to_self(
+        &self,
+        id: String,
+    ) -> Result<Response<MegalodonEntities::admin::Report>, Error> {
+        let params = HashMap::<&str, Value>::new();
+        let res = self
+            .client
+            .post::<entities::admin::Report>(
+                format!("/api/v1/admin/reports/{}/assign_to_self", id).as_str(),
+                &params,
+                None,
+            )
+            .await?;
+
+        Ok(Response::<MegalodonEntities::admin::Report>::new(
+            res.json.into(),
+            res.status,
+            res.status_text,
+            res.header,
+        ))
+    }
+
+    async fn unassign_admin_report(
+        &self,
+        id: String,
+    ) -> Result<Response<MegalodonEntities::admin::Report>, Error> {
+        let params = HashMap::<&str, Value>::new();
+        let res = self
+            .client
+            .post::<entities::admin::Report>(
+                format!("/api/v1/admin/reports/{}/unassign", id).as_str(),
+                &params,
+                None,
+            )
+            .await?;
+
+        Ok(Response::<MegalodonEntities::admin::Report>::new(
+            res.json.into(),
+            res.status,
+            res.status_text,
+            res.header,
+        ))
+    }
+
+    async fn resolve_admin_report(
+        &self,
+        id: String,
+    ) -> Result<Response<MegalodonEntities::admin::Report>, Error> {
+        let params = HashMap::<&str, Value>::new();
+        let res = self
+            .client
+            .post::<entities::admin::Report>(
+                format!("/api/v1/admin/reports/{}/resolve", id).as_str(),
+                &params,
+                None,
+            )
+            .await?;
+
+        Ok(Response::<MegalodonEntities::admin::Report>::new(
+            res.json.into(),
+            res.status,
+            res.status_text,
+            res.header,
+        ))
+    }
+
+    async fn reopen_admin_report(
+        &self,
+        id: String,
+    ) -> Result<Response<MegalodonEntities::admin::Report>, Error> {
+        let params = HashMap::<&str, Value>::new();
+        let res = self
+            .client
+            .post::<entities::admin::Report>(
+                format!("/api/v1/admin/reports/{}/reopen", id).as_str(),
+                &params,
+                None,
+            )
+            .await?;
+
+        Ok(Response::<MegalodonEntities::admin::Report>::new(
+            res.json.into(),
+            res.status,
+            res.status_text,
+            res.header,
+        ))
+    }
+
+    async fn get_admin_domain_blocks(
+        &self,
+        options: Option<&megalodon::GetAdminDomainBlocksInputOptions>,
+    ) -> Result<Response<Vec<MegalodonEntities::admin::DomainBlock>>, Error> {
+        let mut params = Vec::<String>::new();
+        if let Some(options) = options {
+            if let Some(limit) = options.limit {
+                params.push(format!("limit={}", limit));
+            }
+            if let Some(max_id) = &options.max_id {
+                params.push(format!("max_id={}", max_id));
+            }
+            if let Some(since_id) = &options.since_id {
+                params.push(format!("since_id={}", since_id));
+            }
+            if let Some(min_id) = &options.min_id {
+                params.push(format!("min_id={}", min_id));
+            }
+        }
+        let mut path = "/api/v1/admin/domain_blocks".to_string();
+        if !params.is_empty() {
+            path = path + "?" + params.join("&").as_str();
+        }
+        let res = self
+            .client
+            .get::<Vec<entities::admin::DomainBlock>>(path.as_str(), None)
+            .await?;
+
+        Ok(Response::<Vec<MegalodonEntities::admin::DomainBlock>>::new(
+            res.json.into_iter().map(|j| j.into()).collect(),
+            res.status,
+            res.status_text,
+            res.header,
+        ))
+    }
+
+    async fn get_admin_domain_block(
+        &self,
+        id: String,
+    ) -> Result<Response<MegalodonEntities::admin::DomainBlock>, Error> {
+        let res = self
+            .client
+            .get::<entities::admin::DomainBlock>(
+                format!("/api/v1/admin/domain_blocks/{}", id).as_str(),
+                None,
+            )
+            .await?;
+
+        Ok(Response::<MegalodonEntities::admin::DomainBlock>::new(
+            res.json.into(),
+            res.status,
+            res.status_text,
+            res.header,
+        ))
+    }
+
+    async fn create_admin_domain_block(
+        &self,
+        domain: String,
+        options: Option<&megalodon::AdminDomainBlockInputOptions>,
+    ) -> Result<Response<MegalodonEntities::admin::DomainBlock>, Error> {
+        let mut params =
+            HashMap::<&str, Value>::from([("domain", serde_json::Value::String(domain))]);
+        if let Some(options) = options {
+            if let Some(severity) = &options.severity {
+                params.insert("severity", serde_json::Value::String(severity.to_string()));
+            }
+            if let Some(reject_media) = options.reject_media {
+                params.insert("reject_media", serde_json::Value::Bool(reject_media));
+            }
+            if let Some(reject_reports) = options.reject_reports {
+                params.insert("reject_reports", serde_json::Value::Bool(reject_reports));
+            }
+            if let Some(private_comment) = &options.private_comment {
+                params.insert(
+                    "private_comment",
+                    serde_json::Value::String(private_comment.clone()),
+                );
+            }
+            if let Some(public_comment) = &options.public_comment {
+                params.insert(
+                    "public_comment",
+                    serde_json::Value::String(public_comment.clone()),
+                );
+            }
+            if let Some(obfuscate) = options.obfuscate {
+                params.insert("obfuscate", serde_json::Value::Bool(obfuscate));
+            }
+        }
+        let res = self
+            .client
+            .post::<entities::admin::DomainBlock>("/api/v1/admin/domain_blocks", &params, None)
+            .await?;
+
+        Ok(Response::<MegalodonEntities::admin::DomainBlock>::new(
+            res.json.into(),
+            res.status,
+            res.status_text,
+            res.header,
+        ))
+    }
+
+    async fn update_admin_domain_block(
+        &self,
+        id: String,
+        options: &megalodon::AdminDomainBlockInputOptions,
+    ) -> Result<Response<MegalodonEntities::admin::DomainBlock>, Error> {
+        let mut params = HashMap::<&str, Value>::new();
+        if let Some(severity) = &options.severity {
+            params.insert("severity", serde_json::Value::String(severity.to_string()));
+        }
+        if let Some(reject_media) = options.reject_media {
+            params.insert("reject_media", serde_json::Value::Bool(reject_media));
+        }
+        if let Some(reject_reports) = options.reject_reports {
+            params.insert("reject_reports", serde_json::Value::Bool(reject_reports));
+        }
+        if let Some(private_comment) = &options.private_comment {
+            params.insert(
+                "private_comment",
+                serde_json::Value::String(private_comment.clone()),
+            );
+        }
+        if let Some(public_comment) = &options.public_comment {
+            params.insert(
+                "public_comment",
+                serde_json::Value::String(public_comment.clone()),
+            );
+        }
+        if let Some(obfuscate) = options.obfuscate {
+            params.insert("obfuscate", serde_json::Value::Bool(obfuscate));
+        }
+        let res = self
+            .client
+            .put::<entities::admin::DomainBlock>(
+                format!("/api/v1/admin/domain_blocks/{}", id).as_str(),
+                &params,
+                None,
+            )
+            .await?;
+
+        Ok(Response::<MegalodonEntities::admin::DomainBlock>::new(
+            res.json.into(),
+            res.status,
+            res.status_text,
+            res.header,
+        ))
+    }
+
+    async fn delete_admin_domain_block(&self, id: String) -> Result<Response<()>, Error> {
+        let params = HashMap::<&str, Value>::new();
+        let res = self
+            .client
+            .delete::<()>(
+                format!("/api/v1/admin/domain_blocks/{}", id).as_str(),
+                &params,
+                None,
+            )
+            .await?;
+
+        Ok(res)
+    }
+
+    async fn get_admin_domain_allows(
+        &self,
+        options: Option<&megalodon::GetAdminDomainAllowsInputOptions>,
+    ) -> Result<Response<Vec<MegalodonEntities::admin::DomainAllow>>, Error> {
+        let mut params = Vec::<String>::new();
+        if let Some(options) = options {
+            if let Some(limit) = options.limit {
+                params.push(format!("limit={}", limit));
+            }
+            if let Some(max_id) = &options.max_id {
+                params.push(format!("max_id={}", max_id));
+            }
+            if let Some(since_id) = &options.since_id {
+                params.push(format!("since_id={}", since_id));
+            }
+            if let Some(min_id) = &options.min_id {
+                params.push(format!("min_id={}", min_id));
+            }
+        }
+        let mut path = "/api/v1/admin/domain_allows".to_string();
+        if !params.is_empty() {
+            path = path + "?" + params.join("&").as_str();
+        }
+        let res = self
+            .client
+            .get::<Vec<entities::admin::DomainAllow>>(path.as_str(), None)
+            .await?;
+
+        Ok(Response::<Vec<MegalodonEntities::admin::DomainAllow>>::new(
+            res.json.into_iter().map(|j| j.into()).collect(),
+            res.status,
+            res.status_text,
+            res.header,
+        ))
+    }
+
+    async fn get_admin_domain_allow(
+        &self,
+        id: String,
+    ) -> Result<Response<MegalodonEntities::admin::DomainAllow>, Error> {
+        let res = self
+            .client
+            .get::<entities::admin::DomainAllow>(
+                format!("/api/v1/admin/domain_allows/{}", id).as_str(),
+                None,
+            )
+            .await?;
+
+        Ok(Response::<MegalodonEntities::admin::DomainAllow>::new(
+            res.json.into(),
+            res.status,
+            res.status_text,
+            res.header,
+        ))
+    }
+
+    async fn create_admin_domain_allow(
+        &self,
+        domain: String,
+    ) -> Result<Response<MegalodonEntities::admin::DomainAllow>, Error> {
+        let params = HashMap::<&str, Value>::from([("domain", serde_json::Value::String(domain))]);
+        let res = self
+            .client
+            .post::<entities::admin::DomainAllow>("/api/v1/admin/domain_allows", &params, None)
+            .await?;
+
+        Ok(Response::<MegalodonEntities::admin::DomainAllow>::new(
+            res.json.into(),
+            res.status,
+            res.status_text,
+            res.header,
+        ))
+    }
+
+    async fn delete_admin_domain_allow(&self, id: String) -> Result<Response<()>, Error> {
+        let params = HashMap::<&str, Value>::new();
+        let res = self
+            .client
+            .delete::<()>(
+                format!("/api/v1/admin/domain_allows/{}", id).as_str(),
+                &params,
+                None,
+            )
+            .await?;
+
+        Ok(res)
+    }
+
+    async fn get_admin_email_domain_blocks(
+        &self,
+        options: Option<&megalodon::GetAdminEmailDomainBlocksInputOptions>,
+    ) -> Result<Response<Vec<MegalodonEntities::admin::EmailDomainBlock>>, Error> {
+        let mut params = Vec::<String>::new();
+        if let Some(options) = options {
+            if let Some(limit) = options.limit {
+                params.push(format!("limit={}", limit));
+            }
+            if let Some(max_id) = &options.max_id {
+                params.push(format!("max_id={}", max_id));
+            }
+            if let Some(since_id) = &options.since_id {
+                params.push(format!("since_id={}", since_id));
+            }
+            if let Some(min_id) = &options.min_id {
+                params.push(format!("min_id={}", min_id));
+            }
+        }
+        let mut path = "/api/v1/admin/email_domain_blocks".to_string();
+        if !params.is_empty() {
+            path = path + "?" + params.join("&").as_str();
+        }
+        let res = self
+            .client
+            .get::<Vec<entities::admin::EmailDomainBlock>>(path.as_str(), None)
+            .await?;
+
+        Ok(
+            Response::<Vec<MegalodonEntities::admin::EmailDomainBlock>>::new(
+                res.json.into_iter().map(|j| j.into()).collect(),
+                res.status,
+                res.status_text,
+                res.header,
+            ),
+        )
+    }
+
+    async fn get_admin_email_domain_block(
+        &self,
+        id: String,
+    ) -> Result<Response<MegalodonEntities::admin::EmailDomainBlock>, Error> {
+        let res = self
+            .client
+            .get::<entities::admin::EmailDomainBlock>(
+                format!("/api/v1/admin/email_domain_blocks/{}", id).as_str(),
+                None,
+            )
+            .await?;
+
+        Ok(Response::<MegalodonEntities::admin::EmailDomainBlock>::new(
+            res.json.into(),
+            res.status,
+            res.status_text,
+            res.header,
+        ))
+    }
+
+    async fn create_admin_email_domain_block(
+        &self,
+        domain: String,
+    ) -> Result<Response<MegalodonEntities::admin::EmailDomainBlock>, Error> {
+        let params = HashMap::<&str, Value>::from([("domain", serde_json::Value::String(domain))]);
+        let res = self
+            .client
+            .post::<entities::admin::EmailDomainBlock>(
+                "/api/v1/admin/email_domain_blocks",
+                &params,
+                None,
+            )
+            .await?;
+
+        Ok(Response::<MegalodonEntities::admin::EmailDomainBlock>::new(
+            res.json.into(),
+            res.status,
+            res.status_text,
+            res.header,
+        ))
+    }
+
+    async fn delete_admin_email_domain_block(&self, id: String) -> Result<Response<()>, Error> {
+        let params = HashMap::<&str, Value>::new();
+        let res = self
+            .client
+            .delete::<()>(
+                format!("/api/v1/admin/email_domain_blocks/{}", id).as_str(),
+                &params,
+                None,
+            )
+            .await?;
+
+        Ok(res)
+    }
+
+    async fn get_admin_ip_blocks(
+        &self,
+        options: Option<&megalodon::GetAdminIpBlocksInputOptions>,
+    ) -> Result<Response<Vec<MegalodonEntities::admin::IpBlock>>, Error> {
+        let mut params = Vec::<String>::new();
+        if let Some(options) = options {
+            if let Some(limit) = options.limit {
+                params.push(format!("limit={}", limit));
+            }
+            if let Some(max_id) = &options.max_id {
+                params.push(format!("max_id={}", max_id));
+            }
+            if let Some(since_id) = &options.since_id {
+                params.push(format!("since_id={}", since_id));
+            }
+            if let Some(min_id) = &options.min_id {
+                params.push(format!("min_id={}", min_id));
             }
         }
-        let mut path = "/api/v1/directory".to_string();
-        if params.len() > 0 {
+        let mut path = "/api/v1/admin/ip_blocks".to_string();
+        if !params.is_empty() {
             path = path + "?" + params.join("&").as_str();
         }
         let res = self
             .client
-            .get::<Vec<entities::Account>>(path.as_str(), None)
+            .get::<Vec<entities::admin::IpBlock>>(path.as_str(), None)
             .await?;
 
-        Ok(Response::<Vec<MegalodonEntities::Account>>::new(
+        Ok(Response::<Vec<MegalodonEntities::admin::IpBlock>>::new(
             res.json.into_iter().map(|j| j.into()).collect(),
             res.status,
             res.status_text,
@@ -3010,165 +4906,320 @@ impl megalodon::Megalodon for Mastodon {
         ))
     }
 
-    async fn get_instance_custom_emojis(
+    async fn get_admin_ip_block(
         &self,
-    ) -> Result<Response<Vec<MegalodonEntities::Emoji>>, Error> {
+        id: String,
+    ) -> Result<Response<MegalodonEntities::admin::IpBlock>, Error> {
         let res = self
             .client
-            .get::<Vec<entities::Emoji>>("/api/v1/custom_emojis", None)
+            .get::<entities::admin::IpBlock>(
+                format!("/api/v1/admin/ip_blocks/{}", id).as_str(),
+                None,
+            )
             .await?;
 
-        Ok(Response::<Vec<MegalodonEntities::Emoji>>::new(
-            res.json.into_iter().map(|j| j.into()).collect(),
+        Ok(Response::<MegalodonEntities::admin::IpBlock>::new(
+            res.json.into(),
             res.status,
             res.status_text,
             res.header,
         ))
     }
 
-    async fn get_instance_announcements(
+    async fn create_admin_ip_block(
         &self,
-    ) -> Result<Response<Vec<MegalodonEntities::Announcement>>, Error> {
+        ip: String,
+        severity: MegalodonEntities::admin::IpBlockSeverity,
+        options: Option<&megalodon::AdminIpBlockInputOptions>,
+    ) -> Result<Response<MegalodonEntities::admin::IpBlock>, Error> {
+        let mut params = HashMap::<&str, Value>::from([
+            ("ip", serde_json::Value::String(ip)),
+            ("severity", serde_json::Value::String(severity.to_string())),
+        ]);
+        if let Some(options) = options {
+            if let Some(comment) = &options.comment {
+                params.insert("comment", serde_json::Value::String(comment.clone()));
+            }
+            if let Some(expires_in) = options.expires_in {
+                params.insert("expires_in", serde_json::Value::from(expires_in));
+            }
+        }
         let res = self
             .client
-            .get::<Vec<entities::Announcement>>("/api/v1/announcements", None)
+            .post::<entities::admin::IpBlock>("/api/v1/admin/ip_blocks", &params, None)
             .await?;
 
-        Ok(Response::<Vec<MegalodonEntities::Announcement>>::new(
-            res.json.into_iter().map(|j| j.into()).collect(),
+        Ok(Response::<MegalodonEntities::admin::IpBlock>::new(
+            res.json.into(),
             res.status,
             res.status_text,
             res.header,
         ))
     }
 
-    async fn dismiss_instance_announcement(&self, id: String) -> Result<Response<()>, Error> {
-        let params = HashMap::<&str, Value>::new();
+    async fn update_admin_ip_block(
+        &self,
+        id: String,
+        options: &megalodon::UpdateAdminIpBlockInputOptions,
+    ) -> Result<Response<MegalodonEntities::admin::IpBlock>, Error> {
+        let mut params = HashMap::<&str, Value>::new();
+        if let Some(ip) = &options.ip {
+            params.insert("ip", serde_json::Value::String(ip.clone()));
+        }
+        if let Some(severity) = &options.severity {
+            params.insert("severity", serde_json::Value::String(severity.to_string()));
+        }
+        if let Some(comment) = &options.comment {
+            params.insert("comment", serde_json::Value::String(comment.clone()));
+        }
+        if let Some(expires_in) = options.expires_in {
+            params.insert("expires_in", serde_json::Value::from(expires_in));
+        }
         let res = self
             .client
-            .post::<()>(
-                format!("/api/v1/announcements/{}/dismiss", id).as_str(),
+            .put::<entities::admin::IpBlock>(
+                format!("/api/v1/admin/ip_blocks/{}", id).as_str(),
                 &params,
                 None,
             )
             .await?;
 
-        Ok(Response::<()>::new(
-            (),
+        Ok(Response::<MegalodonEntities::admin::IpBlock>::new(
+            res.json.into(),
             res.status,
             res.status_text,
             res.header,
         ))
     }
 
-    async fn add_reaction_to_announcement(
-        &self,
-        id: String,
-        name: String,
-    ) -> Result<Response<()>, Error> {
+    async fn delete_admin_ip_block(&self, id: String) -> Result<Response<()>, Error> {
         let params = HashMap::<&str, Value>::new();
         let res = self
             .client
-            .put::<()>(
-                format!("/api/v1/announcements/{}/reactions/{}", id, name).as_str(),
+            .delete::<()>(
+                format!("/api/v1/admin/ip_blocks/{}", id).as_str(),
                 &params,
                 None,
             )
             .await?;
 
-        Ok(Response::<()>::new(
-            (),
-            res.status,
-            res.status_text,
-            res.header,
-        ))
+        Ok(res)
     }
 
-    async fn remove_reaction_from_announcement(
+    async fn get_admin_canonical_email_blocks(
+        &self,
+        options: Option<&megalodon::GetAdminCanonicalEmailBlocksInputOptions>,
+    ) -> Result<Response<Vec<MegalodonEntities::admin::CanonicalEmailBlock>>, Error> {
+        let mut params = Vec::<String>::new();
+        if let Some(options) = options {
+            if let Some(limit) = options.limit {
+                params.push(format!("limit={}", limit));
+            }
+            if let Some(max_id) = &options.max_id {
+                params.push(format!("max_id={}", max_id));
+            }
+            if let Some(since_id) = &options.since_id {
+                params.push(format!("since_id={}", since_id));
+            }
+            if let Some(min_id) = &options.min_id {
+                params.push(format!("min_id={}", min_id));
+            }
+        }
+        let mut path = "/api/v1/admin/canonical_email_blocks".to_string();
+        if !params.is_empty() {
+            path = path + "?" + params.join("&").as_str();
+        }
+        let res = self
+            .client
+            .get::<Vec<entities::admin::CanonicalEmailBlock>>(path.as_str(), None)
+            .await?;
+
+        Ok(
+            Response::<Vec<MegalodonEntities::admin::CanonicalEmailBlock>>::new(
+                res.json.into_iter().map(|j| j.into()).collect(),
+                res.status,
+                res.status_text,
+                res.header,
+            ),
+        )
+    }
+
+    async fn get_admin_canonical_email_block(
         &self,
         id: String,
-        name: String,
-    ) -> Result<Response<()>, Error> {
+    ) -> Result<Response<MegalodonEntities::admin::CanonicalEmailBlock>, Error> {
+        let res = self
+            .client
+            .get::<entities::admin::CanonicalEmailBlock>(
+                format!("/api/v1/admin/canonical_email_blocks/{}", id).as_str(),
+                None,
+            )
+            .await?;
+
+        Ok(
+            Response::<MegalodonEntities::admin::CanonicalEmailBlock>::new(
+                res.json.into(),
+                res.status,
+                res.status_text,
+                res.header,
+            ),
+        )
+    }
+
+    async fn test_admin_canonical_email_block(
+        &self,
+        email: String,
+    ) -> Result<Response<Vec<MegalodonEntities::admin::CanonicalEmailBlock>>, Error> {
+        let params = HashMap::<&str, Value>::from([("email", serde_json::Value::String(email))]);
+        let res = self
+            .client
+            .post::<Vec<entities::admin::CanonicalEmailBlock>>(
+                "/api/v1/admin/canonical_email_blocks/test",
+                &params,
+                None,
+            )
+            .await?;
+
+        Ok(
+            Response::<Vec<MegalodonEntities::admin::CanonicalEmailBlock>>::new(
+                res.json.into_iter().map(|j| j.into()).collect(),
+                res.status,
+                res.status_text,
+                res.header,
+            ),
+        )
+    }
+
+    async fn create_admin_canonical_email_block(
+        &self,
+        email: String,
+    ) -> Result<Response<MegalodonEntities::admin::CanonicalEmailBlock>, Error> {
+        let params = HashMap::<&str, Value>::from([("email", serde_json::Value::String(email))]);
+        let res = self
+            .client
+            .post::<entities::admin::CanonicalEmailBlock>(
+                "/api/v1/admin/canonical_email_blocks",
+                &params,
+                None,
+            )
+            .await?;
+
+        Ok(
+            Response::<MegalodonEntities::admin::CanonicalEmailBlock>::new(
+                res.json.into(),
+                res.status,
+                res.status_text,
+                res.header,
+            ),
+        )
+    }
+
+    async fn delete_admin_canonical_email_block(&self, id: String) -> Result<Response<()>, Error> {
         let params = HashMap::<&str, Value>::new();
         let res = self
             .client
             .delete::<()>(
-                format!("/api/v1/announcements/{}/reactions/{}", id, name).as_str(),
+                format!("/api/v1/admin/canonical_email_blocks/{}", id).as_str(),
                 &params,
                 None,
             )
             .await?;
 
-        Ok(Response::<()>::new(
-            (),
+        Ok(res)
+    }
+
+    async fn get_admin_measures(
+        &self,
+        keys: Vec<String>,
+        start_at: DateTime<Utc>,
+        end_at: DateTime<Utc>,
+    ) -> Result<Response<Vec<MegalodonEntities::admin::Measure>>, Error> {
+        let params = HashMap::<&str, Value>::from([
+            ("keys", serde_json::to_value(&keys).ok().unwrap_or_default()),
+            ("start_at", serde_json::Value::String(start_at.to_rfc3339())),
+            ("end_at", serde_json::Value::String(end_at.to_rfc3339())),
+        ]);
+        let res = self
+            .client
+            .post::<Vec<entities::admin::Measure>>("/api/v1/admin/measures", &params, None)
+            .await?;
+
+        Ok(Response::<Vec<MegalodonEntities::admin::Measure>>::new(
+            res.json.into_iter().map(|j| j.into()).collect(),
             res.status,
             res.status_text,
             res.header,
         ))
     }
 
-    async fn create_emoji_reaction(
+    async fn get_admin_dimensions(
         &self,
-        _id: String,
-        _emoji: String,
-    ) -> Result<Response<MegalodonEntities::Status>, Error> {
-        Err(Error::new_own(
-            "Mastodon doest not support".to_string(),
-            error::Kind::NoImplementedError,
-            None,
-            None,
-            None,
-        ))
-    }
+        keys: Vec<String>,
+        start_at: DateTime<Utc>,
+        end_at: DateTime<Utc>,
+        limit: Option<u32>,
+    ) -> Result<Response<Vec<MegalodonEntities::admin::Dimension>>, Error> {
+        let mut params = HashMap::<&str, Value>::from([
+            ("keys", serde_json::to_value(&keys).ok().unwrap_or_default()),
+            ("start_at", serde_json::Value::String(start_at.to_rfc3339())),
+            ("end_at", serde_json::Value::String(end_at.to_rfc3339())),
+        ]);
+        if let Some(limit) = limit {
+            params.insert("limit", serde_json::Value::from(limit));
+        }
+        let res = self
+            .client
+            .post::<Vec<entities::admin::Dimension>>("/api/v1/admin/dimensions", &params, None)
+            .await?;
 
-    async fn delete_emoji_reaction(
-        &self,
-        _id: String,
-        _emoji: String,
-    ) -> Result<Response<MegalodonEntities::Status>, Error> {
-        Err(Error::new_own(
-            "Mastodon doest not support".to_string(),
-            error::Kind::NoImplementedError,
-            None,
-            None,
-            None,
+        Ok(Response::<Vec<MegalodonEntities::admin::Dimension>>::new(
+            res.json.into_iter().map(|j| j.into()).collect(),
+            res.status,
+            res.status_text,
+            res.header,
         ))
     }
 
-    async fn get_emoji_reactions(
+    async fn get_admin_retention(
         &self,
-        _id: String,
-    ) -> Result<Response<Vec<MegalodonEntities::Reaction>>, Error> {
-        Err(Error::new_own(
-            "Mastodon doest not support".to_string(),
-            error::Kind::NoImplementedError,
-            None,
-            None,
-            None,
-        ))
-    }
+        start_at: DateTime<Utc>,
+        end_at: DateTime<Utc>,
+        frequency: MegalodonEntities::admin::CohortFrequency,
+    ) -> Result<Response<Vec<MegalodonEntities::admin::Cohort>>, Error> {
+        let params = HashMap::<&str, Value>::from([
+            ("start_at", serde_json::Value::String(start_at.to_rfc3339())),
+            ("end_at", serde_json::Value::String(end_at.to_rfc3339())),
+            (
+                "frequency",
+                serde_json::Value::String(frequency.to_string()),
+            ),
+        ]);
+        let res = self
+            .client
+            .post::<Vec<entities::admin::Cohort>>("/api/v1/admin/retention", &params, None)
+            .await?;
 
-    async fn get_emoji_reaction(
-        &self,
-        _id: String,
-        _emoji: String,
-    ) -> Result<Response<MegalodonEntities::Reaction>, Error> {
-        Err(Error::new_own(
-            "Mastodon doest not support".to_string(),
-            error::Kind::NoImplementedError,
-            None,
-            None,
-            None,
+        Ok(Response::<Vec<MegalodonEntities::admin::Cohort>>::new(
+            res.json.into_iter().map(|j| j.into()).collect(),
+            res.status,
+            res.status_text,
+            res.header,
         ))
     }
 
     async fn streaming_url(&self) -> String {
+        let instance = self.get_instance_v2().await;
+        if let Ok(instance) = instance {
+            if let Some(streaming) = instance.json.configuration.urls.streaming {
+                return streaming;
+            }
+        }
+        // Older servers do not have v2 instance, so fall back to v1 instance.
         let instance = self.get_instance().await;
         if let Ok(instance) = instance {
-            match instance.json.urls {
-                Some(urls) => return urls.streaming_api,
-                _ => {}
-            };
+            if let Some(urls) = instance.json.urls {
+                return urls.streaming_api;
+            }
         }
 
         self.base_url.clone()
@@ -3183,6 +5234,7 @@ impl megalodon::Megalodon for Mastodon {
             Some(params),
             self.access_token.clone(),
             self.user_agent.clone(),
+            self.client.http_client(),
         );
 
         Box::new(c)
@@ -3197,6 +5249,7 @@ impl megalodon::Megalodon for Mastodon {
             Some(params),
             self.access_token.clone(),
             self.user_agent.clone(),
+            self.client.http_client(),
         );
 
         Box::new(c)
@@ -3211,6 +5264,7 @@ impl megalodon::Megalodon for Mastodon {
             Some(params),
             self.access_token.clone(),
             self.user_agent.clone(),
+            self.client.http_client(),
         );
 
         Box::new(c)
@@ -3225,6 +5279,7 @@ impl megalodon::Megalodon for Mastodon {
             Some(params),
             self.access_token.clone(),
             self.user_agent.clone(),
+            self.client.http_client(),
         );
 
         Box::new(c)
@@ -3239,6 +5294,7 @@ impl megalodon::Megalodon for Mastodon {
             Some(params),
             self.access_token.clone(),
             self.user_agent.clone(),
+            self.client.http_client(),
         );
 
         Box::new(c)
@@ -3253,8 +5309,352 @@ impl megalodon::Megalodon for Mastodon {
             Some(params),
             self.access_token.clone(),
             self.user_agent.clone(),
+            self.client.http_client(),
         );
 
         Box::new(c)
     }
+
+    async fn multiplex_streaming(
+        &self,
+    ) -> Result<Box<dyn MultiplexStreaming + Send + Sync>, Error> {
+        let streaming_url = self.streaming_url().await;
+        let c = MultiplexWebSocket::new(
+            streaming_url + "/api/v1/streaming",
+            self.access_token.clone(),
+            self.user_agent.clone(),
+            WebSocket::parse_event,
+        );
+
+        Ok(Box::new(c))
+    }
+}
+
+// Types which Mastodon does not know are skipped, instead of being sent as unknown.
+fn notification_type_params(
+    key: &str,
+    types: &[MegalodonEntities::notification::NotificationType],
+) -> Vec<String> {
+    types
+        .iter()
+        .map(|t| entities::notification::NotificationType::from(t.clone()))
+        .filter(|t| !matches!(t, entities::notification::NotificationType::Unknown))
+        .map(|t| format!("{}[]={}", key, t))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::entities::notification::NotificationType;
+    use crate::entities::notification_policy::NotificationFilteringPolicy;
+    use crate::entities::quote_approval::QuoteApprovalPolicy;
+    use crate::http::testing::{serve, serve_with_status};
+    use crate::megalodon::Megalodon;
+
+    #[tokio::test]
+    async fn test_get_grouped_notifications_params() {
+        let (url, mut requests) = serve(vec![String::from(
+            r#"{"accounts": [], "statuses": [], "notification_groups": []}"#,
+        )])
+        .await;
+        let client = Mastodon::new(url, None, None).unwrap();
+        let options = megalodon::GetGroupedNotificationsInputOptions {
+            limit: Some(20),
+            types: Some(vec![
+                NotificationType::Mention,
+                NotificationType::PollExpired,
+            ]),
+            exclude_types: Some(vec![NotificationType::PollVote]),
+            grouped_types: Some(vec![NotificationType::Favourite]),
+            ..Default::default()
+        };
+        client
+            .get_grouped_notifications(Some(&options))
+            .await
+            .unwrap();
+
+        let request = requests.recv().await.unwrap();
+        assert_eq!(request.method, "GET");
+        assert!(request.body.is_empty());
+        assert_eq!(
+            request.path,
+            "/api/v2/notifications?limit=20&types[]=mention&types[]=poll&exclude_types[]=poll&grouped_types[]=favourite"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_get_unread_notification_count_params() {
+        let (url, mut requests) = serve(vec![String::from(r#"{"count": 3}"#)]).await;
+        let client = Mastodon::new(url, None, None).unwrap();
+        let options = megalodon::GetUnreadNotificationCountInputOptions {
+            types: Some(vec![NotificationType::PollVote]),
+            exclude_types: Some(vec![NotificationType::AdminSignup]),
+            ..Default::default()
+        };
+        let res = client
+            .get_unread_notification_count(Some(&options))
+            .await
+            .unwrap();
+
+        assert_eq!(res.json.count, 3);
+        let request = requests.recv().await.unwrap();
+        assert_eq!(
+            request.path,
+            "/api/v2/notifications/unread_count?types[]=poll&exclude_types[]=admin.sign_up"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_notification_type_params_skip_unknown() {
+        let (url, mut requests) = serve(vec![String::from(r#"{"count": 0}"#)]).await;
+        let client = Mastodon::new(url, None, None).unwrap();
+        let options = megalodon::GetUnreadNotificationCountInputOptions {
+            types: Some(vec![NotificationType::Reaction, NotificationType::Mention]),
+            exclude_types: Some(vec![NotificationType::Move]),
+            ..Default::default()
+        };
+        client
+            .get_unread_notification_count(Some(&options))
+            .await
+            .unwrap();
+
+        let request = requests.recv().await.unwrap();
+        assert_eq!(
+            request.path,
+            "/api/v2/notifications/unread_count?types[]=mention"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_update_notification_policy_body() {
+        let (url, mut requests) = serve(vec![String::from(
+            r#"{
+                "for_not_following": "filter",
+                "for_not_followers": "accept",
+                "for_new_accounts": "drop",
+                "for_private_mentions": "filter",
+                "for_limited_accounts": "filter",
+                "summary": {"pending_requests_count": 0, "pending_notifications_count": 0}
+            }"#,
+        )])
+        .await;
+        let client = Mastodon::new(url, None, None).unwrap();
+        let options = megalodon::UpdateNotificationPolicyInputOptions {
+            for_new_accounts: Some(NotificationFilteringPolicy::Drop),
+            for_private_mentions: Some(NotificationFilteringPolicy::Filter),
+            ..Default::default()
+        };
+        let res = client.update_notification_policy(&options).await.unwrap();
+
+        assert_eq!(res.json.for_new_accounts, NotificationFilteringPolicy::Drop);
+        let request = requests.recv().await.unwrap();
+        assert_eq!(request.method, "PATCH");
+        assert_eq!(request.path, "/api/v2/notifications/policy");
+        assert_eq!(
+            request.json(),
+            serde_json::json!({"for_new_accounts": "drop", "for_private_mentions": "filter"})
+        );
+    }
+
+    #[tokio::test]
+    async fn test_bulk_notification_requests_body() {
+        let (url, mut requests) = serve(vec![String::from("{}"), String::from("{}")]).await;
+        let client = Mastodon::new(url, None, None).unwrap();
+        let ids = vec![String::from("1"), String::from("2")];
+        client
+            .accept_notification_requests(ids.clone())
+            .await
+            .unwrap();
+        client.dismiss_notification_requests(ids).await.unwrap();
+
+        let request = requests.recv().await.unwrap();
+        assert_eq!(request.method, "POST");
+        assert_eq!(request.path, "/api/v1/notifications/requests/accept");
+        assert_eq!(request.json(), serde_json::json!({"id": ["1", "2"]}));
+        let request = requests.recv().await.unwrap();
+        assert_eq!(request.path, "/api/v1/notifications/requests/dismiss");
+        assert_eq!(request.json(), serde_json::json!({"id": ["1", "2"]}));
+    }
+
+    #[tokio::test]
+    async fn test_streaming_url_falls_back_to_v1_instance() {
+        let instance = serde_json::json!({
+            "uri": "mastodon.example",
+            "title": "Mastodon",
+            "description": "",
+            "email": "admin@mastodon.example",
+            "version": "3.5.3",
+            "urls": {"streaming_api": "wss://streaming.mastodon.example"},
+            "stats": {"user_count": 1, "status_count": 1, "domain_count": 1},
+            "languages": ["en"],
+            "registrations": false,
+            "approval_required": false,
+            "invites_enabled": false,
+            "configuration": {
+                "statuses": {
+                    "max_characters": 500,
+                    "max_media_attachments": 4,
+                    "characters_reserved_per_url": 23
+                },
+                "media_attachments": {
+                    "supported_mime_types": ["image/jpeg"],
+                    "image_size_limit": 10485760,
+                    "image_matrix_limit": 16777216,
+                    "video_size_limit": 41943040,
+                    "video_frame_rate_limit": 60,
+                    "video_matrix_limit": 2304000
+                },
+                "polls": {
+                    "max_options": 4,
+                    "max_characters_per_option": 50,
+                    "min_expiration": 300,
+                    "max_expiration": 2629746
+                }
+            },
+            "contact_account": {
+                "id": "1",
+                "username": "admin",
+                "acct": "admin",
+                "display_name": "admin",
+                "locked": false,
+                "created_at": "2023-01-01T00:00:00Z",
+                "followers_count": 0,
+                "following_count": 0,
+                "statuses_count": 0,
+                "note": "",
+                "url": "https://mastodon.example/@admin",
+                "avatar": "",
+                "avatar_static": "",
+                "header": "",
+                "header_static": "",
+                "emojis": [],
+                "fields": [],
+                "bot": false
+            },
+            "rules": []
+        });
+        // v2 instance fails with an error other than 404.
+        let (url, mut requests) = serve_with_status(vec![
+            (500, String::from(r#"{"error": "Internal Server Error"}"#)),
+            (200, instance.to_string()),
+        ])
+        .await;
+        let client = Mastodon::new(url, None, None).unwrap();
+
+        assert_eq!(
+            client.streaming_url().await,
+            "wss://streaming.mastodon.example"
+        );
+        assert_eq!(requests.recv().await.unwrap().path, "/api/v2/instance");
+        assert_eq!(requests.recv().await.unwrap().path, "/api/v1/instance");
+    }
+
+    fn status(id: &str) -> Value {
+        serde_json::json!({
+            "id": id,
+            "uri": format!("https://mastodon.example/statuses/{}", id),
+            "account": {
+                "id": "1",
+                "username": "alice",
+                "acct": "alice",
+                "display_name": "alice",
+                "locked": false,
+                "created_at": "2023-01-01T00:00:00Z",
+                "followers_count": 0,
+                "following_count": 0,
+                "statuses_count": 0,
+                "note": "",
+                "url": "https://mastodon.example/@alice",
+                "avatar": "",
+                "avatar_static": "",
+                "header": "",
+                "header_static": "",
+                "emojis": [],
+                "fields": [],
+                "bot": false
+            },
+            "content": "",
+            "created_at": "2024-01-01T00:00:00Z",
+            "emojis": [],
+            "replies_count": 0,
+            "reblogs_count": 0,
+            "favourites_count": 0,
+            "sensitive": false,
+            "spoiler_text": "",
+            "visibility": "public",
+            "media_attachments": [],
+            "mentions": [],
+            "tags": []
+        })
+    }
+
+    #[tokio::test]
+    async fn test_get_status_quotes_params() {
+        let (url, mut requests) = serve(vec![serde_json::json!([status("2")]).to_string()]).await;
+        let client = Mastodon::new(url, None, None).unwrap();
+        let options = megalodon::GetStatusQuotesInputOptions {
+            limit: Some(10),
+            max_id: Some(String::from("5")),
+            ..Default::default()
+        };
+        let res = client
+            .get_status_quotes(String::from("1"), Some(&options))
+            .await
+            .unwrap();
+
+        assert_eq!(res.json[0].id, "2");
+        let request = requests.recv().await.unwrap();
+        assert_eq!(request.method, "GET");
+        assert_eq!(request.path, "/api/v1/statuses/1/quotes?limit=10&max_id=5");
+    }
+
+    #[tokio::test]
+    async fn test_revoke_quote() {
+        let (url, mut requests) = serve(vec![status("2").to_string()]).await;
+        let client = Mastodon::new(url, None, None).unwrap();
+        let res = client
+            .revoke_quote(String::from("1"), String::from("2"))
+            .await
+            .unwrap();
+
+        assert_eq!(res.json.id, "2");
+        let request = requests.recv().await.unwrap();
+        assert_eq!(request.method, "POST");
+        assert_eq!(request.path, "/api/v1/statuses/1/quotes/2/revoke");
+    }
+
+    #[tokio::test]
+    async fn test_quote_approval_policy_body() {
+        let (url, mut requests) =
+            serve(vec![status("3").to_string(), status("3").to_string()]).await;
+        let client = Mastodon::new(url, None, None).unwrap();
+        let options = megalodon::PostStatusInputOptions {
+            quote_id: Some(String::from("1")),
+            quote_approval_policy: Some(QuoteApprovalPolicy::Followers),
+            ..Default::default()
+        };
+        client
+            .post_status(String::from("hello"), Some(&options))
+            .await
+            .unwrap();
+        let options = megalodon::EditStatusInputOptions {
+            quote_approval_policy: Some(QuoteApprovalPolicy::Nobody),
+            ..Default::default()
+        };
+        client
+            .edit_status(String::from("3"), &options)
+            .await
+            .unwrap();
+
+        let request = requests.recv().await.unwrap();
+        assert_eq!(request.path, "/api/v1/statuses");
+        let body = request.json();
+        assert_eq!(body["quoted_status_id"], "1");
+        assert_eq!(body["quote_approval_policy"], "followers");
+        let request = requests.recv().await.unwrap();
+        assert_eq!(request.method, "PUT");
+        assert_eq!(request.path, "/api/v1/statuses/3");
+        assert_eq!(request.json()["quote_approval_policy"], "nobody");
+    }
 }
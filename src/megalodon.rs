@@ -1,18 +1,21 @@
 //! Megalodon modules
 
 use core::fmt;
+use std::collections::HashMap;
 use std::str::FromStr;
 
 use crate::error::{Error, Kind};
 use crate::oauth::{AppData, TokenData};
-use crate::response::Response;
-use crate::{entities, Streaming};
+use crate::response::{Cursor, CursorOptions, Response};
+use crate::{capabilities::Capabilities, entities, streaming::MultiplexStreaming, Streaming};
 use async_trait::async_trait;
 use chrono::{DateTime, Utc};
 use serde::Serialize;
 use tokio::{fs::File, io::AsyncRead};
 
 /// Megalodon API interface
+///
+/// Methods which have a default implementation return [`Kind::NoImplementedError`] when the SNS does not support them.
 #[async_trait]
 pub trait Megalodon {
     /// Register the application to get client_id and client_secret.
@@ -276,6 +279,114 @@ pub trait Megalodon {
     /// Delete a filter.
     async fn delete_filter(&self, id: String) -> Result<Response<()>, Error>;
 
+    // ======================================
+    // filters v2
+    // ======================================
+    /// Get all filter groups of Filters v2.
+    async fn get_filters_v2(&self) -> Result<Response<Vec<entities::FilterV2>>, Error> {
+        no_implemented("get_filters_v2")
+    }
+
+    /// Get a specified filter group of Filters v2.
+    async fn get_filter_v2(&self, _id: String) -> Result<Response<entities::FilterV2>, Error> {
+        no_implemented("get_filter_v2")
+    }
+
+    /// Create a filter group of Filters v2.
+    async fn create_filter_v2(
+        &self,
+        _title: String,
+        _context: Vec<entities::filter::FilterContext>,
+        _options: Option<&CreateFilterV2InputOptions>,
+    ) -> Result<Response<entities::FilterV2>, Error> {
+        no_implemented("create_filter_v2")
+    }
+
+    /// Update a filter group of Filters v2. Keywords can be added, updated or removed at the same time.
+    async fn update_filter_v2(
+        &self,
+        _id: String,
+        _options: &UpdateFilterV2InputOptions,
+    ) -> Result<Response<entities::FilterV2>, Error> {
+        no_implemented("update_filter_v2")
+    }
+
+    /// Delete a filter group of Filters v2.
+    async fn delete_filter_v2(&self, _id: String) -> Result<Response<()>, Error> {
+        no_implemented("delete_filter_v2")
+    }
+
+    /// Get keywords of a filter group.
+    async fn get_filter_keywords(
+        &self,
+        _filter_id: String,
+    ) -> Result<Response<Vec<entities::FilterKeyword>>, Error> {
+        no_implemented("get_filter_keywords")
+    }
+
+    /// Add a keyword to a filter group.
+    async fn add_filter_keyword(
+        &self,
+        _filter_id: String,
+        _keyword: String,
+        _whole_word: Option<bool>,
+    ) -> Result<Response<entities::FilterKeyword>, Error> {
+        no_implemented("add_filter_keyword")
+    }
+
+    /// Get a specified keyword of a filter group.
+    async fn get_filter_keyword(
+        &self,
+        _id: String,
+    ) -> Result<Response<entities::FilterKeyword>, Error> {
+        no_implemented("get_filter_keyword")
+    }
+
+    /// Update a keyword of a filter group.
+    async fn update_filter_keyword(
+        &self,
+        _id: String,
+        _keyword: String,
+        _whole_word: Option<bool>,
+    ) -> Result<Response<entities::FilterKeyword>, Error> {
+        no_implemented("update_filter_keyword")
+    }
+
+    /// Remove a keyword from a filter group.
+    async fn delete_filter_keyword(&self, _id: String) -> Result<Response<()>, Error> {
+        no_implemented("delete_filter_keyword")
+    }
+
+    /// Get statuses of a filter group.
+    async fn get_filter_statuses(
+        &self,
+        _filter_id: String,
+    ) -> Result<Response<Vec<entities::FilterStatus>>, Error> {
+        no_implemented("get_filter_statuses")
+    }
+
+    /// Add a status to a filter group.
+    async fn add_filter_status(
+        &self,
+        _filter_id: String,
+        _status_id: String,
+    ) -> Result<Response<entities::FilterStatus>, Error> {
+        no_implemented("add_filter_status")
+    }
+
+    /// Get a specified status of a filter group.
+    async fn get_filter_status(
+        &self,
+        _id: String,
+    ) -> Result<Response<entities::FilterStatus>, Error> {
+        no_implemented("get_filter_status")
+    }
+
+    /// Remove a status from a filter group.
+    async fn delete_filter_status(&self, _id: String) -> Result<Response<()>, Error> {
+        no_implemented("delete_filter_status")
+    }
+
     // ======================================
     // accounts/reports
     // ======================================
@@ -386,6 +497,14 @@ pub trait Megalodon {
         id: String,
     ) -> Result<Response<entities::StatusSource>, Error>;
 
+    /// Get all known versions of a status, including the initial and current states.
+    async fn get_status_history(
+        &self,
+        _id: String,
+    ) -> Result<Response<Vec<entities::StatusEdit>>, Error> {
+        no_implemented("get_status_history")
+    }
+
     /// Edit a status.
     async fn edit_status(
         &self,
@@ -415,6 +534,24 @@ pub trait Megalodon {
         id: String,
     ) -> Result<Response<Vec<entities::Account>>, Error>;
 
+    /// Get statuses which quote a given status of your own.
+    async fn get_status_quotes(
+        &self,
+        _id: String,
+        _options: Option<&GetStatusQuotesInputOptions>,
+    ) -> Result<Response<Vec<entities::Status>>, Error> {
+        no_implemented("get_status_quotes")
+    }
+
+    /// Revoke a quote of your status. The quoting status is returned.
+    async fn revoke_quote(
+        &self,
+        _id: String,
+        _quoting_status_id: String,
+    ) -> Result<Response<entities::Status>, Error> {
+        no_implemented("revoke_quote")
+    }
+
     /// Add a status to your favourites list.
     async fn favourite_status(&self, id: String) -> Result<Response<entities::Status>, Error>;
 
@@ -445,6 +582,16 @@ pub trait Megalodon {
     /// Unfeature a status from the top of your profile.
     async fn unpin_status(&self, id: String) -> Result<Response<entities::Status>, Error>;
 
+    /// Translate the status into some language.
+    /// When `lang` is not given, Mastodon uses the locale of the user, while the other SNS require it.
+    async fn translate_status(
+        &self,
+        _id: String,
+        _lang: Option<String>,
+    ) -> Result<Response<entities::Translation>, Error> {
+        no_implemented("translate_status")
+    }
+
     // ======================================
     // statuses/media
     // ======================================
@@ -648,6 +795,108 @@ pub trait Megalodon {
         options: &ReadNotificationsInputOptions,
     ) -> Result<Response<()>, Error>;
 
+    // ======================================
+    // notifications v2
+    // ======================================
+    /// Notifications concerning the user, which are grouped by the server.
+    async fn get_grouped_notifications(
+        &self,
+        _options: Option<&GetGroupedNotificationsInputOptions>,
+    ) -> Result<Response<entities::GroupedNotificationsResults>, Error> {
+        no_implemented("get_grouped_notifications")
+    }
+
+    /// Get a notification group information.
+    async fn get_notification_group(
+        &self,
+        _group_key: String,
+    ) -> Result<Response<entities::GroupedNotificationsResults>, Error> {
+        no_implemented("get_notification_group")
+    }
+
+    /// Clear a notification group from the server.
+    async fn dismiss_notification_group(&self, _group_key: String) -> Result<Response<()>, Error> {
+        no_implemented("dismiss_notification_group")
+    }
+
+    /// Accounts of all notifications in a notification group.
+    async fn get_notification_group_accounts(
+        &self,
+        _group_key: String,
+    ) -> Result<Response<Vec<entities::Account>>, Error> {
+        no_implemented("get_notification_group_accounts")
+    }
+
+    /// Get the number of unread notifications.
+    async fn get_unread_notification_count(
+        &self,
+        _options: Option<&GetUnreadNotificationCountInputOptions>,
+    ) -> Result<Response<entities::UnreadCount>, Error> {
+        no_implemented("get_unread_notification_count")
+    }
+
+    // ======================================
+    // notifications/requests
+    // ======================================
+    /// Get the notification filtering policy of the user.
+    async fn get_notification_policy(
+        &self,
+    ) -> Result<Response<entities::NotificationPolicy>, Error> {
+        no_implemented("get_notification_policy")
+    }
+
+    /// Update the notification filtering policy of the user.
+    async fn update_notification_policy(
+        &self,
+        _options: &UpdateNotificationPolicyInputOptions,
+    ) -> Result<Response<entities::NotificationPolicy>, Error> {
+        no_implemented("update_notification_policy")
+    }
+
+    /// Notification requests for notifications filtered by the policy.
+    async fn get_notification_requests(
+        &self,
+        _options: Option<&GetNotificationRequestsInputOptions>,
+    ) -> Result<Response<Vec<entities::NotificationRequest>>, Error> {
+        no_implemented("get_notification_requests")
+    }
+
+    /// Get a notification request information.
+    async fn get_notification_request(
+        &self,
+        _id: String,
+    ) -> Result<Response<entities::NotificationRequest>, Error> {
+        no_implemented("get_notification_request")
+    }
+
+    /// Accept a notification request, which merges the filtered notifications into the main notifications.
+    async fn accept_notification_request(&self, _id: String) -> Result<Response<()>, Error> {
+        no_implemented("accept_notification_request")
+    }
+
+    /// Dismiss a notification request, which hides the filtered notifications.
+    async fn dismiss_notification_request(&self, _id: String) -> Result<Response<()>, Error> {
+        no_implemented("dismiss_notification_request")
+    }
+
+    /// Accept multiple notification requests at once.
+    async fn accept_notification_requests(&self, _ids: Vec<String>) -> Result<Response<()>, Error> {
+        no_implemented("accept_notification_requests")
+    }
+
+    /// Dismiss multiple notification requests at once.
+    async fn dismiss_notification_requests(
+        &self,
+        _ids: Vec<String>,
+    ) -> Result<Response<()>, Error> {
+        no_implemented("dismiss_notification_requests")
+    }
+
+    /// Whether accepted notification requests have been merged into the main notifications.
+    async fn get_notification_requests_merged(&self) -> Result<Response<bool>, Error> {
+        no_implemented("get_notification_requests_merged")
+    }
+
     // ======================================
     // notifications/push
     // ======================================
@@ -686,12 +935,28 @@ pub trait Megalodon {
     /// Get information about the server.
     async fn get_instance(&self) -> Result<Response<entities::Instance>, Error>;
 
+    /// Get v2 instance information. Servers without v2 instance are mapped from v1 instance.
+    async fn get_instance_v2(&self) -> Result<Response<entities::InstanceV2>, Error>;
+
+    /// Get features which are supported by the server.
+    /// Known capabilities of the SNS are refined with the version and the configuration of the server.
+    /// When the instance can not be fetched, only the known capabilities of the SNS are returned.
+    async fn capabilities(&self) -> Result<Capabilities, Error>;
+
     /// Get domains that this instance is aware of.
     async fn get_instance_peers(&self) -> Result<Response<Vec<String>>, Error>;
 
     /// Get instance activity over the last 3 months, binned weekly.
     async fn get_instance_activity(&self) -> Result<Response<Vec<entities::Activity>>, Error>;
 
+    /// Get languages which statuses can be translated from and into.
+    /// Keys are the source languages and values are the target languages, in ISO 639 codes.
+    async fn get_instance_translation_languages(
+        &self,
+    ) -> Result<Response<HashMap<String, Vec<String>>>, Error> {
+        no_implemented("get_instance_translation_languages")
+    }
+
     // ======================================
     // instance/trends
     // ======================================
@@ -701,6 +966,22 @@ pub trait Megalodon {
         limit: Option<u32>,
     ) -> Result<Response<Vec<entities::Tag>>, Error>;
 
+    /// Statuses that have been interacted with more than others.
+    async fn get_trending_statuses(
+        &self,
+        _options: Option<&GetTrendingStatusesInputOptions>,
+    ) -> Result<Response<Vec<entities::Status>>, Error> {
+        no_implemented("get_trending_statuses")
+    }
+
+    /// Links that have been shared more than others, with the usage history.
+    async fn get_trending_links(
+        &self,
+        _options: Option<&GetTrendingLinksInputOptions>,
+    ) -> Result<Response<Vec<entities::TrendsLink>>, Error> {
+        no_implemented("get_trending_links")
+    }
+
     // ======================================
     // instance/directory
     // ======================================
@@ -771,6 +1052,365 @@ pub trait Megalodon {
         emoji: String,
     ) -> Result<Response<entities::Reaction>, Error>;
 
+    // ======================================
+    // admin/accounts
+    // ======================================
+    /// View accounts matching certain criteria for moderation.
+    async fn get_admin_accounts(
+        &self,
+        _options: Option<&GetAdminAccountsInputOptions>,
+    ) -> Result<Response<Vec<entities::admin::Account>>, Error> {
+        no_implemented("get_admin_accounts")
+    }
+
+    /// View admin-level information about the given account.
+    async fn get_admin_account(
+        &self,
+        _id: String,
+    ) -> Result<Response<entities::admin::Account>, Error> {
+        no_implemented("get_admin_account")
+    }
+
+    /// Approve the given local account if it is currently pending approval.
+    async fn approve_admin_account(
+        &self,
+        _id: String,
+    ) -> Result<Response<entities::admin::Account>, Error> {
+        no_implemented("approve_admin_account")
+    }
+
+    /// Reject the given local account if it is currently pending approval.
+    async fn reject_admin_account(
+        &self,
+        _id: String,
+    ) -> Result<Response<entities::admin::Account>, Error> {
+        no_implemented("reject_admin_account")
+    }
+
+    /// Perform an action against an account, like suspending or silencing it.
+    async fn perform_admin_account_action(
+        &self,
+        _id: String,
+        _action: AdminAccountAction,
+        _options: Option<&AdminAccountActionInputOptions>,
+    ) -> Result<Response<()>, Error> {
+        no_implemented("perform_admin_account_action")
+    }
+
+    /// Re-enable a local account whose login is currently disabled.
+    async fn enable_admin_account(
+        &self,
+        _id: String,
+    ) -> Result<Response<entities::admin::Account>, Error> {
+        no_implemented("enable_admin_account")
+    }
+
+    /// Unsilence an account if it is currently silenced.
+    async fn unsilence_admin_account(
+        &self,
+        _id: String,
+    ) -> Result<Response<entities::admin::Account>, Error> {
+        no_implemented("unsilence_admin_account")
+    }
+
+    /// Unsuspend a currently suspended account.
+    async fn unsuspend_admin_account(
+        &self,
+        _id: String,
+    ) -> Result<Response<entities::admin::Account>, Error> {
+        no_implemented("unsuspend_admin_account")
+    }
+
+    /// Stop marking statuses of the account as sensitive.
+    async fn unsensitive_admin_account(
+        &self,
+        _id: String,
+    ) -> Result<Response<entities::admin::Account>, Error> {
+        no_implemented("unsensitive_admin_account")
+    }
+
+    /// Permanently delete data for a suspended account.
+    async fn delete_admin_account(
+        &self,
+        _id: String,
+    ) -> Result<Response<entities::admin::Account>, Error> {
+        no_implemented("delete_admin_account")
+    }
+
+    // ======================================
+    // admin/reports
+    // ======================================
+    /// View all reports.
+    async fn get_admin_reports(
+        &self,
+        _options: Option<&GetAdminReportsInputOptions>,
+    ) -> Result<Response<Vec<entities::admin::Report>>, Error> {
+        no_implemented("get_admin_reports")
+    }
+
+    /// View information about the report with the given ID.
+    async fn get_admin_report(
+        &self,
+        _id: String,
+    ) -> Result<Response<entities::admin::Report>, Error> {
+        no_implemented("get_admin_report")
+    }
+
+    /// Claim the handling of this report to yourself.
+    async fn assign_admin_report_to_self(
+        &self,
+        _id: String,
+    ) -> Result<Response<entities::admin::Report>, Error> {
+        no_implemented("assign_admin_report_to_self")
+    }
+
+    /// Unassign a report so that someone else can claim it.
+    async fn unassign_admin_report(
+        &self,
+        _id: String,
+    ) -> Result<Response<entities::admin::Report>, Error> {
+        no_implemented("unassign_admin_report")
+    }
+
+    /// Mark a report as resolved with no further action taken.
+    async fn resolve_admin_report(
+        &self,
+        _id: String,
+    ) -> Result<Response<entities::admin::Report>, Error> {
+        no_implemented("resolve_admin_report")
+    }
+
+    /// Reopen a currently closed report.
+    async fn reopen_admin_report(
+        &self,
+        _id: String,
+    ) -> Result<Response<entities::admin::Report>, Error> {
+        no_implemented("reopen_admin_report")
+    }
+
+    // ======================================
+    // admin/domain_blocks
+    // ======================================
+    /// Show information about all blocked domains.
+    async fn get_admin_domain_blocks(
+        &self,
+        _options: Option<&GetAdminDomainBlocksInputOptions>,
+    ) -> Result<Response<Vec<entities::admin::DomainBlock>>, Error> {
+        no_implemented("get_admin_domain_blocks")
+    }
+
+    /// Show information about a single blocked domain.
+    async fn get_admin_domain_block(
+        &self,
+        _id: String,
+    ) -> Result<Response<entities::admin::DomainBlock>, Error> {
+        no_implemented("get_admin_domain_block")
+    }
+
+    /// Add a domain to the list of domains blocked from federating.
+    async fn create_admin_domain_block(
+        &self,
+        _domain: String,
+        _options: Option<&AdminDomainBlockInputOptions>,
+    ) -> Result<Response<entities::admin::DomainBlock>, Error> {
+        no_implemented("create_admin_domain_block")
+    }
+
+    /// Change parameters for an existing domain block.
+    async fn update_admin_domain_block(
+        &self,
+        _id: String,
+        _options: &AdminDomainBlockInputOptions,
+    ) -> Result<Response<entities::admin::DomainBlock>, Error> {
+        no_implemented("update_admin_domain_block")
+    }
+
+    /// Lift a block against a domain.
+    async fn delete_admin_domain_block(&self, _id: String) -> Result<Response<()>, Error> {
+        no_implemented("delete_admin_domain_block")
+    }
+
+    // ======================================
+    // admin/domain_allows
+    // ======================================
+    /// Show information about all allowed domains.
+    async fn get_admin_domain_allows(
+        &self,
+        _options: Option<&GetAdminDomainAllowsInputOptions>,
+    ) -> Result<Response<Vec<entities::admin::DomainAllow>>, Error> {
+        no_implemented("get_admin_domain_allows")
+    }
+
+    /// Show information about a single allowed domain.
+    async fn get_admin_domain_allow(
+        &self,
+        _id: String,
+    ) -> Result<Response<entities::admin::DomainAllow>, Error> {
+        no_implemented("get_admin_domain_allow")
+    }
+
+    /// Add a domain to the list of domains allowed to federate, when the server is in allowlist mode.
+    async fn create_admin_domain_allow(
+        &self,
+        _domain: String,
+    ) -> Result<Response<entities::admin::DomainAllow>, Error> {
+        no_implemented("create_admin_domain_allow")
+    }
+
+    /// Delete a domain from the allowed domains list.
+    async fn delete_admin_domain_allow(&self, _id: String) -> Result<Response<()>, Error> {
+        no_implemented("delete_admin_domain_allow")
+    }
+
+    // ======================================
+    // admin/email_domain_blocks
+    // ======================================
+    /// Show information about all email domains blocked from signing up.
+    async fn get_admin_email_domain_blocks(
+        &self,
+        _options: Option<&GetAdminEmailDomainBlocksInputOptions>,
+    ) -> Result<Response<Vec<entities::admin::EmailDomainBlock>>, Error> {
+        no_implemented("get_admin_email_domain_blocks")
+    }
+
+    /// Show information about a single email domain that is blocked from signups.
+    async fn get_admin_email_domain_block(
+        &self,
+        _id: String,
+    ) -> Result<Response<entities::admin::EmailDomainBlock>, Error> {
+        no_implemented("get_admin_email_domain_block")
+    }
+
+    /// Add a domain to the list of email domains blocked from signups.
+    async fn create_admin_email_domain_block(
+        &self,
+        _domain: String,
+    ) -> Result<Response<entities::admin::EmailDomainBlock>, Error> {
+        no_implemented("create_admin_email_domain_block")
+    }
+
+    /// Lift a block against an email domain.
+    async fn delete_admin_email_domain_block(&self, _id: String) -> Result<Response<()>, Error> {
+        no_implemented("delete_admin_email_domain_block")
+    }
+
+    // ======================================
+    // admin/ip_blocks
+    // ======================================
+    /// Show information about all blocked IP ranges.
+    async fn get_admin_ip_blocks(
+        &self,
+        _options: Option<&GetAdminIpBlocksInputOptions>,
+    ) -> Result<Response<Vec<entities::admin::IpBlock>>, Error> {
+        no_implemented("get_admin_ip_blocks")
+    }
+
+    /// Show information about a single IP block.
+    async fn get_admin_ip_block(
+        &self,
+        _id: String,
+    ) -> Result<Response<entities::admin::IpBlock>, Error> {
+        no_implemented("get_admin_ip_block")
+    }
+
+    /// Add an IP address range to the list of IP blocks.
+    async fn create_admin_ip_block(
+        &self,
+        _ip: String,
+        _severity: entities::admin::IpBlockSeverity,
+        _options: Option<&AdminIpBlockInputOptions>,
+    ) -> Result<Response<entities::admin::IpBlock>, Error> {
+        no_implemented("create_admin_ip_block")
+    }
+
+    /// Change parameters for an existing IP block.
+    async fn update_admin_ip_block(
+        &self,
+        _id: String,
+        _options: &UpdateAdminIpBlockInputOptions,
+    ) -> Result<Response<entities::admin::IpBlock>, Error> {
+        no_implemented("update_admin_ip_block")
+    }
+
+    /// Lift a block against an IP range.
+    async fn delete_admin_ip_block(&self, _id: String) -> Result<Response<()>, Error> {
+        no_implemented("delete_admin_ip_block")
+    }
+
+    // ======================================
+    // admin/canonical_email_blocks
+    // ======================================
+    /// List all canonical email blocks.
+    async fn get_admin_canonical_email_blocks(
+        &self,
+        _options: Option<&GetAdminCanonicalEmailBlocksInputOptions>,
+    ) -> Result<Response<Vec<entities::admin::CanonicalEmailBlock>>, Error> {
+        no_implemented("get_admin_canonical_email_blocks")
+    }
+
+    /// Show a single canonical email block.
+    async fn get_admin_canonical_email_block(
+        &self,
+        _id: String,
+    ) -> Result<Response<entities::admin::CanonicalEmailBlock>, Error> {
+        no_implemented("get_admin_canonical_email_block")
+    }
+
+    /// Canonicalize and hash an email address, and return the blocks which match it.
+    async fn test_admin_canonical_email_block(
+        &self,
+        _email: String,
+    ) -> Result<Response<Vec<entities::admin::CanonicalEmailBlock>>, Error> {
+        no_implemented("test_admin_canonical_email_block")
+    }
+
+    /// Block a canonical email address.
+    async fn create_admin_canonical_email_block(
+        &self,
+        _email: String,
+    ) -> Result<Response<entities::admin::CanonicalEmailBlock>, Error> {
+        no_implemented("create_admin_canonical_email_block")
+    }
+
+    /// Lift a block against a canonical email.
+    async fn delete_admin_canonical_email_block(&self, _id: String) -> Result<Response<()>, Error> {
+        no_implemented("delete_admin_canonical_email_block")
+    }
+
+    // ======================================
+    // admin/measures, dimensions and retention
+    // ======================================
+    /// Obtain quantitative metrics about the server, like `active_users` or `new_users`.
+    async fn get_admin_measures(
+        &self,
+        _keys: Vec<String>,
+        _start_at: DateTime<Utc>,
+        _end_at: DateTime<Utc>,
+    ) -> Result<Response<Vec<entities::admin::Measure>>, Error> {
+        no_implemented("get_admin_measures")
+    }
+
+    /// Obtain qualitative metrics about the server, like `languages` or `servers`.
+    async fn get_admin_dimensions(
+        &self,
+        _keys: Vec<String>,
+        _start_at: DateTime<Utc>,
+        _end_at: DateTime<Utc>,
+        _limit: Option<u32>,
+    ) -> Result<Response<Vec<entities::admin::Dimension>>, Error> {
+        no_implemented("get_admin_dimensions")
+    }
+
+    /// Generate a retention data report for a given time period and bucket.
+    async fn get_admin_retention(
+        &self,
+        _start_at: DateTime<Utc>,
+        _end_at: DateTime<Utc>,
+        _frequency: entities::admin::CohortFrequency,
+    ) -> Result<Response<Vec<entities::admin::Cohort>>, Error> {
+        no_implemented("get_admin_retention")
+    }
+
     // ======================================
     // Streaming
     // ======================================
@@ -794,6 +1434,24 @@ pub trait Megalodon {
 
     /// Get list streaming object.
     async fn list_streaming(&self, list_id: String) -> Box<dyn Streaming + Send + Sync>;
+
+    /// Get streaming object which subscribes several streams on a single connection.
+    async fn multiplex_streaming(
+        &self,
+    ) -> Result<Box<dyn MultiplexStreaming + Send + Sync>, Error> {
+        no_implemented("multiplex_streaming")
+    }
+}
+
+/// Error of the methods which are not supported by the SNS.
+fn no_implemented<T>(method: &str) -> Result<T, Error> {
+    Err(Error::new_own(
+        format!("The SNS does not support {}", method),
+        Kind::NoImplementedError,
+        None,
+        None,
+        None,
+    ))
 }
 
 /// Input options for [`Megalodon::register_app`] and [`Megalodon::create_app`].
@@ -943,6 +1601,13 @@ pub struct GetArrayOptions {
     pub min_id: Option<String>,
 }
 
+impl CursorOptions for GetArrayOptions {
+    fn set_cursor(&mut self, cursor: &Cursor) {
+        self.max_id = cursor.max_id.clone();
+        self.min_id = cursor.min_id.clone().or(cursor.since_id.clone());
+    }
+}
+
 /// Get array options with since.
 #[derive(Debug, Clone, Default)]
 pub struct GetArrayWithSinceOptions {
@@ -956,6 +1621,14 @@ pub struct GetArrayWithSinceOptions {
     pub min_id: Option<String>,
 }
 
+impl CursorOptions for GetArrayWithSinceOptions {
+    fn set_cursor(&mut self, cursor: &Cursor) {
+        self.max_id = cursor.max_id.clone();
+        self.since_id = cursor.since_id.clone();
+        self.min_id = cursor.min_id.clone();
+    }
+}
+
 /// Input options for [`Megalodon::create_filter`] and [`Megalodon::update_filter`].
 #[derive(Debug, Clone, Default)]
 pub struct FilterInputOptions {
@@ -967,6 +1640,49 @@ pub struct FilterInputOptions {
     pub expires_in: Option<u64>,
 }
 
+/// Input options for [`Megalodon::create_filter_v2`].
+#[derive(Debug, Clone, Default)]
+pub struct CreateFilterV2InputOptions {
+    /// The action to be taken when a status matches the filter. Defaults to warn.
+    pub filter_action: Option<entities::FilterAction>,
+    /// Number of seconds from now the filter should expire.
+    pub expires_in: Option<u64>,
+    /// Keywords to be added to the filter.
+    pub keywords_attributes: Option<Vec<FilterKeywordAttributes>>,
+}
+
+/// Input options for [`Megalodon::update_filter_v2`].
+#[derive(Debug, Clone, Default)]
+pub struct UpdateFilterV2InputOptions {
+    /// The name of the filter group.
+    pub title: Option<String>,
+    /// Where the filter should be applied.
+    pub context: Option<Vec<entities::filter::FilterContext>>,
+    /// The action to be taken when a status matches the filter.
+    pub filter_action: Option<entities::FilterAction>,
+    /// Number of seconds from now the filter should expire.
+    pub expires_in: Option<u64>,
+    /// Keywords to be added, updated or removed.
+    pub keywords_attributes: Option<Vec<FilterKeywordAttributes>>,
+}
+
+/// Keyword which is changed with the filter group.
+#[derive(Debug, Serialize, Clone, Default)]
+pub struct FilterKeywordAttributes {
+    /// ID of the keyword to update or remove. Leave it empty to add a new keyword.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub id: Option<String>,
+    /// The keyword to be filtered.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub keyword: Option<String>,
+    /// Consider word boundaries.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub whole_word: Option<bool>,
+    /// Remove the keyword.
+    #[serde(rename = "_destroy", skip_serializing_if = "Option::is_none")]
+    pub destroy: Option<bool>,
+}
+
 /// Input options for [`Megalodon::report`].
 #[derive(Debug, Clone, Default)]
 pub struct ReportInputOptions {
@@ -1014,6 +1730,11 @@ pub struct PostStatusInputOptions {
     pub language: Option<String>,
     /// ID of the status being quoted to.
     pub quote_id: Option<String>,
+    /// Who can quote the status without approval.
+    pub quote_approval_policy: Option<entities::quote_approval::QuoteApprovalPolicy>,
+    /// Key to prevent the status from being posted twice when the request is retried.
+    /// A random key is generated for each call if it is not specified.
+    pub idempotency_key: Option<String>,
 }
 
 /// Input options for [`Megalodon::edit_status`].
@@ -1031,6 +1752,8 @@ pub struct EditStatusInputOptions {
     pub media_ids: Option<Vec<String>>,
     /// Poll options.
     pub poll: Option<PollOptions>,
+    /// Who can quote the status without approval.
+    pub quote_approval_policy: Option<entities::quote_approval::QuoteApprovalPolicy>,
 }
 
 /// Poll options.
@@ -1057,6 +1780,9 @@ pub struct GetStatusContextInputOptions {
     pub since_id: Option<String>,
 }
 
+/// Input options for [`Megalodon::get_status_quotes`].
+pub type GetStatusQuotesInputOptions = GetArrayWithSinceOptions;
+
 /// Input options for [`Megalodon::upload_media`].
 #[derive(Debug, Clone, Default)]
 pub struct UploadMediaInputOptions {
@@ -1111,6 +1837,14 @@ pub struct GetTimelineOptions {
     pub min_id: Option<String>,
 }
 
+impl CursorOptions for GetTimelineOptions {
+    fn set_cursor(&mut self, cursor: &Cursor) {
+        self.max_id = cursor.max_id.clone();
+        self.since_id = cursor.since_id.clone();
+        self.min_id = cursor.min_id.clone();
+    }
+}
+
 /// Timeline options with local flag.
 #[derive(Debug, Clone, Default)]
 pub struct GetTimelineOptionsWithLocal {
@@ -1128,6 +1862,14 @@ pub struct GetTimelineOptionsWithLocal {
     pub local: Option<bool>,
 }
 
+impl CursorOptions for GetTimelineOptionsWithLocal {
+    fn set_cursor(&mut self, cursor: &Cursor) {
+        self.max_id = cursor.max_id.clone();
+        self.since_id = cursor.since_id.clone();
+        self.min_id = cursor.min_id.clone();
+    }
+}
+
 /// Input options for [`Megalodon::save_markers`].
 #[derive(Debug, Clone, Default)]
 pub struct SaveMarkersInputOptions {
@@ -1161,6 +1903,98 @@ pub struct GetNotificationsInputOptions {
     pub account_id: Option<String>,
 }
 
+impl CursorOptions for GetNotificationsInputOptions {
+    fn set_cursor(&mut self, cursor: &Cursor) {
+        self.max_id = cursor.max_id.clone();
+        self.since_id = cursor.since_id.clone();
+        self.min_id = cursor.min_id.clone();
+    }
+}
+
+/// Input options for [`Megalodon::get_grouped_notifications`].
+#[derive(Debug, Clone, Default)]
+pub struct GetGroupedNotificationsInputOptions {
+    /// Maximum number of results to return. Default 40.
+    pub limit: Option<u32>,
+    /// Return results older than this ID.
+    pub max_id: Option<String>,
+    /// Return results newer than this ID.
+    pub since_id: Option<String>,
+    /// Return results immediately newer than this ID.
+    pub min_id: Option<String>,
+    /// Types to include in the result.
+    pub types: Option<Vec<entities::notification::NotificationType>>,
+    /// Types to exclude from the result.
+    pub exclude_types: Option<Vec<entities::notification::NotificationType>>,
+    /// Return only notifications received from this account.
+    pub account_id: Option<String>,
+    /// Whether to return full accounts or partial accounts with avatars.
+    pub expand_accounts: Option<ExpandAccounts>,
+    /// Types which are allowed to be grouped. Default favourite, follow and reblog.
+    pub grouped_types: Option<Vec<entities::notification::NotificationType>>,
+    /// Whether to include notifications filtered by the notification policy.
+    pub include_filtered: Option<bool>,
+}
+
+impl CursorOptions for GetGroupedNotificationsInputOptions {
+    fn set_cursor(&mut self, cursor: &Cursor) {
+        self.max_id = cursor.max_id.clone();
+        self.since_id = cursor.since_id.clone();
+        self.min_id = cursor.min_id.clone();
+    }
+}
+
+/// How accounts are returned in grouped notifications.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ExpandAccounts {
+    /// Full accounts in `accounts`.
+    Full,
+    /// Only the most recent accounts in `accounts`, and the rest in `partial_accounts`.
+    PartialAvatars,
+}
+
+impl fmt::Display for ExpandAccounts {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ExpandAccounts::Full => write!(f, "full"),
+            ExpandAccounts::PartialAvatars => write!(f, "partial_avatars"),
+        }
+    }
+}
+
+/// Input options for [`Megalodon::get_unread_notification_count`].
+#[derive(Debug, Clone, Default)]
+pub struct GetUnreadNotificationCountInputOptions {
+    /// Maximum number of results to count. Default 100.
+    pub limit: Option<u32>,
+    /// Types to include in the count.
+    pub types: Option<Vec<entities::notification::NotificationType>>,
+    /// Types to exclude from the count.
+    pub exclude_types: Option<Vec<entities::notification::NotificationType>>,
+    /// Count only notifications received from this account.
+    pub account_id: Option<String>,
+    /// Types which are counted as groups.
+    pub grouped_types: Option<Vec<entities::notification::NotificationType>>,
+}
+
+/// Input options for [`Megalodon::update_notification_policy`].
+#[derive(Debug, Clone, Default)]
+pub struct UpdateNotificationPolicyInputOptions {
+    /// Policy for notifications from accounts the user is not following.
+    pub for_not_following: Option<entities::notification_policy::NotificationFilteringPolicy>,
+    /// Policy for notifications from accounts which are not following the user.
+    pub for_not_followers: Option<entities::notification_policy::NotificationFilteringPolicy>,
+    /// Policy for notifications from accounts created in the past 30 days.
+    pub for_new_accounts: Option<entities::notification_policy::NotificationFilteringPolicy>,
+    /// Policy for private mentions which are not replies to the user.
+    pub for_private_mentions: Option<entities::notification_policy::NotificationFilteringPolicy>,
+    /// Policy for notifications from accounts limited by moderators.
+    pub for_limited_accounts: Option<entities::notification_policy::NotificationFilteringPolicy>,
+}
+
+/// Input options for [`Megalodon::get_notification_requests`].
+pub type GetNotificationRequestsInputOptions = GetArrayWithSinceOptions;
+
 /// Input options for [`Megalodon::read_notifications`].
 #[derive(Debug, Clone, Default)]
 pub struct ReadNotificationsInputOptions {
@@ -1273,6 +2107,21 @@ pub struct SearchInputOptions {
     pub exclude_unreviewed: Option<bool>,
 }
 
+/// Input options for [`Megalodon::get_trending_statuses`].
+pub type GetTrendingStatusesInputOptions = GetTrendsInputOptions;
+
+/// Input options for [`Megalodon::get_trending_links`].
+pub type GetTrendingLinksInputOptions = GetTrendsInputOptions;
+
+/// Get trends options.
+#[derive(Debug, Clone, Default)]
+pub struct GetTrendsInputOptions {
+    /// Maximum number of results to return.
+    pub limit: Option<u32>,
+    /// Skip the first n results.
+    pub offset: Option<u64>,
+}
+
 /// Input options for [`Megalodon::get_instance_directory`].
 #[derive(Debug, Clone, Default)]
 pub struct GetInstanceDirectoryInputOptions {
@@ -1322,6 +2171,168 @@ impl FromStr for Order {
     }
 }
 
+/// Input options for [`Megalodon::get_admin_accounts`].
+#[derive(Debug, Clone, Default)]
+pub struct GetAdminAccountsInputOptions {
+    /// Filter for `local` or `remote` accounts.
+    pub origin: Option<String>,
+    /// Filter for `active`, `pending`, `disabled`, `silenced` or `suspended` accounts.
+    pub status: Option<String>,
+    /// Filter for accounts with `staff` permissions.
+    pub permissions: Option<String>,
+    /// Filter for accounts with these roles.
+    pub role_ids: Option<Vec<String>>,
+    /// Lookup users invited by the account with this ID.
+    pub invited_by: Option<String>,
+    /// Search for the given username.
+    pub username: Option<String>,
+    /// Search for the given display name.
+    pub display_name: Option<String>,
+    /// Filter by the given domain.
+    pub by_domain: Option<String>,
+    /// Lookup a user with this email.
+    pub email: Option<String>,
+    /// Lookup users with this IP address.
+    pub ip: Option<String>,
+    /// Maximum number of results to return. Default 100.
+    pub limit: Option<u32>,
+    /// Return results older than this ID.
+    pub max_id: Option<String>,
+    /// Return results newer than this ID.
+    pub since_id: Option<String>,
+    /// Return results immediately newer than this ID.
+    pub min_id: Option<String>,
+}
+
+impl CursorOptions for GetAdminAccountsInputOptions {
+    fn set_cursor(&mut self, cursor: &Cursor) {
+        self.max_id = cursor.max_id.clone();
+        self.since_id = cursor.since_id.clone();
+        self.min_id = cursor.min_id.clone();
+    }
+}
+
+/// Action for [`Megalodon::perform_admin_account_action`].
+#[derive(Debug, Clone, PartialEq)]
+pub enum AdminAccountAction {
+    /// Only send a warning.
+    None,
+    /// Mark all statuses of the account as sensitive.
+    Sensitive,
+    /// Disable the login of the account.
+    Disable,
+    /// Limit the visibility of the account.
+    Silence,
+    /// Suspend the account.
+    Suspend,
+}
+
+impl fmt::Display for AdminAccountAction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AdminAccountAction::None => write!(f, "none"),
+            AdminAccountAction::Sensitive => write!(f, "sensitive"),
+            AdminAccountAction::Disable => write!(f, "disable"),
+            AdminAccountAction::Silence => write!(f, "silence"),
+            AdminAccountAction::Suspend => write!(f, "suspend"),
+        }
+    }
+}
+
+/// Input options for [`Megalodon::perform_admin_account_action`].
+#[derive(Debug, Clone, Default)]
+pub struct AdminAccountActionInputOptions {
+    /// The ID of an associated report that caused this action to be taken.
+    pub report_id: Option<String>,
+    /// The ID of a preset warning.
+    pub warning_preset_id: Option<String>,
+    /// Additional clarification for why this action was taken.
+    pub text: Option<String>,
+    /// Whether an email should be sent to the user with the above information.
+    pub send_email_notification: Option<bool>,
+}
+
+/// Input options for [`Megalodon::get_admin_reports`].
+#[derive(Debug, Clone, Default)]
+pub struct GetAdminReportsInputOptions {
+    /// Filter for resolved reports.
+    pub resolved: Option<bool>,
+    /// Filter for reports filed by this account.
+    pub account_id: Option<String>,
+    /// Filter for reports against this account.
+    pub target_account_id: Option<String>,
+    /// Maximum number of results to return. Default 100.
+    pub limit: Option<u32>,
+    /// Return results older than this ID.
+    pub max_id: Option<String>,
+    /// Return results newer than this ID.
+    pub since_id: Option<String>,
+    /// Return results immediately newer than this ID.
+    pub min_id: Option<String>,
+}
+
+impl CursorOptions for GetAdminReportsInputOptions {
+    fn set_cursor(&mut self, cursor: &Cursor) {
+        self.max_id = cursor.max_id.clone();
+        self.since_id = cursor.since_id.clone();
+        self.min_id = cursor.min_id.clone();
+    }
+}
+
+/// Input options for [`Megalodon::get_admin_domain_blocks`].
+pub type GetAdminDomainBlocksInputOptions = GetArrayWithSinceOptions;
+
+/// Input options for [`Megalodon::create_admin_domain_block`] and [`Megalodon::update_admin_domain_block`].
+#[derive(Debug, Clone, Default)]
+pub struct AdminDomainBlockInputOptions {
+    /// Whether to apply a silence, suspend or noop to the domain. Default silence.
+    pub severity: Option<entities::admin::DomainBlockSeverity>,
+    /// Whether media attachments should be rejected.
+    pub reject_media: Option<bool>,
+    /// Whether reports from this domain should be rejected.
+    pub reject_reports: Option<bool>,
+    /// A private note about this domain block, visible only to admins.
+    pub private_comment: Option<String>,
+    /// A public note about this domain block, optionally shown on the about page.
+    pub public_comment: Option<String>,
+    /// Whether to partially censor the domain when shown in public.
+    pub obfuscate: Option<bool>,
+}
+
+/// Input options for [`Megalodon::get_admin_domain_allows`].
+pub type GetAdminDomainAllowsInputOptions = GetArrayWithSinceOptions;
+
+/// Input options for [`Megalodon::get_admin_email_domain_blocks`].
+pub type GetAdminEmailDomainBlocksInputOptions = GetArrayWithSinceOptions;
+
+/// Input options for [`Megalodon::get_admin_ip_blocks`].
+pub type GetAdminIpBlocksInputOptions = GetArrayWithSinceOptions;
+
+/// Input options for [`Megalodon::create_admin_ip_block`].
+#[derive(Debug, Clone, Default)]
+pub struct AdminIpBlockInputOptions {
+    /// The reason for this IP block.
+    pub comment: Option<String>,
+    /// The number of seconds in which this IP block will expire.
+    pub expires_in: Option<u64>,
+}
+
+/// Input options for [`Megalodon::update_admin_ip_block`].
+#[derive(Debug, Clone, Default)]
+pub struct UpdateAdminIpBlockInputOptions {
+    /// The IP address and prefix to block.
+    pub ip: Option<String>,
+    /// The policy to apply to this IP range.
+    pub severity: Option<entities::admin::IpBlockSeverity>,
+    /// The reason for this IP block.
+    pub comment: Option<String>,
+    /// The number of seconds in which this IP block will expire.
+    pub expires_in: Option<u64>,
+}
+
+/// Input options for [`Megalodon::get_admin_canonical_email_blocks`].
+pub type GetAdminCanonicalEmailBlocksInputOptions = GetArrayWithSinceOptions;
+
 /// FollowRequest output object. It is FollowRequest object only if Friendica, otherwise it is Account object.
 #[derive(Debug, Clone)]
 pub enum FollowRequestOutput {
@@ -0,0 +1,291 @@
+//! Pagination modules
+use crate::error::Error;
+use crate::response::{Cursor, Response};
+use futures_util::stream::{self, Stream};
+use std::collections::VecDeque;
+use std::future::Future;
+
+/// Direction to follow pages.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub enum Direction {
+    /// Follow `next` links, which return older results.
+    #[default]
+    Next,
+    /// Follow `prev` links, which return newer results.
+    Prev,
+}
+
+/// Cursor parameters which are passed to the fetch function for each page.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct PageParams {
+    /// Maximum number of results to return.
+    pub limit: Option<u32>,
+    /// Return results older than this ID.
+    pub max_id: Option<String>,
+    /// Return results immediately newer than this ID.
+    pub min_id: Option<String>,
+    /// Return results newer than this ID.
+    pub since_id: Option<String>,
+}
+
+/// Options for [`paginate`].
+#[derive(Debug, Clone, Default)]
+pub struct PaginateOptions {
+    /// Direction to follow pages.
+    pub direction: Direction,
+    /// Page size, which is passed to the fetch function as `limit`.
+    pub limit: Option<u32>,
+    /// Stop after this number of items have been yielded.
+    pub max_items: Option<usize>,
+    /// Start from results older than this ID.
+    pub max_id: Option<String>,
+    /// Start from results immediately newer than this ID.
+    pub min_id: Option<String>,
+}
+
+/// Turn a list method into a stream which follows pages.
+///
+/// Pages are followed by [`Response::pagination`], which is filled by each SNS.
+///
+/// ```rust
+/// # use megalodon;
+/// # use megalodon::error::Error;
+/// # use megalodon::megalodon::GetHomeTimelineInputOptions;
+/// # use megalodon::pagination::{paginate, PaginateOptions};
+/// # use futures_util::{pin_mut, StreamExt};
+/// #
+/// # async fn run() -> Result<(), Error> {
+/// let client = megalodon::generator(
+///   megalodon::SNS::Mastodon,
+///   String::from("https://fedibird.com"),
+///   Some(String::from("your access token")),
+///   None,
+/// )?;
+/// let options = PaginateOptions {
+///   limit: Some(40),
+///   max_items: Some(200),
+///   ..Default::default()
+/// };
+/// let statuses = paginate(options, |page| {
+///   let client = &client;
+///   async move {
+///     let options = GetHomeTimelineInputOptions {
+///       limit: page.limit,
+///       max_id: page.max_id,
+///       min_id: page.min_id,
+///       since_id: page.since_id,
+///       ..Default::default()
+///     };
+///     client.get_home_timeline(Some(&options)).await
+///   }
+/// });
+/// pin_mut!(statuses);
+/// while let Some(status) = statuses.next().await {
+///   println!("{:#?}", status?);
+/// }
+/// # Ok(())
+/// # }
+/// ```
+pub fn paginate<T, F, Fut>(
+    options: PaginateOptions,
+    fetch: F,
+) -> impl Stream<Item = Result<T, Error>>
+where
+    F: FnMut(PageParams) -> Fut,
+    Fut: Future<Output = Result<Response<Vec<T>>, Error>>,
+{
+    let first = PageParams {
+        limit: options.limit,
+        max_id: options.max_id.clone(),
+        min_id: options.min_id.clone(),
+        since_id: None,
+    };
+    let state = State {
+        options,
+        fetch,
+        next: Some(first),
+        buffer: VecDeque::new(),
+        yielded: 0,
+    };
+
+    stream::unfold(state, |mut state| async move {
+        loop {
+            if let Some(max_items) = state.options.max_items {
+                if state.yielded >= max_items {
+                    return None;
+                }
+            }
+            if let Some(item) = state.buffer.pop_front() {
+                state.yielded += 1;
+                return Some((Ok(item), state));
+            }
+            let params = state.next.take()?;
+            match (state.fetch)(params.clone()).await {
+                Ok(res) => {
+                    // An empty page can still have a cursor, when the SNS filters the results.
+                    // Stop when the cursor does not move, otherwise the same page is fetched forever.
+                    state.next = state.next_params(&res).filter(|next| *next != params);
+                    state.buffer.extend(res.json);
+                }
+                Err(err) => return Some((Err(err), state)),
+            }
+        }
+    })
+}
+
+struct State<F, T> {
+    options: PaginateOptions,
+    fetch: F,
+    next: Option<PageParams>,
+    buffer: VecDeque<T>,
+    yielded: usize,
+}
+
+impl<F, T> State<F, T> {
+    fn next_params(&self, res: &Response<Vec<T>>) -> Option<PageParams> {
+        let cursor: &Cursor = match self.options.direction {
+            Direction::Next => res.pagination.next.as_ref(),
+            Direction::Prev => res.pagination.prev.as_ref(),
+        }?;
+        if cursor.is_empty() {
+            return None;
+        }
+        Some(PageParams {
+            limit: self.options.limit,
+            max_id: cursor.max_id.clone(),
+            min_id: cursor.min_id.clone(),
+            since_id: cursor.since_id.clone(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::response::Pagination;
+    use futures_util::StreamExt;
+    use reqwest::header::{HeaderMap, HeaderValue, LINK};
+
+    fn response(ids: Vec<&str>, link: Option<&str>) -> Response<Vec<String>> {
+        let mut header = HeaderMap::new();
+        if let Some(link) = link {
+            header.insert(LINK, HeaderValue::from_str(link).unwrap());
+        }
+        Response::new(
+            ids.into_iter().map(String::from).collect(),
+            200,
+            "200".to_string(),
+            header,
+        )
+    }
+
+    #[tokio::test]
+    async fn test_paginate_link_header() {
+        let pages = vec![
+            response(
+                vec!["3", "2"],
+                Some("<https://mastodon.example/api/v1/bookmarks?max_id=2>; rel=\"next\""),
+            ),
+            response(vec!["1"], None),
+        ];
+        let mut pages = pages.into_iter();
+        let mut requested = Vec::new();
+        let stream = paginate(PaginateOptions::default(), |page| {
+            requested.push(page.max_id.clone());
+            let res = pages.next().unwrap();
+            async move { Ok(res) }
+        });
+        let items: Vec<String> = stream.map(|i| i.unwrap()).collect().await;
+
+        assert_eq!(items, vec!["3", "2", "1"]);
+        assert_eq!(requested, vec![None, Some("2".to_string())]);
+    }
+
+    #[tokio::test]
+    async fn test_paginate_max_items() {
+        let options = PaginateOptions {
+            max_items: Some(3),
+            ..Default::default()
+        };
+        let stream = paginate(options, |_page| async {
+            Ok(response(
+                vec!["2", "1"],
+                Some("<https://mastodon.example/api/v1/bookmarks?max_id=1>; rel=\"next\""),
+            ))
+        });
+        let items: Vec<String> = stream.map(|i| i.unwrap()).collect().await;
+
+        assert_eq!(items.len(), 3);
+    }
+
+    #[tokio::test]
+    async fn test_paginate_ids() {
+        let mut pages = vec![vec!["9hzxa0001", "9hzx90001"], vec!["9hzx80001"], vec![]].into_iter();
+        let mut requested = Vec::new();
+        let stream = paginate(PaginateOptions::default(), |page| {
+            requested.push(page.max_id.clone());
+            let ids = pages.next().unwrap();
+            let res =
+                response(ids.clone(), None).with_pagination(Pagination::from_ids(ids.into_iter()));
+            async move { Ok(res) }
+        });
+        let items: Vec<String> = stream.map(|i| i.unwrap()).collect().await;
+
+        assert_eq!(items, vec!["9hzxa0001", "9hzx90001", "9hzx80001"]);
+        assert_eq!(
+            requested,
+            vec![
+                None,
+                Some("9hzx90001".to_string()),
+                Some("9hzx80001".to_string())
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_paginate_stops_on_same_cursor() {
+        let mut count = 0;
+        let stream = paginate(PaginateOptions::default(), |_page| {
+            count += 1;
+            async {
+                Ok(response(
+                    vec!["2", "1"],
+                    Some("<https://mastodon.example/api/v1/bookmarks?max_id=1>; rel=\"next\""),
+                ))
+            }
+        });
+        let items: Vec<String> = stream.map(|i| i.unwrap()).collect().await;
+
+        assert_eq!(items, vec!["2", "1", "2", "1"]);
+        assert_eq!(count, 2);
+    }
+
+    #[tokio::test]
+    async fn test_paginate_ids_prev() {
+        // Like Firefish users/notes, the server reads only sinceId and returns the newest notes without it.
+        let notes = ["9hzx80001", "9hzx90001", "9hzxa0001"];
+        let mut requested = Vec::new();
+        let options = PaginateOptions {
+            direction: Direction::Prev,
+            ..Default::default()
+        };
+        let stream = paginate(options, |page| {
+            requested.push(page.since_id.clone());
+            let ids: Vec<&str> = match &page.since_id {
+                Some(since_id) => notes
+                    .iter()
+                    .copied()
+                    .filter(|id| *id > since_id.as_str())
+                    .collect(),
+                None => notes.iter().rev().take(2).copied().collect(),
+            };
+            let res =
+                response(ids.clone(), None).with_pagination(Pagination::from_ids(ids.into_iter()));
+            async move { Ok(res) }
+        });
+        let items: Vec<String> = stream.map(|i| i.unwrap()).collect().await;
+
+        assert_eq!(items, vec!["9hzxa0001", "9hzx90001"]);
+        assert_eq!(requested, vec![None, Some("9hzxa0001".to_string())]);
+    }
+}
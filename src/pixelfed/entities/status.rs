@@ -162,6 +162,7 @@ impl From<Status> for MegalodonEntities::Status {
             quote: None,
             quote_approval: MegalodonEntities::QuoteApproval::default(),
             bookmarked: val.bookmarked,
+            filtered: Vec::new(),
         }
     }
 }
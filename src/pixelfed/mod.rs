@@ -5,6 +5,5 @@ mod entities;
 mod oauth;
 /// Pixelfed API client.
 pub mod pixelfed;
-mod web_socket;
 
 pub use pixelfed::Pixelfed;
@@ -3,8 +3,11 @@ use std::collections::HashMap;
 use super::api_client::APIClient;
 use super::entities;
 use super::oauth;
-use super::web_socket::WebSocket;
+use crate::SNS;
+use crate::capabilities::Capabilities;
+use crate::http::{ClientBuilder, idempotency_header};
 use crate::megalodon::FollowRequestOutput;
+use crate::polling::{Polling, Source};
 use crate::{Streaming, error};
 use crate::{
     default, entities as MegalodonEntities, error::Error, megalodon, oauth as MegalodonOAuth,
@@ -21,6 +24,7 @@ use oauth2::{
 use serde_json::Value;
 use sha1::{Digest, Sha1};
 use std::ops::Sub;
+use std::sync::Arc;
 use tokio::{fs::File, io::AsyncRead};
 use tokio_util::codec::{BytesCodec, FramedRead};
 
@@ -38,7 +42,22 @@ impl Pixelfed {
         access_token: Option<String>,
         user_agent: Option<String>,
     ) -> Result<Pixelfed, Error> {
-        let client = APIClient::new(base_url.clone(), access_token.clone(), user_agent.clone())?;
+        Self::new_with_client(base_url, access_token, user_agent, ClientBuilder::default())
+    }
+
+    /// Create a new [`Pixelfed`] with the HTTP client settings.
+    pub fn new_with_client(
+        base_url: String,
+        access_token: Option<String>,
+        user_agent: Option<String>,
+        client: ClientBuilder,
+    ) -> Result<Pixelfed, Error> {
+        let client = APIClient::new(
+            base_url.clone(),
+            access_token.clone(),
+            user_agent.clone(),
+            &client,
+        )?;
         Ok(Self { client, base_url })
     }
 
@@ -1444,10 +1463,12 @@ impl megalodon::Megalodon for Pixelfed {
             }
         }
 
+        let headers = idempotency_header(options.and_then(|o| o.idempotency_key.as_deref()))?;
+
         if is_scheduled {
             let res = self
                 .client
-                .post::<entities::ScheduledStatus>("/api/v1/statuses", &params, None)
+                .post::<entities::ScheduledStatus>("/api/v1/statuses", &params, Some(headers))
                 .await?;
 
             Ok(Response::<megalodon::PostStatusOutput>::new(
@@ -1459,7 +1480,7 @@ impl megalodon::Megalodon for Pixelfed {
         } else {
             let res = self
                 .client
-                .post::<entities::Status>("/api/v1/statuses", &params, None)
+                .post::<entities::Status>("/api/v1/statuses", &params, Some(headers))
                 .await?;
 
             Ok(Response::<megalodon::PostStatusOutput>::new(
@@ -1551,6 +1572,7 @@ impl megalodon::Megalodon for Pixelfed {
             status,
             status_text,
             header,
+            ..
         } = self
             .client
             .delete::<Value>(format!("/api/v1/statuses/{}", id).as_str(), &params, None)
@@ -2681,6 +2703,21 @@ impl megalodon::Megalodon for Pixelfed {
         ))
     }
 
+    async fn get_instance_v2(&self) -> Result<Response<MegalodonEntities::InstanceV2>, Error> {
+        let res = self.get_instance().await?;
+        Ok(Response::<MegalodonEntities::InstanceV2>::new(
+            res.json.into(),
+            res.status,
+            res.status_text,
+            res.header,
+        ))
+    }
+
+    async fn capabilities(&self) -> Result<Capabilities, Error> {
+        let instance = self.get_instance_v2().await;
+        Ok(Capabilities::detect(SNS::Pixelfed, instance))
+    }
+
     async fn get_instance_peers(&self) -> Result<Response<Vec<String>>, Error> {
         let res = self
             .client
@@ -2904,37 +2941,37 @@ impl megalodon::Megalodon for Pixelfed {
     }
 
     async fn user_streaming(&self) -> Box<dyn Streaming + Send + Sync> {
-        let c = WebSocket::new();
+        let c = Polling::new(Arc::new(self.clone()), Source::User);
 
         Box::new(c)
     }
 
     async fn public_streaming(&self) -> Box<dyn Streaming + Send + Sync> {
-        let c = WebSocket::new();
+        let c = Polling::new(Arc::new(self.clone()), Source::Public);
 
         Box::new(c)
     }
 
     async fn local_streaming(&self) -> Box<dyn Streaming + Send + Sync> {
-        let c = WebSocket::new();
+        let c = Polling::new(Arc::new(self.clone()), Source::Local);
 
         Box::new(c)
     }
 
     async fn direct_streaming(&self) -> Box<dyn Streaming + Send + Sync> {
-        let c = WebSocket::new();
+        let c = Polling::new(Arc::new(self.clone()), Source::Direct);
 
         Box::new(c)
     }
 
-    async fn tag_streaming(&self, _tag: String) -> Box<dyn Streaming + Send + Sync> {
-        let c = WebSocket::new();
+    async fn tag_streaming(&self, tag: String) -> Box<dyn Streaming + Send + Sync> {
+        let c = Polling::new(Arc::new(self.clone()), Source::Tag(tag));
 
         Box::new(c)
     }
 
-    async fn list_streaming(&self, _list_id: String) -> Box<dyn Streaming + Send + Sync> {
-        let c = WebSocket::new();
+    async fn list_streaming(&self, list_id: String) -> Box<dyn Streaming + Send + Sync> {
+        let c = Polling::new(Arc::new(self.clone()), Source::List(list_id));
 
         Box::new(c)
     }
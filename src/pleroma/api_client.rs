@@ -1,5 +1,5 @@
-use crate::default::DEFAULT_UA;
-use crate::error::{Error as MegalodonError, Kind};
+use crate::error::Error as MegalodonError;
+use crate::http::{ClientBuilder, HttpClient};
 use crate::response::Response;
 use reqwest::header::HeaderMap;
 use reqwest::Url;
@@ -12,7 +12,7 @@ use std::fmt::Debug;
 pub struct APIClient {
     access_token: Option<String>,
     base_url: String,
-    client: reqwest::Client,
+    client: HttpClient,
 }
 
 impl APIClient {
@@ -20,14 +20,9 @@ impl APIClient {
         base_url: String,
         access_token: Option<String>,
         user_agent: Option<String>,
+        client: &ClientBuilder,
     ) -> Result<Self, MegalodonError> {
-        let ua: String;
-        match user_agent {
-            Some(agent) => ua = agent,
-            None => ua = DEFAULT_UA.to_string(),
-        }
-
-        let client = reqwest::Client::builder().user_agent(ua).build()?;
+        let client = client.build(user_agent)?;
 
         Ok(Self {
             access_token,
@@ -36,6 +31,11 @@ impl APIClient {
         })
     }
 
+    /// HTTP client which is shared with streaming connections.
+    pub(crate) fn http_client(&self) -> HttpClient {
+        self.client.clone()
+    }
+
     pub async fn get<T>(
         &self,
         path: &str,
@@ -55,7 +55,7 @@ impl APIClient {
             req = req.headers(headers);
         }
 
-        let res = req.send().await?;
+        let res = self.client.send(req).await?;
         let res_headers = res.headers().clone();
         let status = res.status();
         match status {
@@ -67,19 +67,17 @@ impl APIClient {
                 Ok(res)
             }
             _ => match res.text().await {
-                Ok(text) => Err(MegalodonError::new_own(
+                Ok(text) => Err(MegalodonError::from_response(
                     text,
-                    Kind::HTTPStatusError,
-                    Some(url_str),
-                    Some(status.as_u16()),
-                    Some(res_headers),
+                    url_str,
+                    status.as_u16(),
+                    res_headers,
                 )),
-                Err(_err) => Err(MegalodonError::new_own(
+                Err(_err) => Err(MegalodonError::from_response(
                     "Unknown error".to_string(),
-                    Kind::HTTPStatusError,
-                    Some(url_str),
-                    Some(status.as_u16()),
-                    Some(res_headers),
+                    url_str,
+                    status.as_u16(),
+                    res_headers,
                 )),
             },
         }
@@ -105,7 +103,7 @@ impl APIClient {
             req = req.headers(headers);
         }
 
-        let res = req.json(params).send().await?;
+        let res = self.client.send(req.json(params)).await?;
         let res_headers = res.headers().clone();
         let status = res.status();
         match status {
@@ -117,19 +115,17 @@ impl APIClient {
                 Ok(res)
             }
             _ => match res.text().await {
-                Ok(text) => Err(MegalodonError::new_own(
+                Ok(text) => Err(MegalodonError::from_response(
                     text,
-                    Kind::HTTPStatusError,
-                    Some(url_str),
-                    Some(status.as_u16()),
-                    Some(res_headers),
+                    url_str,
+                    status.as_u16(),
+                    res_headers,
                 )),
-                Err(_err) => Err(MegalodonError::new_own(
+                Err(_err) => Err(MegalodonError::from_response(
                     "Unknown error".to_string(),
-                    Kind::HTTPStatusError,
-                    Some(url_str),
-                    Some(status.as_u16()),
-                    Some(res_headers),
+                    url_str,
+                    status.as_u16(),
+                    res_headers,
                 )),
             },
         }
@@ -155,7 +151,7 @@ impl APIClient {
             req = req.headers(headers);
         }
 
-        let res = req.multipart(params).send().await?;
+        let res = self.client.send(req.multipart(params)).await?;
         let res_headers = res.headers().clone();
         let status = res.status();
         match status {
@@ -167,19 +163,17 @@ impl APIClient {
                 Ok(res)
             }
             _ => match res.text().await {
-                Ok(text) => Err(MegalodonError::new_own(
+                Ok(text) => Err(MegalodonError::from_response(
                     text,
-                    Kind::HTTPStatusError,
-                    Some(url_str),
-                    Some(status.as_u16()),
-                    Some(res_headers),
+                    url_str,
+                    status.as_u16(),
+                    res_headers,
                 )),
-                Err(_err) => Err(MegalodonError::new_own(
+                Err(_err) => Err(MegalodonError::from_response(
                     "Unknown error".to_string(),
-                    Kind::HTTPStatusError,
-                    Some(url_str),
-                    Some(status.as_u16()),
-                    Some(res_headers),
+                    url_str,
+                    status.as_u16(),
+                    res_headers,
                 )),
             },
         }
@@ -205,7 +199,7 @@ impl APIClient {
             req = req.headers(headers);
         }
 
-        let res = req.json(params).send().await?;
+        let res = self.client.send(req.json(params)).await?;
         let res_headers = res.headers().clone();
         let status = res.status();
         match status {
@@ -217,19 +211,17 @@ impl APIClient {
                 Ok(res)
             }
             _ => match res.text().await {
-                Ok(text) => Err(MegalodonError::new_own(
+                Ok(text) => Err(MegalodonError::from_response(
                     text,
-                    Kind::HTTPStatusError,
-                    Some(url_str),
-                    Some(status.as_u16()),
-                    Some(res_headers),
+                    url_str,
+                    status.as_u16(),
+                    res_headers,
                 )),
-                Err(_err) => Err(MegalodonError::new_own(
+                Err(_err) => Err(MegalodonError::from_response(
                     "Unknown error".to_string(),
-                    Kind::HTTPStatusError,
-                    Some(url_str),
-                    Some(status.as_u16()),
-                    Some(res_headers),
+                    url_str,
+                    status.as_u16(),
+                    res_headers,
                 )),
             },
         }
@@ -255,7 +247,7 @@ impl APIClient {
             req = req.headers(headers);
         }
 
-        let res = req.multipart(params).send().await?;
+        let res = self.client.send(req.multipart(params)).await?;
         let res_headers = res.headers().clone();
         let status = res.status();
         match status {
@@ -267,19 +259,17 @@ impl APIClient {
                 Ok(res)
             }
             _ => match res.text().await {
-                Ok(text) => Err(MegalodonError::new_own(
+                Ok(text) => Err(MegalodonError::from_response(
                     text,
-                    Kind::HTTPStatusError,
-                    Some(url_str),
-                    Some(status.as_u16()),
-                    Some(res_headers),
+                    url_str,
+                    status.as_u16(),
+                    res_headers,
                 )),
-                Err(_err) => Err(MegalodonError::new_own(
+                Err(_err) => Err(MegalodonError::from_response(
                     "Unknown error".to_string(),
-                    Kind::HTTPStatusError,
-                    Some(url_str),
-                    Some(status.as_u16()),
-                    Some(res_headers),
+                    url_str,
+                    status.as_u16(),
+                    res_headers,
                 )),
             },
         }
@@ -305,7 +295,7 @@ impl APIClient {
             req = req.headers(headers);
         }
 
-        let res = req.json(params).send().await?;
+        let res = self.client.send(req.json(params)).await?;
         let res_headers = res.headers().clone();
         let status = res.status();
         match status {
@@ -317,19 +307,17 @@ impl APIClient {
                 Ok(res)
             }
             _ => match res.text().await {
-                Ok(text) => Err(MegalodonError::new_own(
+                Ok(text) => Err(MegalodonError::from_response(
                     text,
-                    Kind::HTTPStatusError,
-                    Some(url_str),
-                    Some(status.as_u16()),
-                    Some(res_headers),
+                    url_str,
+                    status.as_u16(),
+                    res_headers,
                 )),
-                Err(_err) => Err(MegalodonError::new_own(
+                Err(_err) => Err(MegalodonError::from_response(
                     "Unknown error".to_string(),
-                    Kind::HTTPStatusError,
-                    Some(url_str),
-                    Some(status.as_u16()),
-                    Some(res_headers),
+                    url_str,
+                    status.as_u16(),
+                    res_headers,
                 )),
             },
         }
@@ -355,7 +343,7 @@ impl APIClient {
             req = req.headers(headers);
         }
 
-        let res = req.json(params).send().await?;
+        let res = self.client.send(req.json(params)).await?;
         let res_headers = res.headers().clone();
         let status = res.status();
         match status {
@@ -367,19 +355,17 @@ impl APIClient {
                 Ok(res)
             }
             _ => match res.text().await {
-                Ok(text) => Err(MegalodonError::new_own(
+                Ok(text) => Err(MegalodonError::from_response(
                     text,
-                    Kind::HTTPStatusError,
-                    Some(url_str),
-                    Some(status.as_u16()),
-                    Some(res_headers),
+                    url_str,
+                    status.as_u16(),
+                    res_headers,
                 )),
-                Err(_err) => Err(MegalodonError::new_own(
+                Err(_err) => Err(MegalodonError::from_response(
                     "Unknown error".to_string(),
-                    Kind::HTTPStatusError,
-                    Some(url_str),
-                    Some(status.as_u16()),
-                    Some(res_headers),
+                    url_str,
+                    status.as_u16(),
+                    res_headers,
                 )),
             },
         }
@@ -0,0 +1,144 @@
+use super::Status;
+use crate::entities as MegalodonEntities;
+use chrono::{DateTime, Utc};
+use serde::Deserialize;
+
+#[derive(Debug, Deserialize, Clone)]
+pub struct Account {
+    id: String,
+    pub nickname: String,
+    email: Option<String>,
+    is_active: bool,
+    is_confirmed: bool,
+    is_approved: bool,
+    roles: Roles,
+    registration_reason: Option<String>,
+    created_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Deserialize, Clone)]
+struct Roles {
+    admin: bool,
+    moderator: bool,
+}
+
+#[derive(Debug, Deserialize, Clone)]
+pub struct Accounts {
+    pub users: Vec<Account>,
+}
+
+#[derive(Debug, Deserialize, Clone)]
+pub struct Report {
+    id: String,
+    state: ReportState,
+    account: Account,
+    actor: Account,
+    content: Option<String>,
+    created_at: DateTime<Utc>,
+    statuses: Vec<Status>,
+}
+
+#[derive(Debug, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum ReportState {
+    Open,
+    Closed,
+    Resolved,
+}
+
+#[derive(Debug, Deserialize, Clone)]
+pub struct Reports {
+    pub reports: Vec<Report>,
+}
+
+impl From<Account> for MegalodonEntities::admin::Account {
+    fn from(val: Account) -> Self {
+        // Remote accounts are identified by nickname@domain.
+        let (username, domain) = match val.nickname.split_once('@') {
+            Some((username, domain)) => (username.to_string(), Some(domain.to_string())),
+            None => (val.nickname, None),
+        };
+        let role = if val.roles.admin {
+            Some(MegalodonEntities::Role {
+                name: "Admin".to_string(),
+            })
+        } else if val.roles.moderator {
+            Some(MegalodonEntities::Role {
+                name: "Moderator".to_string(),
+            })
+        } else {
+            None
+        };
+        MegalodonEntities::admin::Account {
+            id: val.id,
+            username,
+            domain,
+            created_at: val.created_at,
+            email: val.email,
+            ip: None,
+            ips: Vec::new(),
+            locale: None,
+            invite_request: val.registration_reason,
+            role,
+            confirmed: val.is_confirmed,
+            approved: val.is_approved,
+            disabled: false,
+            silenced: false,
+            suspended: !val.is_active,
+            account: None,
+        }
+    }
+}
+
+impl From<Report> for MegalodonEntities::admin::Report {
+    fn from(val: Report) -> Self {
+        MegalodonEntities::admin::Report {
+            id: val.id,
+            action_taken: val.state != ReportState::Open,
+            action_taken_at: None,
+            category: MegalodonEntities::report::Category::Other,
+            comment: val.content.unwrap_or_default(),
+            forwarded: false,
+            created_at: val.created_at,
+            updated_at: None,
+            account: val.actor.into(),
+            target_account: val.account.into(),
+            assigned_account: None,
+            action_taken_by_account: None,
+            statuses: val.statuses.into_iter().map(|s| s.into()).collect(),
+            rules: Vec::new(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_remote_account() {
+        let text = r#"{
+            "id": "9tKi3esbG7OQgZ2920",
+            "nickname": "lain@pleroma.example",
+            "email": null,
+            "avatar": "https://pleroma.example/images/avi.png",
+            "display_name": "lain",
+            "is_active": false,
+            "local": false,
+            "roles": { "admin": false, "moderator": true },
+            "tags": [],
+            "is_confirmed": true,
+            "is_approved": true,
+            "url": "https://pleroma.example/users/lain",
+            "registration_reason": null,
+            "actor_type": "Person"
+        }"#;
+
+        let account: MegalodonEntities::admin::Account =
+            serde_json::from_str::<Account>(text).unwrap().into();
+        assert_eq!(account.username, "lain");
+        assert_eq!(account.domain.as_deref(), Some("pleroma.example"));
+        assert_eq!(account.role.unwrap().name, "Moderator");
+        assert!(account.suspended);
+    }
+}
@@ -95,3 +95,20 @@ impl From<Reaction> for MegalodonEntities::announcement::Reaction {
         }
     }
 }
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct AnnouncementReaction {
+    name: String,
+    count: i64,
+    announcement_id: String,
+}
+
+impl From<AnnouncementReaction> for MegalodonEntities::announcement::AnnouncementReaction {
+    fn from(val: AnnouncementReaction) -> Self {
+        MegalodonEntities::announcement::AnnouncementReaction {
+            name: val.name,
+            count: val.count,
+            announcement_id: val.announcement_id,
+        }
+    }
+}
@@ -49,6 +49,7 @@ impl From<Card> for MegalodonEntities::Card {
             height: None,
             embed_url: None,
             blurhash: None,
+            authors: Vec::new(),
         }
     }
 }
@@ -0,0 +1,79 @@
+use super::{Account, Attachment, Card, Emoji};
+use crate::entities as MegalodonEntities;
+use chrono::{DateTime, Utc};
+use serde::Deserialize;
+
+#[derive(Debug, Deserialize, Clone)]
+pub struct Chat {
+    id: String,
+    account: Account,
+    unread: u32,
+    last_message: Option<ChatMessage>,
+    updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Deserialize, Clone)]
+pub struct ChatMessage {
+    id: String,
+    chat_id: String,
+    account_id: String,
+    content: Option<String>,
+    created_at: DateTime<Utc>,
+    emojis: Vec<Emoji>,
+    attachment: Option<Attachment>,
+    card: Option<Card>,
+    unread: bool,
+}
+
+impl From<Chat> for MegalodonEntities::Chat {
+    fn from(val: Chat) -> Self {
+        MegalodonEntities::Chat {
+            id: val.id,
+            account: val.account.into(),
+            unread: val.unread,
+            last_message: val.last_message.map(|i| i.into()),
+            updated_at: val.updated_at,
+        }
+    }
+}
+
+impl From<ChatMessage> for MegalodonEntities::ChatMessage {
+    fn from(val: ChatMessage) -> Self {
+        MegalodonEntities::ChatMessage {
+            id: val.id,
+            chat_id: val.chat_id,
+            account_id: val.account_id,
+            content: val.content,
+            created_at: val.created_at,
+            emojis: val.emojis.into_iter().map(|i| i.into()).collect(),
+            attachment: val.attachment.map(|i| i.into()),
+            card: val.card.map(|i| i.into()),
+            unread: val.unread,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_chat_message() {
+        let data = r#"{
+            "id": "9",
+            "chat_id": "1",
+            "account_id": "2",
+            "content": "hello",
+            "created_at": "2020-01-02T03:04:05.000Z",
+            "emojis": [],
+            "attachment": null,
+            "card": null,
+            "unread": true
+        }"#;
+        let message: ChatMessage = serde_json::from_str(data).unwrap();
+        let message: MegalodonEntities::ChatMessage = message.into();
+        assert_eq!(message.chat_id, "1");
+        assert_eq!(message.content.as_deref(), Some("hello"));
+        assert!(message.unread);
+    }
+}
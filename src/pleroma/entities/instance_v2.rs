@@ -0,0 +1,198 @@
+use super::Account;
+use crate::entities as MegalodonEntities;
+use serde::Deserialize;
+use std::collections::HashMap;
+
+#[derive(Debug, Deserialize, Clone)]
+pub struct InstanceV2 {
+    domain: String,
+    title: String,
+    version: String,
+    source_url: Option<String>,
+    description: String,
+    thumbnail: Option<InstanceThumbnail>,
+    #[serde(default)]
+    languages: Vec<String>,
+    configuration: InstanceV2Config,
+    registrations: InstanceRegistrations,
+    contact: InstanceContact,
+    #[serde(default)]
+    rules: Vec<InstanceRule>,
+}
+
+#[derive(Debug, Deserialize, Clone)]
+pub struct InstanceThumbnail {
+    url: String,
+}
+
+// Akkoma does not report matrix and frame rate limits of media attachments, so they are not parsed.
+#[derive(Debug, Deserialize, Clone)]
+pub struct InstanceV2Config {
+    urls: InstanceV2URLs,
+    statuses: Statuses,
+    polls: Option<Polls>,
+    #[serde(default)]
+    translation: Translation,
+}
+
+#[derive(Debug, Deserialize, Clone)]
+pub struct InstanceV2URLs {
+    streaming: Option<String>,
+}
+
+#[derive(Debug, Deserialize, Clone)]
+pub struct Statuses {
+    max_characters: u32,
+    max_media_attachments: Option<u32>,
+}
+
+#[derive(Debug, Deserialize, Clone)]
+pub struct Polls {
+    max_options: u32,
+    max_characters_per_option: u32,
+    min_expiration: u32,
+    max_expiration: u32,
+}
+
+#[derive(Debug, Deserialize, Clone, Default)]
+pub struct Translation {
+    enabled: bool,
+}
+
+#[derive(Debug, Deserialize, Clone)]
+pub struct InstanceRegistrations {
+    enabled: bool,
+    approval_required: bool,
+    message: Option<String>,
+}
+
+#[derive(Debug, Deserialize, Clone)]
+pub struct InstanceContact {
+    email: String,
+    account: Option<Account>,
+}
+
+#[derive(Debug, Deserialize, Clone)]
+pub struct InstanceRule {
+    id: String,
+    text: String,
+}
+
+impl From<InstanceV2> for MegalodonEntities::InstanceV2 {
+    fn from(val: InstanceV2) -> Self {
+        MegalodonEntities::InstanceV2 {
+            domain: val.domain,
+            title: val.title,
+            version: val.version,
+            source_url: val.source_url,
+            description: val.description,
+            thumbnail: val
+                .thumbnail
+                .map(|t| MegalodonEntities::instance_v2::InstanceThumbnail {
+                    url: t.url,
+                    blurhash: None,
+                    versions: None,
+                }),
+            languages: val.languages,
+            configuration: val.configuration.into(),
+            registrations: MegalodonEntities::instance_v2::InstanceRegistrations {
+                enabled: val.registrations.enabled,
+                approval_required: val.registrations.approval_required,
+                message: val.registrations.message,
+                url: None,
+            },
+            api_versions: HashMap::new(),
+            contact: MegalodonEntities::instance_v2::InstanceContact {
+                email: val.contact.email,
+                account: val.contact.account.map(|a| a.into()),
+            },
+            rules: val
+                .rules
+                .into_iter()
+                .map(|r| MegalodonEntities::instance::InstanceRule {
+                    id: r.id,
+                    text: r.text,
+                })
+                .collect(),
+        }
+    }
+}
+
+impl From<InstanceV2Config> for MegalodonEntities::instance_v2::InstanceV2Config {
+    fn from(val: InstanceV2Config) -> Self {
+        MegalodonEntities::instance_v2::InstanceV2Config {
+            urls: MegalodonEntities::instance_v2::InstanceV2URLs {
+                streaming: val.urls.streaming,
+            },
+            statuses: MegalodonEntities::instance::Statuses {
+                max_characters: val.statuses.max_characters,
+                max_media_attachments: val.statuses.max_media_attachments,
+                characters_reserved_per_url: None,
+            },
+            media_attachments: None,
+            polls: val.polls.map(|p| MegalodonEntities::instance::Polls {
+                max_options: p.max_options,
+                max_characters_per_option: p.max_characters_per_option,
+                min_expiration: p.min_expiration,
+                max_expiration: p.max_expiration,
+            }),
+            translation: MegalodonEntities::instance_v2::Translation {
+                enabled: val.translation.enabled,
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_instance_v2() {
+        let text = r#"{
+            "domain": "akkoma.example",
+            "title": "Akkoma",
+            "version": "2.7.2 (compatible; Akkoma 3.13.2)",
+            "source_url": "https://akkoma.dev/AkkomaGang/akkoma",
+            "description": "",
+            "usage": { "users": { "active_month": 3 } },
+            "thumbnail": { "url": "https://akkoma.example/instance/thumbnail.jpeg" },
+            "languages": ["en"],
+            "configuration": {
+                "accounts": { "max_featured_tags": 0, "max_pinned_statuses": 5 },
+                "statuses": { "max_characters": 5000, "max_media_attachments": 1000 },
+                "media_attachments": {
+                    "supported_mime_types": ["image/jpeg"],
+                    "image_size_limit": 16000000,
+                    "video_size_limit": 16000000
+                },
+                "polls": {
+                    "max_options": 20,
+                    "max_characters_per_option": 200,
+                    "min_expiration": 0,
+                    "max_expiration": 31536000
+                },
+                "urls": { "streaming": "wss://akkoma.example" },
+                "translation": { "enabled": true }
+            },
+            "registrations": { "enabled": true, "approval_required": false, "message": null },
+            "contact": { "email": "admin@akkoma.example", "account": null },
+            "rules": []
+        }"#;
+
+        let instance: MegalodonEntities::InstanceV2 =
+            serde_json::from_str::<InstanceV2>(text).unwrap().into();
+        assert_eq!(instance.domain, "akkoma.example");
+        assert_eq!(
+            instance.configuration.urls.streaming.as_deref(),
+            Some("wss://akkoma.example")
+        );
+        assert_eq!(
+            instance.configuration.statuses.max_media_attachments,
+            Some(1000)
+        );
+        assert!(instance.configuration.media_attachments.is_none());
+        assert_eq!(instance.configuration.polls.unwrap().max_options, 20);
+        assert!(instance.configuration.translation.enabled);
+    }
+}
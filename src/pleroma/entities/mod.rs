@@ -1,9 +1,11 @@
 pub mod account;
 pub mod activity;
+pub mod admin;
 pub mod announcement;
 pub mod application;
 pub mod attachment;
 pub mod card;
+pub mod chat;
 pub mod context;
 pub mod conversation;
 pub mod emoji;
@@ -13,6 +15,7 @@ pub mod filter;
 pub mod history;
 pub mod identity_proof;
 pub mod instance;
+pub mod instance_v2;
 pub mod list;
 pub mod marker;
 pub mod mention;
@@ -29,10 +32,12 @@ pub mod scheduled_status;
 pub mod source;
 pub mod stats;
 pub mod status;
+pub mod status_edit;
 pub mod status_params;
 pub mod status_source;
 pub mod tag;
 pub mod token;
+pub mod translation;
 pub mod urls;
 
 pub use account::Account;
@@ -41,6 +46,7 @@ pub use announcement::Announcement;
 pub use application::Application;
 pub use attachment::Attachment;
 pub use card::Card;
+pub use chat::{Chat, ChatMessage};
 pub use context::Context;
 pub use conversation::Conversation;
 pub use emoji::Emoji;
@@ -50,6 +56,7 @@ pub use filter::Filter;
 pub use history::History;
 pub use identity_proof::IdentityProof;
 pub use instance::Instance;
+pub use instance_v2::InstanceV2;
 pub use list::List;
 pub use marker::Marker;
 pub use mention::Mention;
@@ -66,8 +73,10 @@ pub use scheduled_status::ScheduledStatus;
 pub use source::Source;
 pub use stats::Stats;
 pub use status::{Status, StatusVisibility};
+pub use status_edit::StatusEdit;
 pub use status_params::StatusParams;
 pub use status_source::StatusSource;
 pub use tag::Tag;
 pub use token::Token;
+pub use translation::Translation;
 pub use urls::URLs;
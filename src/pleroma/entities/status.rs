@@ -191,6 +191,7 @@ impl From<Status> for MegalodonEntities::Status {
             quote,
             quote_approval: MegalodonEntities::QuoteApproval::automatic_unsupported(),
             bookmarked: val.bookmarked,
+            filtered: Vec::new(),
         }
     }
 }
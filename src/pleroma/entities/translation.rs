@@ -0,0 +1,95 @@
+use crate::entities as MegalodonEntities;
+use serde::Deserialize;
+use std::collections::HashMap;
+
+#[derive(Debug, Deserialize, Clone)]
+pub struct Translation {
+    content: String,
+    spoiler_text: Option<String>,
+    detected_source_language: String,
+    provider: String,
+}
+
+impl From<Translation> for MegalodonEntities::Translation {
+    fn from(val: Translation) -> Self {
+        MegalodonEntities::Translation {
+            content: val.content,
+            spoiler_text: val.spoiler_text.unwrap_or_default(),
+            poll: None,
+            media_attachments: Vec::new(),
+            detected_source_language: val.detected_source_language,
+            provider: val.provider,
+        }
+    }
+}
+
+// Akkoma translates statuses with its own API.
+#[derive(Debug, Deserialize, Clone)]
+pub struct AkkomaTranslation {
+    text: String,
+    detected_language: String,
+}
+
+impl From<AkkomaTranslation> for MegalodonEntities::Translation {
+    fn from(val: AkkomaTranslation) -> Self {
+        MegalodonEntities::Translation {
+            content: val.text,
+            spoiler_text: "".to_string(),
+            poll: None,
+            media_attachments: Vec::new(),
+            detected_source_language: val.detected_language.to_lowercase(),
+            provider: "".to_string(),
+        }
+    }
+}
+
+#[derive(Debug, Deserialize, Clone)]
+pub struct AkkomaTranslationLanguages {
+    source: Vec<AkkomaLanguage>,
+    target: Vec<AkkomaLanguage>,
+}
+
+#[derive(Debug, Deserialize, Clone)]
+pub struct AkkomaLanguage {
+    code: String,
+}
+
+impl From<AkkomaTranslationLanguages> for HashMap<String, Vec<String>> {
+    fn from(val: AkkomaTranslationLanguages) -> Self {
+        val.source
+            .into_iter()
+            .map(|source| {
+                let targets = val
+                    .target
+                    .iter()
+                    .filter(|target| !target.code.eq_ignore_ascii_case(&source.code))
+                    .map(|target| target.code.to_lowercase())
+                    .collect();
+                (source.code.to_lowercase(), targets)
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_akkoma_translation_languages() {
+        let json = r#"{
+  "source": [
+    {"code": "EN", "name": "English"},
+    {"code": "JA", "name": "Japanese"}
+  ],
+  "target": [
+    {"code": "EN-US", "name": "English (American)"},
+    {"code": "JA", "name": "Japanese"}
+  ]
+}"#;
+        let languages: AkkomaTranslationLanguages = serde_json::from_str(json).unwrap();
+        let languages: HashMap<String, Vec<String>> = languages.into();
+        assert_eq!(languages["en"], vec!["en-us".to_string(), "ja".to_string()]);
+        assert_eq!(languages["ja"], vec!["en-us".to_string()]);
+    }
+}
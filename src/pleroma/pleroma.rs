@@ -2,9 +2,13 @@ use super::api_client::APIClient;
 use super::entities;
 use super::oauth;
 use super::web_socket::WebSocket;
+use crate::SNS;
+use crate::capabilities::Capabilities;
 use crate::error::Error as MegalodonError;
+use crate::http::{ClientBuilder, idempotency_header};
 use crate::megalodon::FollowRequestOutput;
-use crate::{Streaming, error};
+use crate::web_socket::MultiplexWebSocket;
+use crate::{Streaming, error, streaming::MultiplexStreaming};
 use crate::{
     default, entities as MegalodonEntities, error::Error, megalodon, oauth as MegalodonOAuth,
     response::Response,
@@ -39,7 +43,22 @@ impl Pleroma {
         access_token: Option<String>,
         user_agent: Option<String>,
     ) -> Result<Self, MegalodonError> {
-        let client = APIClient::new(base_url.clone(), access_token.clone(), user_agent.clone())?;
+        Self::new_with_client(base_url, access_token, user_agent, ClientBuilder::default())
+    }
+
+    /// Create a new [`Pleroma`] with the HTTP client settings.
+    pub fn new_with_client(
+        base_url: String,
+        access_token: Option<String>,
+        user_agent: Option<String>,
+        client: ClientBuilder,
+    ) -> Result<Self, MegalodonError> {
+        let client = APIClient::new(
+            base_url.clone(),
+            access_token.clone(),
+            user_agent.clone(),
+            &client,
+        )?;
         Ok(Self {
             client,
             base_url,
@@ -70,6 +89,56 @@ impl Pleroma {
             .url();
         Ok(auth_url.to_string())
     }
+
+    // Admin API of Pleroma identifies users by nickname, so the account is looked up by ID at first.
+    async fn update_admin_account(
+        &self,
+        id: String,
+        action: &str,
+    ) -> Result<Response<MegalodonEntities::admin::Account>, Error> {
+        let account = self
+            .client
+            .get::<entities::admin::Account>(
+                format!("/api/v1/pleroma/admin/users/{}", id).as_str(),
+                None,
+            )
+            .await?;
+        let params = HashMap::<&str, Value>::from([(
+            "nicknames",
+            Value::from(vec![account.json.nickname.clone()]),
+        )]);
+        let res = self
+            .client
+            .patch::<entities::admin::Accounts>(
+                format!("/api/v1/pleroma/admin/users/{}", action).as_str(),
+                &params,
+                None,
+            )
+            .await?;
+        let updated = res.json.users.into_iter().next().unwrap_or(account.json);
+
+        Ok(Response::<MegalodonEntities::admin::Account>::new(
+            updated.into(),
+            res.status,
+            res.status_text,
+            res.header,
+        ))
+    }
+
+    async fn update_admin_report(
+        &self,
+        id: String,
+        state: &str,
+    ) -> Result<Response<MegalodonEntities::admin::Report>, Error> {
+        let params = HashMap::<&str, Value>::from([(
+            "reports",
+            serde_json::json!([{ "id": id, "state": state }]),
+        )]);
+        self.client
+            .patch::<Value>("/api/v1/pleroma/admin/reports", &params, None)
+            .await?;
+        megalodon::Megalodon::get_admin_report(self, id).await
+    }
 }
 
 #[async_trait]
@@ -1465,16 +1534,20 @@ impl megalodon::Megalodon for Pleroma {
                     "quoted_status_id",
                     serde_json::Value::String(quote_id.clone()),
                 );
+                // Akkoma accepts only quote_id.
+                params.insert("quote_id", serde_json::Value::String(quote_id.clone()));
             }
             if let Some(poll) = &options.poll {
                 params.insert("poll", serde_json::to_value(&poll).unwrap());
             }
         }
 
+        let headers = idempotency_header(options.and_then(|o| o.idempotency_key.as_deref()))?;
+
         if params.contains_key("scheduled_at") {
             let res = self
                 .client
-                .post::<entities::ScheduledStatus>("/api/v1/statuses", &params, None)
+                .post::<entities::ScheduledStatus>("/api/v1/statuses", &params, Some(headers))
                 .await?;
 
             Ok(Response::<megalodon::PostStatusOutput>::new(
@@ -1486,7 +1559,7 @@ impl megalodon::Megalodon for Pleroma {
         } else {
             let res = self
                 .client
-                .post::<entities::Status>("/api/v1/statuses", &params, None)
+                .post::<entities::Status>("/api/v1/statuses", &params, Some(headers))
                 .await?;
 
             Ok(Response::<megalodon::PostStatusOutput>::new(
@@ -1529,6 +1602,26 @@ impl megalodon::Megalodon for Pleroma {
         ))
     }
 
+    async fn get_status_history(
+        &self,
+        id: String,
+    ) -> Result<Response<Vec<MegalodonEntities::StatusEdit>>, Error> {
+        let res = self
+            .client
+            .get::<Vec<entities::StatusEdit>>(
+                format!("/api/v1/statuses/{}/history", id).as_str(),
+                None,
+            )
+            .await?;
+
+        Ok(Response::<Vec<MegalodonEntities::StatusEdit>>::new(
+            res.json.into_iter().map(|j| j.into()).collect(),
+            res.status,
+            res.status_text,
+            res.header,
+        ))
+    }
+
     async fn edit_status(
         &self,
         id: String,
@@ -1582,6 +1675,7 @@ impl megalodon::Megalodon for Pleroma {
             status,
             status_text,
             header,
+            ..
         } = self
             .client
             .delete::<Value>(format!("/api/v1/statuses/{}", id).as_str(), &params, None)
@@ -1664,6 +1758,40 @@ impl megalodon::Megalodon for Pleroma {
         ))
     }
 
+    async fn get_status_quotes(
+        &self,
+        id: String,
+        options: Option<&megalodon::GetStatusQuotesInputOptions>,
+    ) -> Result<Response<Vec<MegalodonEntities::Status>>, Error> {
+        let mut params = Vec::<String>::new();
+        if let Some(options) = options {
+            if let Some(limit) = options.limit {
+                params.push(format!("limit={}", limit));
+            }
+            if let Some(max_id) = &options.max_id {
+                params.push(format!("max_id={}", max_id));
+            }
+            if let Some(since_id) = &options.since_id {
+                params.push(format!("since_id={}", since_id));
+            }
+        }
+        let mut path = format!("/api/v1/pleroma/statuses/{}/quotes", id);
+        if !params.is_empty() {
+            path = path + "?" + params.join("&").as_str();
+        }
+        let res = self
+            .client
+            .get::<Vec<entities::Status>>(path.as_str(), None)
+            .await?;
+
+        Ok(Response::<Vec<MegalodonEntities::Status>>::new(
+            res.json.into_iter().map(|j| j.into()).collect(),
+            res.status,
+            res.status_text,
+            res.header,
+        ))
+    }
+
     async fn favourite_status(
         &self,
         id: String,
@@ -1875,6 +2003,62 @@ impl megalodon::Megalodon for Pleroma {
         ))
     }
 
+    async fn translate_status(
+        &self,
+        id: String,
+        lang: Option<String>,
+    ) -> Result<Response<MegalodonEntities::Translation>, Error> {
+        let mut params = HashMap::<&str, Value>::new();
+        if let Some(lang) = &lang {
+            params.insert("lang", Value::String(lang.clone()));
+        }
+        match self
+            .client
+            .post::<entities::Translation>(
+                format!("/api/v1/statuses/{}/translate", id).as_str(),
+                &params,
+                None,
+            )
+            .await
+        {
+            Ok(res) => Ok(Response::<MegalodonEntities::Translation>::new(
+                res.json.into(),
+                res.status,
+                res.status_text,
+                res.header,
+            )),
+            // Akkoma does not have the Mastodon compatible API.
+            Err(Error::OwnError(error::OwnError {
+                kind: error::Kind::NotFoundError,
+                ..
+            })) => {
+                let Some(lang) = lang else {
+                    return Err(Error::new_own(
+                        "lang is required".to_string(),
+                        error::Kind::UnsatisfiedError,
+                        None,
+                        None,
+                        None,
+                    ));
+                };
+                let res = self
+                    .client
+                    .get::<entities::translation::AkkomaTranslation>(
+                        format!("/api/v1/statuses/{}/translations/{}", id, lang).as_str(),
+                        None,
+                    )
+                    .await?;
+                Ok(Response::<MegalodonEntities::Translation>::new(
+                    res.json.into(),
+                    res.status,
+                    res.status_text,
+                    res.header,
+                ))
+            }
+            Err(err) => Err(err),
+        }
+    }
+
     async fn upload_media_reader(
         &self,
         reader: Box<dyn AsyncRead + Sync + Send + Unpin>,
@@ -2867,6 +3051,40 @@ impl megalodon::Megalodon for Pleroma {
         ))
     }
 
+    async fn get_instance_v2(&self) -> Result<Response<MegalodonEntities::InstanceV2>, Error> {
+        match self
+            .client
+            .get::<entities::InstanceV2>("/api/v2/instance", None)
+            .await
+        {
+            Ok(res) => Ok(Response::<MegalodonEntities::InstanceV2>::new(
+                res.json.into(),
+                res.status,
+                res.status_text,
+                res.header,
+            )),
+            // Pleroma does not have v2 instance, while Akkoma has it.
+            Err(Error::OwnError(error::OwnError {
+                kind: error::Kind::NotFoundError,
+                ..
+            })) => {
+                let res = self.get_instance().await?;
+                Ok(Response::<MegalodonEntities::InstanceV2>::new(
+                    res.json.into(),
+                    res.status,
+                    res.status_text,
+                    res.header,
+                ))
+            }
+            Err(err) => Err(err),
+        }
+    }
+
+    async fn capabilities(&self) -> Result<Capabilities, Error> {
+        let instance = self.get_instance_v2().await;
+        Ok(Capabilities::detect(SNS::Pleroma, instance))
+    }
+
     async fn get_instance_peers(&self) -> Result<Response<Vec<String>>, Error> {
         let res = self
             .client
@@ -2891,6 +3109,38 @@ impl megalodon::Megalodon for Pleroma {
         ))
     }
 
+    async fn get_instance_translation_languages(
+        &self,
+    ) -> Result<Response<HashMap<String, Vec<String>>>, Error> {
+        match self
+            .client
+            .get::<HashMap<String, Vec<String>>>("/api/v1/instance/translation_languages", None)
+            .await
+        {
+            Ok(res) => Ok(res),
+            // Akkoma does not have the Mastodon compatible API.
+            Err(Error::OwnError(error::OwnError {
+                kind: error::Kind::NotFoundError,
+                ..
+            })) => {
+                let res = self
+                    .client
+                    .get::<entities::translation::AkkomaTranslationLanguages>(
+                        "/api/v1/akkoma/translation/languages",
+                        None,
+                    )
+                    .await?;
+                Ok(Response::<HashMap<String, Vec<String>>>::new(
+                    res.json.into(),
+                    res.status,
+                    res.status_text,
+                    res.header,
+                ))
+            }
+            Err(err) => Err(err),
+        }
+    }
+
     async fn get_instance_trends(
         &self,
         limit: Option<u32>,
@@ -3129,6 +3379,229 @@ impl megalodon::Megalodon for Pleroma {
         ))
     }
 
+    async fn get_admin_accounts(
+        &self,
+        options: Option<&megalodon::GetAdminAccountsInputOptions>,
+    ) -> Result<Response<Vec<MegalodonEntities::admin::Account>>, Error> {
+        let mut params = Vec::<String>::new();
+        if let Some(options) = options {
+            let mut filters = Vec::<&str>::new();
+            match options.origin.as_deref() {
+                Some("local") => filters.push("local"),
+                Some("remote") => filters.push("external"),
+                _ => {}
+            }
+            match options.status.as_deref() {
+                Some("active") => filters.push("active"),
+                Some("pending") => filters.push("need_approval"),
+                Some("disabled") | Some("suspended") => filters.push("deactivated"),
+                Some("silenced") => {
+                    return Err(Error::new_own(
+                        "Pleroma does not support silenced status".to_string(),
+                        error::Kind::NoImplementedError,
+                        None,
+                        None,
+                        None,
+                    ))
+                }
+                _ => {}
+            }
+            if !filters.is_empty() {
+                params.push(format!("filters={}", filters.join(",")));
+            }
+            if let Some(username) = &options.username {
+                params.push(format!("query={}", encode(username)));
+            }
+            if let Some(limit) = options.limit {
+                params.push(format!("page_size={}", limit));
+            }
+        }
+        let mut path = "/api/v1/pleroma/admin/users".to_string();
+        if !params.is_empty() {
+            path = path + "?" + params.join("&").as_str();
+        }
+        let res = self
+            .client
+            .get::<entities::admin::Accounts>(path.as_str(), None)
+            .await?;
+
+        Ok(Response::<Vec<MegalodonEntities::admin::Account>>::new(
+            res.json.users.into_iter().map(|j| j.into()).collect(),
+            res.status,
+            res.status_text,
+            res.header,
+        ))
+    }
+
+    async fn get_admin_account(
+        &self,
+        id: String,
+    ) -> Result<Response<MegalodonEntities::admin::Account>, Error> {
+        let res = self
+            .client
+            .get::<entities::admin::Account>(
+                format!("/api/v1/pleroma/admin/users/{}", id).as_str(),
+                None,
+            )
+            .await?;
+
+        Ok(Response::<MegalodonEntities::admin::Account>::new(
+            res.json.into(),
+            res.status,
+            res.status_text,
+            res.header,
+        ))
+    }
+
+    async fn approve_admin_account(
+        &self,
+        id: String,
+    ) -> Result<Response<MegalodonEntities::admin::Account>, Error> {
+        self.update_admin_account(id, "approve").await
+    }
+
+    async fn reject_admin_account(
+        &self,
+        id: String,
+    ) -> Result<Response<MegalodonEntities::admin::Account>, Error> {
+        // Pending accounts are rejected by deleting them.
+        self.delete_admin_account(id).await
+    }
+
+    async fn perform_admin_account_action(
+        &self,
+        id: String,
+        action: megalodon::AdminAccountAction,
+        _options: Option<&megalodon::AdminAccountActionInputOptions>,
+    ) -> Result<Response<()>, Error> {
+        match action {
+            megalodon::AdminAccountAction::Suspend | megalodon::AdminAccountAction::Disable => {
+                let res = self.update_admin_account(id, "deactivate").await?;
+                Ok(Response::<()>::new(
+                    (),
+                    res.status,
+                    res.status_text,
+                    res.header,
+                ))
+            }
+            _ => Err(Error::new_own(
+                format!("Pleroma does not support {} action", action),
+                error::Kind::NoImplementedError,
+                None,
+                None,
+                None,
+            )),
+        }
+    }
+
+    async fn enable_admin_account(
+        &self,
+        id: String,
+    ) -> Result<Response<MegalodonEntities::admin::Account>, Error> {
+        self.update_admin_account(id, "activate").await
+    }
+
+    async fn unsuspend_admin_account(
+        &self,
+        id: String,
+    ) -> Result<Response<MegalodonEntities::admin::Account>, Error> {
+        self.update_admin_account(id, "activate").await
+    }
+
+    async fn delete_admin_account(
+        &self,
+        id: String,
+    ) -> Result<Response<MegalodonEntities::admin::Account>, Error> {
+        let account = self
+            .client
+            .get::<entities::admin::Account>(
+                format!("/api/v1/pleroma/admin/users/{}", id).as_str(),
+                None,
+            )
+            .await?;
+        let params = HashMap::<&str, Value>::from([(
+            "nicknames",
+            Value::from(vec![account.json.nickname.clone()]),
+        )]);
+        let res = self
+            .client
+            .delete::<Value>("/api/v1/pleroma/admin/users", &params, None)
+            .await?;
+
+        Ok(Response::<MegalodonEntities::admin::Account>::new(
+            account.json.into(),
+            res.status,
+            res.status_text,
+            res.header,
+        ))
+    }
+
+    async fn get_admin_reports(
+        &self,
+        options: Option<&megalodon::GetAdminReportsInputOptions>,
+    ) -> Result<Response<Vec<MegalodonEntities::admin::Report>>, Error> {
+        let mut params = Vec::<String>::new();
+        if let Some(options) = options {
+            match options.resolved {
+                Some(true) => params.push("state=resolved".to_string()),
+                Some(false) => params.push("state=open".to_string()),
+                None => {}
+            }
+            if let Some(limit) = options.limit {
+                params.push(format!("page_size={}", limit));
+            }
+        }
+        let mut path = "/api/v1/pleroma/admin/reports".to_string();
+        if !params.is_empty() {
+            path = path + "?" + params.join("&").as_str();
+        }
+        let res = self
+            .client
+            .get::<entities::admin::Reports>(path.as_str(), None)
+            .await?;
+
+        Ok(Response::<Vec<MegalodonEntities::admin::Report>>::new(
+            res.json.reports.into_iter().map(|j| j.into()).collect(),
+            res.status,
+            res.status_text,
+            res.header,
+        ))
+    }
+
+    async fn get_admin_report(
+        &self,
+        id: String,
+    ) -> Result<Response<MegalodonEntities::admin::Report>, Error> {
+        let res = self
+            .client
+            .get::<entities::admin::Report>(
+                format!("/api/v1/pleroma/admin/reports/{}", id).as_str(),
+                None,
+            )
+            .await?;
+
+        Ok(Response::<MegalodonEntities::admin::Report>::new(
+            res.json.into(),
+            res.status,
+            res.status_text,
+            res.header,
+        ))
+    }
+
+    async fn resolve_admin_report(
+        &self,
+        id: String,
+    ) -> Result<Response<MegalodonEntities::admin::Report>, Error> {
+        self.update_admin_report(id, "resolved").await
+    }
+
+    async fn reopen_admin_report(
+        &self,
+        id: String,
+    ) -> Result<Response<MegalodonEntities::admin::Report>, Error> {
+        self.update_admin_report(id, "open").await
+    }
+
     async fn streaming_url(&self) -> String {
         let instance = self.get_instance().await;
         if let Ok(instance) = instance {
@@ -3150,6 +3623,7 @@ impl megalodon::Megalodon for Pleroma {
             Some(params),
             self.access_token.clone(),
             self.user_agent.clone(),
+            self.client.http_client(),
         );
 
         Box::new(c)
@@ -3164,6 +3638,7 @@ impl megalodon::Megalodon for Pleroma {
             Some(params),
             self.access_token.clone(),
             self.user_agent.clone(),
+            self.client.http_client(),
         );
 
         Box::new(c)
@@ -3178,6 +3653,7 @@ impl megalodon::Megalodon for Pleroma {
             Some(params),
             self.access_token.clone(),
             self.user_agent.clone(),
+            self.client.http_client(),
         );
 
         Box::new(c)
@@ -3192,6 +3668,7 @@ impl megalodon::Megalodon for Pleroma {
             Some(params),
             self.access_token.clone(),
             self.user_agent.clone(),
+            self.client.http_client(),
         );
 
         Box::new(c)
@@ -3206,6 +3683,7 @@ impl megalodon::Megalodon for Pleroma {
             Some(params),
             self.access_token.clone(),
             self.user_agent.clone(),
+            self.client.http_client(),
         );
 
         Box::new(c)
@@ -3220,8 +3698,172 @@ impl megalodon::Megalodon for Pleroma {
             Some(params),
             self.access_token.clone(),
             self.user_agent.clone(),
+            self.client.http_client(),
         );
 
         Box::new(c)
     }
+
+    async fn multiplex_streaming(
+        &self,
+    ) -> Result<Box<dyn MultiplexStreaming + Send + Sync>, Error> {
+        let streaming_url = self.streaming_url().await;
+        let c = MultiplexWebSocket::new(
+            streaming_url + "/api/v1/streaming",
+            self.access_token.clone(),
+            self.user_agent.clone(),
+            WebSocket::parse_event,
+        );
+
+        Ok(Box::new(c))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::http::testing::serve;
+    use crate::megalodon::Megalodon;
+
+    fn admin_account(id: &str, nickname: &str) -> Value {
+        serde_json::json!({
+            "id": id,
+            "nickname": nickname,
+            "email": null,
+            "is_active": true,
+            "is_confirmed": true,
+            "is_approved": false,
+            "roles": {"admin": false, "moderator": false},
+            "registration_reason": "hello",
+            "created_at": "2024-01-01T00:00:00Z"
+        })
+    }
+
+    #[tokio::test]
+    async fn test_update_admin_account_by_nickname() {
+        let (url, mut requests) = serve(vec![
+            admin_account("9", "alice").to_string(),
+            serde_json::json!({"users": [admin_account("9", "alice")]}).to_string(),
+        ])
+        .await;
+        let client = Pleroma::new(url, None, None).unwrap();
+        let res = client
+            .approve_admin_account(String::from("9"))
+            .await
+            .unwrap();
+
+        assert_eq!(res.json.username, "alice");
+        let request = requests.recv().await.unwrap();
+        assert_eq!(request.method, "GET");
+        assert_eq!(request.path, "/api/v1/pleroma/admin/users/9");
+        let request = requests.recv().await.unwrap();
+        assert_eq!(request.method, "PATCH");
+        assert_eq!(request.path, "/api/v1/pleroma/admin/users/approve");
+        assert_eq!(request.json(), serde_json::json!({"nicknames": ["alice"]}));
+    }
+
+    #[tokio::test]
+    async fn test_update_admin_report_state() {
+        let report = serde_json::json!({
+            "id": "7",
+            "state": "resolved",
+            "account": admin_account("9", "alice"),
+            "actor": admin_account("10", "bob"),
+            "content": "spam",
+            "created_at": "2024-01-01T00:00:00Z",
+            "statuses": []
+        });
+        let (url, mut requests) = serve(vec![String::from("{}"), report.to_string()]).await;
+        let client = Pleroma::new(url, None, None).unwrap();
+        let res = client
+            .resolve_admin_report(String::from("7"))
+            .await
+            .unwrap();
+
+        assert!(res.json.action_taken);
+        let request = requests.recv().await.unwrap();
+        assert_eq!(request.method, "PATCH");
+        assert_eq!(request.path, "/api/v1/pleroma/admin/reports");
+        assert_eq!(
+            request.json(),
+            serde_json::json!({"reports": [{"id": "7", "state": "resolved"}]})
+        );
+        let request = requests.recv().await.unwrap();
+        assert_eq!(request.path, "/api/v1/pleroma/admin/reports/7");
+    }
+
+    #[tokio::test]
+    async fn test_get_admin_accounts_silenced() {
+        let client = Pleroma::new(String::from("http://127.0.0.1:1"), None, None).unwrap();
+        let options = megalodon::GetAdminAccountsInputOptions {
+            status: Some(String::from("silenced")),
+            ..Default::default()
+        };
+        let err = client.get_admin_accounts(Some(&options)).await.unwrap_err();
+
+        assert!(matches!(
+            err,
+            Error::OwnError(error::OwnError {
+                kind: error::Kind::NoImplementedError,
+                ..
+            })
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_post_status_quote_ids() {
+        let status = serde_json::json!({
+            "id": "3",
+            "uri": "https://pleroma.example/objects/3",
+            "account": {
+                "id": "9",
+                "username": "alice",
+                "acct": "alice",
+                "display_name": "alice",
+                "locked": false,
+                "created_at": "2024-01-01T00:00:00Z",
+                "followers_count": 0,
+                "following_count": 0,
+                "statuses_count": 0,
+                "note": "",
+                "url": "https://pleroma.example/users/alice",
+                "avatar": "",
+                "avatar_static": "",
+                "header": "",
+                "header_static": "",
+                "emojis": [],
+                "fields": [],
+                "bot": false
+            },
+            "content": "hello",
+            "created_at": "2024-01-01T00:00:00Z",
+            "emojis": [],
+            "replies_count": 0,
+            "reblogs_count": 0,
+            "favourites_count": 0,
+            "sensitive": false,
+            "spoiler_text": "",
+            "visibility": "public",
+            "media_attachments": [],
+            "mentions": [],
+            "tags": [],
+            "pleroma": {"local": true, "quote_id": "1"}
+        });
+        let (url, mut requests) = serve(vec![status.to_string()]).await;
+        let client = Pleroma::new(url, None, None).unwrap();
+        let options = megalodon::PostStatusInputOptions {
+            quote_id: Some(String::from("1")),
+            ..Default::default()
+        };
+        client
+            .post_status(String::from("hello"), Some(&options))
+            .await
+            .unwrap();
+
+        let request = requests.recv().await.unwrap();
+        assert_eq!(request.path, "/api/v1/statuses");
+        let body = request.json();
+        assert_eq!(body["quoted_status_id"], "1");
+        assert_eq!(body["quote_id"], "1");
+    }
 }
@@ -1,30 +1,23 @@
-use std::fmt;
 use std::future::Future;
 use std::pin::Pin;
-use std::thread;
 use std::time::Duration;
 
 use super::entities;
 use crate::default::DEFAULT_UA;
 use crate::error::{Error, Kind};
-use crate::streaming::{Message, Streaming};
+use crate::http::HttpClient;
+use crate::sse::Sse;
+use crate::streaming::{
+    ConnectionError, EVENT_CHANNEL_CAPACITY, EventStream, Message, ReconnectPolicy, Reconnection,
+    StreamEvent, StreamHandle, Streaming, Transport, event_stream,
+};
+use crate::web_socket;
 use async_trait::async_trait;
-use futures_util::{SinkExt, StreamExt};
 use serde::Deserialize;
-use tokio_tungstenite::{
-    connect_async_tls_with_config,
-    tungstenite::{
-        Error as WebSocketError,
-        client::IntoClientRequest,
-        http::StatusCode,
-        protocol::{Message as WebSocketMessage, frame::coding::CloseCode},
-    },
-};
+use tokio::sync::mpsc;
+use tokio_tungstenite::tungstenite::protocol::Message as WebSocketMessage;
+use tokio_util::sync::CancellationToken;
 use tracing::{debug, error, info, warn};
-use url::Url;
-
-const RECONNECT_INTERVAL: u64 = 5000;
-const READ_MESSAGE_TIMEOUT_SECONDS: u64 = 60;
 
 #[derive(Debug, Clone)]
 pub struct WebSocket {
@@ -33,11 +26,15 @@ pub struct WebSocket {
     params: Option<Vec<String>>,
     access_token: Option<String>,
     user_agent: String,
+    client: HttpClient,
+    reconnect_policy: ReconnectPolicy,
+    transport: Transport,
 }
 
 #[derive(Deserialize)]
 struct RawMessage {
     event: String,
+    #[serde(default)]
     payload: String,
 }
 
@@ -48,6 +45,7 @@ impl WebSocket {
         params: Option<Vec<String>>,
         access_token: Option<String>,
         user_agent: Option<String>,
+        client: HttpClient,
     ) -> Self {
         let ua: String;
         match user_agent {
@@ -60,73 +58,19 @@ impl WebSocket {
             params,
             access_token,
             user_agent: ua,
+            client,
+            reconnect_policy: ReconnectPolicy::default(),
+            transport: Transport::default(),
         }
     }
 
-    fn parse(&self, message: WebSocketMessage) -> Result<Message, Error> {
+    fn parse(message: WebSocketMessage) -> Result<Message, Error> {
         if message.is_ping() || message.is_pong() {
             Ok(Message::Heartbeat())
         } else if message.is_text() {
             let text = message.to_text()?;
             let mes = serde_json::from_str::<RawMessage>(text)?;
-            match &*mes.event {
-                "update" => {
-                    let res =
-                        serde_json::from_str::<entities::Status>(&mes.payload).map_err(|e| {
-                            error!(
-                                "failed to parse status: {}\n{}",
-                                e.to_string(),
-                                &mes.payload
-                            );
-                            e
-                        })?;
-                    Ok(Message::Update(res.into()))
-                }
-                "notification" => {
-                    let res = serde_json::from_str::<entities::Notification>(&mes.payload)
-                        .map_err(|e| {
-                            error!(
-                                "failed to parse notification: {}\n{}",
-                                e.to_string(),
-                                &mes.payload
-                            );
-                            e
-                        })?;
-                    Ok(Message::Notification(res.into()))
-                }
-                "conversation" => {
-                    let res = serde_json::from_str::<entities::Conversation>(&mes.payload)
-                        .map_err(|e| {
-                            error!(
-                                "failed to parse conversation: {}\n{}",
-                                e.to_string(),
-                                &mes.payload
-                            );
-                            e
-                        })?;
-                    Ok(Message::Conversation(res.into()))
-                }
-                "delete" => Ok(Message::Delete(mes.payload)),
-                "status.update" => {
-                    let res =
-                        serde_json::from_str::<entities::Status>(&mes.payload).map_err(|e| {
-                            error!(
-                                "failed to parse status: {}\n{}",
-                                e.to_string(),
-                                &mes.payload
-                            );
-                            e
-                        })?;
-                    Ok(Message::StatusUpdate(res.into()))
-                }
-                event => Err(Error::new_own(
-                    format!("Unknown event is received: {}", event),
-                    Kind::ParseError,
-                    None,
-                    None,
-                    None,
-                )),
-            }
+            Self::parse_event(&mes.event, mes.payload)
         } else {
             Err(Error::new_own(
                 String::from("Receiving message is not ping, pong or text"),
@@ -138,34 +82,111 @@ impl WebSocket {
         }
     }
 
-    async fn connect(
-        &self,
-        url: &str,
-        callback: Box<
-            dyn Fn(Message) -> Pin<Box<dyn Future<Output = ()> + Send>> + Send + Sync + '_,
-        >,
-    ) {
+    /// Parse the event name and the payload, which are common to WebSocket and SSE.
+    pub(crate) fn parse_event(event: &str, payload: String) -> Result<Message, Error> {
+        match event {
+            "update" => {
+                let res = serde_json::from_str::<entities::Status>(&payload).map_err(|e| {
+                    error!("failed to parse status: {}\n{}", e.to_string(), &payload);
+                    e
+                })?;
+                Ok(Message::Update(res.into()))
+            }
+            "notification" => {
+                let res =
+                    serde_json::from_str::<entities::Notification>(&payload).map_err(|e| {
+                        error!(
+                            "failed to parse notification: {}\n{}",
+                            e.to_string(),
+                            &payload
+                        );
+                        e
+                    })?;
+                Ok(Message::Notification(res.into()))
+            }
+            "conversation" => {
+                let res =
+                    serde_json::from_str::<entities::Conversation>(&payload).map_err(|e| {
+                        error!(
+                            "failed to parse conversation: {}\n{}",
+                            e.to_string(),
+                            &payload
+                        );
+                        e
+                    })?;
+                Ok(Message::Conversation(res.into()))
+            }
+            "delete" => Ok(Message::Delete(payload)),
+            "status.update" => {
+                let res = serde_json::from_str::<entities::Status>(&payload).map_err(|e| {
+                    error!("failed to parse status: {}\n{}", e.to_string(), &payload);
+                    e
+                })?;
+                Ok(Message::StatusUpdate(res.into()))
+            }
+            "filters_changed" => Ok(Message::FiltersChanged),
+            "announcement" => {
+                let res =
+                    serde_json::from_str::<entities::Announcement>(&payload).map_err(|e| {
+                        error!(
+                            "failed to parse announcement: {}\n{}",
+                            e.to_string(),
+                            &payload
+                        );
+                        e
+                    })?;
+                Ok(Message::Announcement(res.into()))
+            }
+            "announcement.reaction" => {
+                let res =
+                    serde_json::from_str::<entities::announcement::AnnouncementReaction>(&payload)
+                        .map_err(|e| {
+                            error!(
+                                "failed to parse announcement reaction: {}\n{}",
+                                e.to_string(),
+                                &payload
+                            );
+                            e
+                        })?;
+                Ok(Message::AnnouncementReaction(res.into()))
+            }
+            "announcement.delete" => Ok(Message::AnnouncementDelete(payload)),
+            "notifications_merged" => Ok(Message::NotificationsMerged),
+            "encrypted_message" => Ok(Message::EncryptedMessage(payload)),
+            "pleroma:chat_update" => {
+                let res = serde_json::from_str::<entities::Chat>(&payload).map_err(|e| {
+                    error!("failed to parse chat: {}\n{}", e.to_string(), &payload);
+                    e
+                })?;
+                Ok(Message::ChatUpdate(res.into()))
+            }
+            event => {
+                debug!("Unknown event is received: {}", event);
+                Ok(Message::Unknown {
+                    event: event.to_string(),
+                    payload,
+                })
+            }
+        }
+    }
+
+    async fn connect(&self, url: &str, tx: mpsc::Sender<StreamEvent>, token: CancellationToken) {
+        if self.transport == Transport::Sse {
+            return self.sse().connect(tx, token).await;
+        }
+        let mut fallback = self.transport == Transport::Auto;
+        let mut reconnection = Reconnection::new(url, &self.reconnect_policy, &tx, &token);
         loop {
-            match self.do_connect(url, &callback).await {
-                Ok(()) => {
-                    warn!("connection for {} is closed, reconnecting...", url);
-                    thread::sleep(Duration::from_millis(RECONNECT_INTERVAL));
-                    continue;
+            let result = self.do_connect(url, &tx, &token, &mut reconnection).await;
+            if fallback {
+                if let Err(ConnectionError::Connection) = result {
+                    info!("Failed to connect to {}, falling back to SSE", url);
+                    return self.sse().connect(tx, token).await;
                 }
-                Err(err) => match err.kind {
-                    InnerKind::ConnectionError
-                    | InnerKind::SocketReadError
-                    | InnerKind::UnusualSocketCloseError
-                    | InnerKind::TimeoutError => {
-                        thread::sleep(Duration::from_millis(RECONNECT_INTERVAL));
-                        info!("Reconnecting to {}", url);
-                        continue;
-                    }
-                    InnerKind::UnauthorizedError => {
-                        info!("Unauthorized so give up");
-                        return;
-                    }
-                },
+                fallback = false;
+            }
+            if !reconnection.retry(result).await {
+                return;
             }
         }
     }
@@ -173,82 +194,32 @@ impl WebSocket {
     async fn do_connect(
         &self,
         url: &str,
-        callback: &Box<
-            dyn Fn(Message) -> Pin<Box<dyn Future<Output = ()> + Send>> + Send + Sync + '_,
-        >,
-    ) -> Result<(), InnerError> {
-        let mut req = Url::parse(url)
-            .unwrap()
-            .into_client_request()
-            .map_err(|e| {
-                error!("Failed to parse url: {}", e);
-                InnerError::new(InnerKind::ConnectionError)
-            })?;
-        req.headers_mut()
-            .insert("User-Agent", self.user_agent.parse().unwrap());
-        let connector = crate::tls::build_connector();
-        let (mut socket, response) =
-            connect_async_tls_with_config(req, None, false, connector).await.map_err(|e| {
-            error!("Failed to connect: {}", e);
-            match e {
-                WebSocketError::Http(response) => match response.status() {
-                    StatusCode::UNAUTHORIZED => InnerError::new(InnerKind::UnauthorizedError),
-                    _ => InnerError::new(InnerKind::ConnectionError),
-                },
-                _ => InnerError::new(InnerKind::ConnectionError),
-            }
-        })?;
-
-        debug!("Connected to {}", url);
-        debug!("Response HTTP code: {}", response.status());
-        debug!("Response contains the following headers:");
-        for (ref header, _value) in response.headers() {
-            debug!("* {}", header);
-        }
+        tx: &mpsc::Sender<StreamEvent>,
+        token: &CancellationToken,
+        reconnection: &mut Reconnection<'_, Message>,
+    ) -> Result<(), ConnectionError> {
+        let Some(mut socket) = web_socket::open(url, &self.user_agent, token).await? else {
+            return Ok(());
+        };
+        reconnection.connected().await;
 
         loop {
-            let res = tokio::time::timeout(
-                Duration::from_secs(READ_MESSAGE_TIMEOUT_SECONDS),
-                socket.next(),
-            )
-            .await
-            .map_err(|e| {
-                error!("Timeout reading message: {}", e);
-                InnerError::new(InnerKind::TimeoutError)
-            })?;
-            let Some(r) = res else {
-                warn!("WebSocket stream has ended");
-                return Err(InnerError::new(InnerKind::SocketReadError));
-            };
-            let msg = r.map_err(|e| {
-                error!("Failed to read message: {}", e);
-                InnerError::new(InnerKind::SocketReadError)
-            })?;
-            if msg.is_ping() {
-                let _ = socket
-                    .send(WebSocketMessage::Pong(Vec::<u8>::new().into()))
-                    .await
-                    .map_err(|e| {
-                        error!("{:#?}", e);
-                        e
-                    });
-            }
-            if msg.is_close() {
-                let _ = socket.close(None).await.map_err(|e| {
-                    error!("{:#?}", e);
-                    e
-                });
-                if let WebSocketMessage::Close(Some(close)) = msg {
-                    warn!("Connection to {} is closed because {}", url, close.code);
-                    if close.code != CloseCode::Normal {
-                        return Err(InnerError::new(InnerKind::UnusualSocketCloseError));
-                    }
+            let msg = tokio::select! {
+                _ = token.cancelled() => {
+                    web_socket::close(&mut socket).await;
+                    return Ok(());
                 }
+                msg = web_socket::receive(&mut socket, url) => msg?,
+            };
+            let Some(msg) = msg else {
                 return Ok(());
-            }
-            match self.parse(msg) {
+            };
+            match Self::parse(msg) {
                 Ok(message) => {
-                    callback(message).await;
+                    if tx.send(StreamEvent::Message(message)).await.is_err() {
+                        // The receiver has been dropped, so nobody listens this connection.
+                        token.cancel();
+                    }
                 }
                 Err(err) => {
                     warn!("{}", err);
@@ -256,6 +227,41 @@ impl WebSocket {
             }
         }
     }
+
+    fn sse(&self) -> Sse {
+        // SSE endpoints are served by HTTP at the path which corresponds to the stream name.
+        let base = match self.url.split_once("://") {
+            Some(("wss", rest)) => format!("https://{}", rest),
+            Some(("ws", rest)) => format!("http://{}", rest),
+            _ => self.url.clone(),
+        };
+        let mut url = format!("{}/{}", base, self.stream.replace(':', "/"));
+        if let Some(params) = &self.params {
+            if !params.is_empty() {
+                url = url + "?" + params.join("&").as_str();
+            }
+        }
+        Sse::new(
+            url,
+            self.access_token.clone(),
+            self.client.clone(),
+            self.reconnect_policy.clone(),
+            Self::parse_event,
+        )
+    }
+
+    fn streaming_url(&self) -> String {
+        let mut parameter = Vec::<String>::from([format!("stream={}", self.stream)]);
+        if let Some(access_token) = &self.access_token {
+            parameter.push(format!("access_token={}", access_token));
+        }
+        if let Some(mut params) = self.params.clone() {
+            parameter.append(&mut params);
+        }
+        let mut url = self.url.clone();
+        url = url + "?" + parameter.join("&").as_str();
+        url
+    }
 }
 
 #[async_trait]
@@ -273,51 +279,66 @@ impl Streaming for WebSocket {
                 + 'async_trait,
         >,
     ) {
-        let mut parameter = Vec::<String>::from([format!("stream={}", self.stream)]);
-        if let Some(access_token) = &self.access_token {
-            parameter.push(format!("access_token={}", access_token));
-        }
-        if let Some(mut params) = self.params.clone() {
-            parameter.append(&mut params);
-        }
-        let mut url = self.url.clone();
-        url = url + "?" + parameter.join("&").as_str();
+        let url = self.streaming_url();
+        let (tx, mut rx) = mpsc::channel(EVENT_CHANNEL_CAPACITY);
+        let token = CancellationToken::new();
 
-        self.connect(url.as_str(), callback).await;
+        tokio::join!(self.connect(url.as_str(), tx, token), async {
+            while let Some(event) = rx.recv().await {
+                if let StreamEvent::Message(message) = event {
+                    callback(message).await;
+                }
+            }
+        });
     }
-}
 
-#[derive(thiserror::Error)]
-#[error("{kind}")]
-struct InnerError {
-    kind: InnerKind,
-}
+    fn stream(&self) -> (EventStream, StreamHandle) {
+        let url = self.streaming_url();
+        let (tx, rx) = mpsc::channel(EVENT_CHANNEL_CAPACITY);
+        let token = CancellationToken::new();
+        let ws = self.clone();
+        let connection_token = token.clone();
+        tokio::spawn(async move {
+            ws.connect(url.as_str(), tx, connection_token).await;
+        });
 
-#[derive(Debug, thiserror::Error)]
-enum InnerKind {
-    #[error("connection error")]
-    ConnectionError,
-    #[error("socket read error")]
-    SocketReadError,
-    #[error("unusual socket close error")]
-    UnusualSocketCloseError,
-    #[error("timeout error")]
-    TimeoutError,
-    #[error("unauthorized error")]
-    UnauthorizedError,
-}
+        (event_stream(rx), StreamHandle::new(token))
+    }
+
+    fn set_reconnect_policy(&mut self, policy: ReconnectPolicy) {
+        self.reconnect_policy = policy;
+    }
 
-impl InnerError {
-    pub fn new(kind: InnerKind) -> Self {
-        Self { kind }
+    fn set_transport(&mut self, transport: Transport) {
+        self.transport = transport;
     }
+
+    fn set_poll_interval(&mut self, _interval: Duration) {}
 }
 
-impl fmt::Debug for InnerError {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        let mut builder = f.debug_struct("megalodon::pleroma::web_socket::InnerError");
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::http::ClientBuilder;
+    use crate::streaming::testing;
+
+    fn new_web_socket(url: String) -> WebSocket {
+        let client = ClientBuilder::default().build(None).unwrap();
+        WebSocket::new(url, String::from("user"), None, None, None, client)
+    }
+
+    #[tokio::test]
+    async fn test_stream_reconnect_lifecycle() {
+        testing::assert_reconnect_lifecycle(new_web_socket).await;
+    }
+
+    #[tokio::test]
+    async fn test_stream_handle_close() {
+        testing::assert_handle_close(new_web_socket).await;
+    }
 
-        builder.field("kind", &self.kind);
-        builder.finish()
+    #[tokio::test]
+    async fn test_stream_receiver_dropped() {
+        testing::assert_receiver_dropped(new_web_socket).await;
     }
 }
@@ -0,0 +1,636 @@
+//! Polling modules, which emulate streaming for SNS without streaming API
+use std::collections::{HashSet, VecDeque};
+use std::fmt;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::time::Duration;
+
+use crate::entities;
+use crate::error::{Error, Kind};
+use crate::megalodon::{
+    GetArrayWithSinceOptions, GetNotificationsInputOptions, GetTimelineOptions,
+    GetTimelineOptionsWithLocal,
+};
+use crate::response::{compare_ids, Response};
+use crate::streaming::{
+    event_stream, EventStream, Message, ReconnectPolicy, StreamEvent, StreamHandle, Streaming,
+    Transport, EVENT_CHANNEL_CAPACITY,
+};
+use crate::Megalodon;
+use async_trait::async_trait;
+use tokio::sync::mpsc;
+use tokio_util::sync::CancellationToken;
+use tracing::{info, warn};
+
+/// Default interval between polls.
+pub const DEFAULT_INTERVAL: Duration = Duration::from_secs(15);
+// Number of IDs which are remembered to de-duplicate items.
+const SEEN_CAPACITY: usize = 1000;
+// Maximum number of pages which are fetched in a poll.
+const MAX_PAGES: usize = 10;
+
+/// Timelines which can be polled.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Source {
+    /// Home timeline and notifications.
+    User,
+    /// Federated timeline.
+    Public,
+    /// Local timeline.
+    Local,
+    /// Conversations.
+    Direct,
+    /// Statuses with the hashtag.
+    Tag(String),
+    /// Statuses of the list.
+    List(String),
+}
+
+/// Streaming which polls timelines of the client.
+///
+/// The first poll remembers the latest items, and the following polls emit only new items in chronological order.
+///
+/// ```rust
+/// # use megalodon;
+/// # use megalodon::polling::{Polling, Source};
+/// # use megalodon::Streaming;
+/// # use std::sync::Arc;
+/// # use std::time::Duration;
+/// #
+/// # async fn run() -> Result<(), megalodon::error::Error> {
+/// let client = megalodon::generator(
+///   megalodon::SNS::Friendica,
+///   String::from("https://friendica.example"),
+///   Some(String::from("your access token")),
+///   None,
+/// )?;
+/// let streaming = Polling::new(Arc::from(client), Source::User).with_interval(Duration::from_secs(30));
+/// let (events, handle) = streaming.stream();
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone)]
+pub struct Polling {
+    client: Arc<dyn Megalodon + Send + Sync>,
+    source: Source,
+    interval: Duration,
+    reconnect_policy: ReconnectPolicy,
+}
+
+impl Polling {
+    /// Create a new [`Polling`] with [`DEFAULT_INTERVAL`].
+    pub fn new(client: Arc<dyn Megalodon + Send + Sync>, source: Source) -> Self {
+        Self {
+            client,
+            source,
+            interval: DEFAULT_INTERVAL,
+            reconnect_policy: ReconnectPolicy::default(),
+        }
+    }
+
+    /// Set the interval between polls.
+    pub fn with_interval(mut self, interval: Duration) -> Self {
+        self.interval = interval;
+        self
+    }
+
+    async fn connect(&self, tx: mpsc::Sender<StreamEvent>, token: CancellationToken) {
+        let mut state = PollState::default();
+        let mut connected = false;
+        let mut attempt: u32 = 0;
+        loop {
+            let polled = tokio::select! {
+                _ = token.cancelled() => None,
+                polled = self.poll(&mut state) => Some(polled),
+            };
+            let wait = match polled {
+                None => Duration::ZERO,
+                Some(Ok(messages)) => {
+                    attempt = 0;
+                    if !connected {
+                        connected = true;
+                        if tx.send(StreamEvent::Connected).await.is_err() {
+                            return;
+                        }
+                    }
+                    for message in messages {
+                        if tx.send(StreamEvent::Message(message)).await.is_err() {
+                            // The receiver has been dropped, so nobody listens this polling.
+                            return;
+                        }
+                    }
+                    self.interval
+                }
+                Some(Err(err)) => {
+                    warn!("Failed to poll: {}", err);
+                    connected = false;
+                    if tx
+                        .send(StreamEvent::Disconnected {
+                            reason: err.to_string(),
+                        })
+                        .await
+                        .is_err()
+                    {
+                        return;
+                    }
+                    if is_fatal(&err) {
+                        info!("Polling can not be recovered so give up");
+                        self.reconnect_policy.give_up(attempt);
+                        return;
+                    }
+                    attempt += 1;
+                    if self.reconnect_policy.is_exhausted(attempt) {
+                        info!("Polling failed {} times so give up", attempt - 1);
+                        self.reconnect_policy.give_up(attempt - 1);
+                        return;
+                    }
+                    if tx
+                        .send(StreamEvent::Reconnecting { attempt })
+                        .await
+                        .is_err()
+                    {
+                        return;
+                    }
+                    self.reconnect_policy.interval(attempt)
+                }
+            };
+            tokio::select! {
+                _ = token.cancelled() => {
+                    let _ = tx
+                        .send(StreamEvent::Disconnected {
+                            reason: String::from("closed by the client"),
+                        })
+                        .await;
+                    return;
+                }
+                _ = tokio::time::sleep(wait) => {}
+            }
+        }
+    }
+
+    async fn poll(&self, state: &mut PollState) -> Result<Vec<Message>, Error> {
+        let mut messages = Vec::new();
+        match &self.source {
+            Source::User => {
+                let statuses = state
+                    .statuses
+                    .fetch(
+                        |min_id| async move {
+                            let options = GetTimelineOptionsWithLocal {
+                                min_id,
+                                ..Default::default()
+                            };
+                            self.client.get_home_timeline(Some(&options)).await
+                        },
+                        |s| s.id.clone(),
+                    )
+                    .await?;
+                let notifications = state
+                    .notifications
+                    .fetch(
+                        |min_id| async move {
+                            let options = GetNotificationsInputOptions {
+                                min_id,
+                                ..Default::default()
+                            };
+                            self.client.get_notifications(Some(&options)).await
+                        },
+                        |n| n.id.clone(),
+                    )
+                    .await?;
+
+                // Both are fetched before updating the state, not to lose statuses when notifications fail.
+                let statuses = state.statuses.accept(statuses, |s| s.id.clone());
+                let notifications = state.notifications.accept(notifications, |n| n.id.clone());
+                messages.extend(merge_chronological(statuses, notifications));
+            }
+            Source::Public | Source::Local => {
+                let local = self.source == Source::Local;
+                let statuses = state
+                    .statuses
+                    .fetch(
+                        |min_id| async move {
+                            let options = GetTimelineOptions {
+                                min_id,
+                                ..Default::default()
+                            };
+                            match local {
+                                true => self.client.get_local_timeline(Some(&options)).await,
+                                false => self.client.get_public_timeline(Some(&options)).await,
+                            }
+                        },
+                        |s| s.id.clone(),
+                    )
+                    .await?;
+                let statuses = state.statuses.accept(statuses, |s| s.id.clone());
+                messages.extend(statuses.into_iter().map(Message::Update));
+            }
+            Source::Tag(tag) => {
+                let statuses = state
+                    .statuses
+                    .fetch(
+                        |min_id| async move {
+                            let options = GetTimelineOptionsWithLocal {
+                                min_id,
+                                ..Default::default()
+                            };
+                            self.client
+                                .get_tag_timeline(tag.clone(), Some(&options))
+                                .await
+                        },
+                        |s| s.id.clone(),
+                    )
+                    .await?;
+                let statuses = state.statuses.accept(statuses, |s| s.id.clone());
+                messages.extend(statuses.into_iter().map(Message::Update));
+            }
+            Source::List(list_id) => {
+                let statuses = state
+                    .statuses
+                    .fetch(
+                        |min_id| async move {
+                            let options = GetArrayWithSinceOptions {
+                                min_id,
+                                ..Default::default()
+                            };
+                            self.client
+                                .get_list_timeline(list_id.clone(), Some(&options))
+                                .await
+                        },
+                        |s| s.id.clone(),
+                    )
+                    .await?;
+                let statuses = state.statuses.accept(statuses, |s| s.id.clone());
+                messages.extend(statuses.into_iter().map(Message::Update));
+            }
+            Source::Direct => {
+                // Conversations are updated in place, so they are identified by the last status.
+                let res = self.client.get_conversation_timeline(None).await?;
+                let conversations =
+                    state
+                        .conversations
+                        .accept(res.json, |c: &entities::Conversation| {
+                            match &c.last_status {
+                                Some(status) => status.id.clone(),
+                                None => c.id.clone(),
+                            }
+                        });
+                messages.extend(conversations.into_iter().map(Message::Conversation));
+            }
+        }
+        Ok(messages)
+    }
+}
+
+impl fmt::Debug for Polling {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("megalodon::polling::Polling")
+            .field("source", &self.source)
+            .field("interval", &self.interval)
+            .field("reconnect_policy", &self.reconnect_policy)
+            .finish()
+    }
+}
+
+#[async_trait]
+impl Streaming for Polling {
+    fn is_supported(&self) -> bool {
+        true
+    }
+
+    async fn listen(
+        &self,
+        callback: Box<
+            dyn Fn(Message) -> Pin<Box<dyn Future<Output = ()> + Send>>
+                + Send
+                + Sync
+                + 'async_trait,
+        >,
+    ) {
+        let (tx, mut rx) = mpsc::channel(EVENT_CHANNEL_CAPACITY);
+        let token = CancellationToken::new();
+
+        tokio::join!(self.connect(tx, token), async {
+            while let Some(event) = rx.recv().await {
+                if let StreamEvent::Message(message) = event {
+                    callback(message).await;
+                }
+            }
+        });
+    }
+
+    fn stream(&self) -> (EventStream, StreamHandle) {
+        let (tx, rx) = mpsc::channel(EVENT_CHANNEL_CAPACITY);
+        let token = CancellationToken::new();
+        let polling = self.clone();
+        let connection_token = token.clone();
+        tokio::spawn(async move {
+            polling.connect(tx, connection_token).await;
+        });
+
+        (event_stream(rx), StreamHandle::new(token))
+    }
+
+    fn set_reconnect_policy(&mut self, policy: ReconnectPolicy) {
+        self.reconnect_policy = policy;
+    }
+
+    fn set_transport(&mut self, _transport: Transport) {}
+
+    fn set_poll_interval(&mut self, interval: Duration) {
+        self.interval = interval;
+    }
+}
+
+/// Whether the error is not recovered by retrying.
+fn is_fatal(err: &Error) -> bool {
+    match err {
+        Error::OwnError(own) => {
+            matches!(own.kind, Kind::NoImplementedError | Kind::UnauthorizedError)
+        }
+        _ => false,
+    }
+}
+
+/// Merge statuses and notifications, each of which is in chronological order, into messages in chronological order.
+fn merge_chronological(
+    statuses: Vec<entities::Status>,
+    notifications: Vec<entities::Notification>,
+) -> Vec<Message> {
+    let mut timed = statuses
+        .into_iter()
+        .map(|s| (s.created_at, Message::Update(s)))
+        .chain(
+            notifications
+                .into_iter()
+                .map(|n| (n.created_at, Message::Notification(n))),
+        )
+        .collect::<Vec<_>>();
+    // The sort is stable, so items at the same time keep the order of each.
+    timed.sort_by_key(|(created_at, _)| *created_at);
+    timed.into_iter().map(|(_, message)| message).collect()
+}
+
+#[derive(Debug, Default)]
+struct PollState {
+    statuses: Feed,
+    notifications: Feed,
+    conversations: Feed,
+}
+
+/// Progress of a polled timeline.
+#[derive(Debug, Default)]
+struct Feed {
+    since_id: Option<String>,
+    seen: HashSet<String>,
+    order: VecDeque<String>,
+    initialized: bool,
+}
+
+impl Feed {
+    /// Fetch items which are newer than the last poll.
+    /// Pages are followed with `min_id` until an empty page, not to lose items when many items arrive between polls.
+    async fn fetch<T, F, Fut>(&self, fetch: F, id: impl Fn(&T) -> String) -> Result<Vec<T>, Error>
+    where
+        F: Fn(Option<String>) -> Fut,
+        Fut: Future<Output = Result<Response<Vec<T>>, Error>>,
+    {
+        let Some(mut min_id) = self.since_id.clone().filter(|_| self.initialized) else {
+            return Ok(fetch(None).await?.json);
+        };
+        let mut items = Vec::new();
+        for _ in 0..MAX_PAGES {
+            let page = fetch(Some(min_id.clone())).await?.json;
+            let newest = page.iter().map(&id).max_by(|a, b| compare_ids(a, b));
+            items.extend(page);
+            match newest {
+                // Stop when the cursor does not move, because the server may ignore min_id.
+                Some(newest) if compare_ids(&newest, &min_id).is_gt() => min_id = newest,
+                _ => break,
+            }
+        }
+        Ok(items)
+    }
+
+    /// Return items which have not been seen yet in chronological order.
+    /// All items of the first call are regarded as seen, because they are not new.
+    fn accept<T>(&mut self, mut items: Vec<T>, id: impl Fn(&T) -> String) -> Vec<T> {
+        items.sort_by(|a, b| compare_ids(&id(a), &id(b)));
+        if let Some(newest) = items.last() {
+            let newest = id(newest);
+            let newer = match &self.since_id {
+                Some(since_id) => compare_ids(&newest, since_id).is_gt(),
+                None => true,
+            };
+            if newer {
+                self.since_id = Some(newest);
+            }
+        }
+        let initialized = self.initialized;
+        self.initialized = true;
+        items.retain(|item| self.remember(id(item)));
+        if !initialized {
+            return Vec::new();
+        }
+        items
+    }
+
+    fn remember(&mut self, id: String) -> bool {
+        if !self.seen.insert(id.clone()) {
+            return false;
+        }
+        self.order.push_back(id);
+        if self.order.len() > SEEN_CAPACITY {
+            if let Some(oldest) = self.order.pop_front() {
+                self.seen.remove(&oldest);
+            }
+        }
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{DateTime, Utc};
+    use reqwest::header::HeaderMap;
+    use serde_json::json;
+    use std::sync::Mutex;
+
+    fn status(id: &str, created_at: &str) -> entities::Status {
+        serde_json::from_value(json!({
+            "id": id,
+            "uri": format!("https://example.com/statuses/{}", id),
+            "account": {
+                "id": "1",
+                "username": "h3poteto",
+                "acct": "h3poteto",
+                "display_name": "h3poteto",
+                "locked": false,
+                "created_at": "2023-01-01T00:00:00Z",
+                "followers_count": 0,
+                "following_count": 0,
+                "statuses_count": 0,
+                "note": "",
+                "url": "https://example.com/@h3poteto",
+                "avatar": "",
+                "avatar_static": "",
+                "header": "",
+                "header_static": "",
+                "emojis": [],
+                "fields": [],
+                "bot": false,
+            },
+            "content": "",
+            "created_at": created_at,
+            "emojis": [],
+            "replies_count": 0,
+            "reblogs_count": 0,
+            "favourites_count": 0,
+            "sensitive": false,
+            "spoiler_text": "",
+            "visibility": "public",
+            "media_attachments": [],
+            "mentions": [],
+            "tags": [],
+            "quote_approval": {"automatic": [], "manual": [], "current_user": "denied"},
+            "filtered": [],
+        }))
+        .unwrap()
+    }
+
+    fn notification(id: &str, created_at: &str) -> entities::Notification {
+        entities::Notification {
+            account: None,
+            created_at: created_at.parse::<DateTime<Utc>>().unwrap(),
+            id: id.to_string(),
+            status: None,
+            reaction: None,
+            target: None,
+            r#type: entities::notification::NotificationType::Mention,
+        }
+    }
+
+    fn ids(ids: &[&str]) -> Vec<String> {
+        ids.iter().map(|id| id.to_string()).collect()
+    }
+
+    #[test]
+    fn test_feed_accept() {
+        let mut feed = Feed::default();
+
+        let items = feed.accept(ids(&["10", "9"]), |s| s.clone());
+        assert!(items.is_empty());
+        assert_eq!(feed.since_id.as_deref(), Some("10"));
+
+        let items = feed.accept(ids(&["12", "10", "11"]), |s| s.clone());
+        assert_eq!(items, ids(&["11", "12"]));
+        assert_eq!(feed.since_id.as_deref(), Some("12"));
+
+        let items = feed.accept(ids(&["12"]), |s| s.clone());
+        assert!(items.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_feed_fetch_gap() {
+        let mut feed = Feed::default();
+        feed.accept(ids(&["2", "1"]), |s| s.clone());
+
+        // The server returns 2 items just after min_id in descending order.
+        let timeline = ids(&["1", "2", "3", "4", "5", "6", "7"]);
+        let requested = Mutex::new(Vec::new());
+        let items = feed
+            .fetch(
+                |min_id| {
+                    requested.lock().unwrap().push(min_id.clone());
+                    let min_id = min_id.unwrap();
+                    let mut page = timeline
+                        .iter()
+                        .filter(|id| compare_ids(id, &min_id).is_gt())
+                        .take(2)
+                        .cloned()
+                        .collect::<Vec<_>>();
+                    page.reverse();
+                    async move { Ok(Response::new(page, 200, String::new(), HeaderMap::new())) }
+                },
+                |s| s.clone(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(
+            requested.into_inner().unwrap(),
+            vec![
+                Some(String::from("2")),
+                Some(String::from("4")),
+                Some(String::from("6")),
+                Some(String::from("7")),
+            ]
+        );
+        let items = feed.accept(items, |s| s.clone());
+        assert_eq!(items, ids(&["3", "4", "5", "6", "7"]));
+        assert_eq!(feed.since_id.as_deref(), Some("7"));
+    }
+
+    #[tokio::test]
+    async fn test_feed_fetch_ignored_min_id() {
+        let mut feed = Feed::default();
+        feed.accept(ids(&["2", "1"]), |s| s.clone());
+
+        // The server ignores min_id and returns the latest items every time.
+        let items = feed
+            .fetch(
+                |_| async {
+                    Ok(Response::new(
+                        ids(&["3", "2"]),
+                        200,
+                        String::new(),
+                        HeaderMap::new(),
+                    ))
+                },
+                |s| s.clone(),
+            )
+            .await
+            .unwrap();
+        let items = feed.accept(items, |s| s.clone());
+        assert_eq!(items, ids(&["3"]));
+    }
+
+    #[test]
+    fn test_merge_chronological() {
+        let messages = merge_chronological(
+            vec![
+                status("10", "2024-01-01T00:00:01Z"),
+                status("11", "2024-01-01T00:00:03Z"),
+            ],
+            vec![
+                notification("20", "2024-01-01T00:00:02Z"),
+                notification("21", "2024-01-01T00:00:04Z"),
+            ],
+        );
+        let ids = messages
+            .iter()
+            .map(|message| match message {
+                Message::Update(status) => status.id.clone(),
+                Message::Notification(notification) => notification.id.clone(),
+                _ => String::new(),
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(ids, vec!["10", "20", "11", "21"]);
+    }
+
+    #[tokio::test]
+    async fn test_set_poll_interval() {
+        let client = crate::generator(
+            crate::SNS::Friendica,
+            String::from("https://friendica.example"),
+            None,
+            None,
+        )
+        .unwrap();
+        let mut polling = Polling::new(Arc::from(client), Source::User);
+        polling.set_poll_interval(Duration::from_secs(60));
+        assert_eq!(polling.interval, Duration::from_secs(60));
+    }
+}
@@ -1,7 +1,10 @@
 //! Response modules
-use reqwest::header::HeaderMap;
+use chrono::{DateTime, TimeZone, Utc};
+use reqwest::header::{HeaderMap, LINK};
 use serde::de::DeserializeOwned;
+use std::cmp::Ordering;
 use std::fmt::Debug;
+use url::Url;
 
 /// Response struct for API response.
 #[derive(Debug, Clone)]
@@ -14,16 +17,24 @@ pub struct Response<T> {
     pub status_text: String,
     /// Headers of the response.
     pub header: HeaderMap,
+    /// Pagination cursors of the response.
+    pub pagination: Pagination,
+    /// Rate limit of the endpoint, if the server returns it.
+    pub rate_limit: Option<RateLimit>,
 }
 
 impl<T> Response<T> {
     /// Create a new Response struct.
     pub fn new(json: T, status: u16, status_text: String, header: HeaderMap) -> Response<T> {
+        let pagination = Pagination::from_header(&header);
+        let rate_limit = RateLimit::from_header(&header);
         Self {
             json,
             status,
             status_text,
             header,
+            pagination,
+            rate_limit,
         }
     }
 
@@ -36,12 +47,18 @@ impl<T> Response<T> {
         let status_code = response.status();
         let json = response.json::<T>().await?;
 
-        Ok(Self {
+        Ok(Self::new(
             json,
-            status: status_code.as_u16(),
-            status_text: status_code.as_str().to_string(),
+            status_code.as_u16(),
+            status_code.as_str().to_string(),
             header,
-        })
+        ))
+    }
+
+    /// Replace pagination cursors of the response.
+    pub fn with_pagination(mut self, pagination: Pagination) -> Response<T> {
+        self.pagination = pagination;
+        self
     }
 
     /// Get json object.
@@ -52,3 +69,247 @@ impl<T> Response<T> {
         self.json.clone()
     }
 }
+
+/// Pagination cursors of the response.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct Pagination {
+    /// Cursor for the next page, which has older results.
+    pub next: Option<Cursor>,
+    /// Cursor for the previous page, which has newer results.
+    pub prev: Option<Cursor>,
+}
+
+/// Pagination cursor.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct Cursor {
+    /// Raw URL of the page, if the server returns it.
+    pub url: Option<String>,
+    /// Return results older than this ID.
+    pub max_id: Option<String>,
+    /// Return results immediately newer than this ID.
+    pub min_id: Option<String>,
+    /// Return results newer than this ID.
+    pub since_id: Option<String>,
+}
+
+/// Input options which can receive a pagination cursor.
+pub trait CursorOptions {
+    /// Replace the position of the options with the cursor.
+    fn set_cursor(&mut self, cursor: &Cursor);
+}
+
+impl Pagination {
+    /// Parse `next` and `prev` of Link header.
+    pub fn from_header(header: &HeaderMap) -> Pagination {
+        Self {
+            next: parse_link_header(header, "next").map(Cursor::from_url),
+            prev: parse_link_header(header, "prev").map(Cursor::from_url),
+        }
+    }
+
+    /// Derive cursors from the IDs of the page, for servers which do not return Link header.
+    pub(crate) fn from_ids<'a>(ids: impl Iterator<Item = &'a str>) -> Pagination {
+        let ids: Vec<&str> = ids.collect();
+        let oldest = ids.iter().min_by(|a, b| compare_ids(a, b));
+        let newest = ids.iter().max_by(|a, b| compare_ids(a, b));
+        Self {
+            next: oldest.map(|id| Cursor {
+                max_id: Some(id.to_string()),
+                ..Default::default()
+            }),
+            // Some Firefish endpoints accept only sinceId, so both are set.
+            prev: newest.map(|id| Cursor {
+                min_id: Some(id.to_string()),
+                since_id: Some(id.to_string()),
+                ..Default::default()
+            }),
+        }
+    }
+}
+
+impl Cursor {
+    fn from_url(url: Url) -> Cursor {
+        let mut cursor = Cursor {
+            url: Some(url.to_string()),
+            ..Default::default()
+        };
+        for (key, value) in url.query_pairs() {
+            match key.as_ref() {
+                "max_id" => cursor.max_id = Some(value.to_string()),
+                "min_id" => cursor.min_id = Some(value.to_string()),
+                "since_id" => cursor.since_id = Some(value.to_string()),
+                _ => {}
+            }
+        }
+        cursor
+    }
+
+    /// Whether the cursor points to any position.
+    pub fn is_empty(&self) -> bool {
+        self.max_id.is_none() && self.min_id.is_none() && self.since_id.is_none()
+    }
+
+    /// Set the cursor to the input options.
+    pub fn apply<O: CursorOptions>(&self, options: &mut O) {
+        options.set_cursor(self)
+    }
+}
+
+/// Rate limit which is returned in `X-RateLimit-*` headers.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RateLimit {
+    /// Number of requests which are permitted in the period.
+    pub limit: u64,
+    /// Number of requests which remain in the period.
+    pub remaining: u64,
+    /// When the period is reset.
+    pub reset: Option<DateTime<Utc>>,
+}
+
+impl RateLimit {
+    /// Parse `X-RateLimit-Limit`, `X-RateLimit-Remaining` and `X-RateLimit-Reset` headers.
+    pub fn from_header(header: &HeaderMap) -> Option<RateLimit> {
+        let value = |name: &str| header.get(name).and_then(|v| v.to_str().ok());
+        let limit = value("x-ratelimit-limit")?.trim().parse::<u64>().ok()?;
+        let remaining = value("x-ratelimit-remaining")?.trim().parse::<u64>().ok()?;
+        let reset = value("x-ratelimit-reset").and_then(|v| {
+            let v = v.trim();
+            // Mastodon returns ISO 8601, but some servers return UNIX time.
+            match v.parse::<i64>() {
+                Ok(timestamp) => Utc.timestamp_opt(timestamp, 0).single(),
+                Err(_) => DateTime::parse_from_rfc3339(v)
+                    .ok()
+                    .map(|d| d.with_timezone(&Utc)),
+            }
+        });
+        Some(Self {
+            limit,
+            remaining,
+            reset,
+        })
+    }
+
+    /// Whether no request remains until the reset time.
+    pub fn is_exhausted(&self) -> bool {
+        self.remaining == 0
+    }
+}
+
+/// Compare IDs in chronological order.
+pub(crate) fn compare_ids(a: &str, b: &str) -> Ordering {
+    // IDs are compared by length at first, because numeric IDs do not have a fixed width.
+    a.len().cmp(&b.len()).then(a.cmp(b))
+}
+
+fn parse_link_header(header: &HeaderMap, rel: &str) -> Option<Url> {
+    let target = format!("rel=\"{}\"", rel);
+    header
+        .get_all(LINK)
+        .iter()
+        .filter_map(|v| v.to_str().ok())
+        .flat_map(|v| v.split(','))
+        .find_map(|link| {
+            let mut parts = link.split(';').map(|p| p.trim());
+            let url = parts.next()?;
+            if !parts.any(|p| p == target) {
+                return None;
+            }
+            let url = url.strip_prefix('<')?.strip_suffix('>')?;
+            Url::parse(url).ok()
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::megalodon::{GetArrayOptions, GetTimelineOptions};
+    use reqwest::header::HeaderValue;
+
+    #[test]
+    fn test_pagination_from_header() {
+        let mut header = HeaderMap::new();
+        header.insert(LINK, HeaderValue::from_static("<https://mastodon.example/api/v1/timelines/home?max_id=109>; rel=\"next\", <https://mastodon.example/api/v1/timelines/home?min_id=120>; rel=\"prev\""));
+        let pagination = Pagination::from_header(&header);
+
+        let next = pagination.next.unwrap();
+        assert_eq!(
+            next.url.as_deref(),
+            Some("https://mastodon.example/api/v1/timelines/home?max_id=109")
+        );
+        assert_eq!(next.max_id.as_deref(), Some("109"));
+        assert_eq!(next.min_id, None);
+        let prev = pagination.prev.unwrap();
+        assert_eq!(prev.min_id.as_deref(), Some("120"));
+        assert_eq!(prev.max_id, None);
+
+        assert_eq!(
+            Pagination::from_header(&HeaderMap::new()),
+            Pagination::default()
+        );
+    }
+
+    #[test]
+    fn test_rate_limit_from_header() {
+        let mut header = HeaderMap::new();
+        header.insert("x-ratelimit-limit", HeaderValue::from_static("300"));
+        header.insert("x-ratelimit-remaining", HeaderValue::from_static("0"));
+        header.insert(
+            "x-ratelimit-reset",
+            HeaderValue::from_static("2024-05-01T10:05:00.000000Z"),
+        );
+        let rate_limit = RateLimit::from_header(&header).unwrap();
+        assert_eq!(rate_limit.limit, 300);
+        assert!(rate_limit.is_exhausted());
+        assert_eq!(
+            rate_limit.reset,
+            Some(Utc.with_ymd_and_hms(2024, 5, 1, 10, 5, 0).unwrap())
+        );
+
+        header.insert("x-ratelimit-reset", HeaderValue::from_static("1714557900"));
+        let rate_limit = RateLimit::from_header(&header).unwrap();
+        assert_eq!(
+            rate_limit.reset,
+            Some(Utc.with_ymd_and_hms(2024, 5, 1, 10, 5, 0).unwrap())
+        );
+
+        assert_eq!(RateLimit::from_header(&HeaderMap::new()), None);
+    }
+
+    #[test]
+    fn test_pagination_from_ids() {
+        let pagination = Pagination::from_ids(vec!["9hzxa0001", "9hzx90001"].into_iter());
+
+        assert_eq!(
+            pagination.next.unwrap().max_id.as_deref(),
+            Some("9hzx90001")
+        );
+        let prev = pagination.prev.unwrap();
+        assert_eq!(prev.min_id.as_deref(), Some("9hzxa0001"));
+        assert_eq!(prev.since_id.as_deref(), Some("9hzxa0001"));
+        assert_eq!(
+            Pagination::from_ids(vec![].into_iter()),
+            Pagination::default()
+        );
+    }
+
+    #[test]
+    fn test_cursor_apply() {
+        let cursor = Cursor {
+            since_id: Some("3".to_string()),
+            ..Default::default()
+        };
+        let mut options = GetTimelineOptions {
+            max_id: Some("1".to_string()),
+            limit: Some(20),
+            ..Default::default()
+        };
+        cursor.apply(&mut options);
+        assert_eq!(options.max_id, None);
+        assert_eq!(options.since_id.as_deref(), Some("3"));
+        assert_eq!(options.limit, Some(20));
+
+        let mut options = GetArrayOptions::default();
+        cursor.apply(&mut options);
+        assert_eq!(options.min_id.as_deref(), Some("3"));
+    }
+}
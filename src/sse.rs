@@ -0,0 +1,271 @@
+//! Server-Sent Events transport for streaming
+use std::time::Duration;
+
+use crate::error::Error;
+use crate::http::HttpClient;
+use crate::streaming::{ConnectionError, Message, ReconnectPolicy, Reconnection, StreamEvent};
+use futures_util::StreamExt;
+use reqwest::{header::ACCEPT, StatusCode};
+use tokio::sync::mpsc;
+use tokio_util::sync::CancellationToken;
+use tracing::{debug, error, warn};
+
+const READ_MESSAGE_TIMEOUT_SECONDS: u64 = 60;
+
+/// Parse the event name and the data of a frame into a message.
+pub(crate) type EventParser = fn(&str, String) -> Result<Message, Error>;
+
+/// Connection to a streaming endpoint which serves `text/event-stream`.
+#[derive(Debug, Clone)]
+pub(crate) struct Sse {
+    url: String,
+    access_token: Option<String>,
+    client: HttpClient,
+    reconnect_policy: ReconnectPolicy,
+    parse: EventParser,
+}
+
+impl Sse {
+    pub(crate) fn new(
+        url: String,
+        access_token: Option<String>,
+        client: HttpClient,
+        reconnect_policy: ReconnectPolicy,
+        parse: EventParser,
+    ) -> Self {
+        Self {
+            url,
+            access_token,
+            client,
+            reconnect_policy,
+            parse,
+        }
+    }
+
+    pub(crate) async fn connect(&self, tx: mpsc::Sender<StreamEvent>, token: CancellationToken) {
+        let mut reconnection = Reconnection::new(&self.url, &self.reconnect_policy, &tx, &token);
+        loop {
+            let result = self.do_connect(&tx, &token, &mut reconnection).await;
+            if !reconnection.retry(result).await {
+                return;
+            }
+        }
+    }
+
+    async fn do_connect(
+        &self,
+        tx: &mpsc::Sender<StreamEvent>,
+        token: &CancellationToken,
+        reconnection: &mut Reconnection<'_, Message>,
+    ) -> Result<(), ConnectionError> {
+        let mut req = self
+            .client
+            .get_stream(&self.url)
+            .header(ACCEPT, "text/event-stream");
+        if let Some(access_token) = &self.access_token {
+            req = req.bearer_auth(access_token);
+        }
+        let sent = tokio::select! {
+            _ = token.cancelled() => return Ok(()),
+            sent = self.client.send_stream(req) => sent,
+        };
+        let response = sent.map_err(|e| {
+            error!("Failed to connect: {}", e);
+            ConnectionError::Connection
+        })?;
+        match response.status() {
+            StatusCode::UNAUTHORIZED => return Err(ConnectionError::Unauthorized),
+            status if !status.is_success() => {
+                error!("Failed to connect: {}", status);
+                return Err(ConnectionError::Connection);
+            }
+            _ => {}
+        }
+
+        debug!("Connected to {}", self.url);
+        reconnection.connected().await;
+
+        let mut body = response.bytes_stream();
+        let mut parser = FrameParser::default();
+        loop {
+            let res = tokio::select! {
+                _ = token.cancelled() => return Ok(()),
+                res = tokio::time::timeout(
+                    Duration::from_secs(READ_MESSAGE_TIMEOUT_SECONDS),
+                    body.next(),
+                ) => res,
+            };
+            let res = res.map_err(|e| {
+                error!("Timeout reading message: {}", e);
+                ConnectionError::Timeout
+            })?;
+            let Some(r) = res else {
+                return Ok(());
+            };
+            let chunk = r.map_err(|e| {
+                error!("Failed to read message: {}", e);
+                ConnectionError::SocketRead
+            })?;
+            for frame in parser.feed(&chunk) {
+                let parsed = match frame {
+                    Frame::Comment => Ok(Message::Heartbeat()),
+                    Frame::Event { event, data } => (self.parse)(&event, data),
+                };
+                match parsed {
+                    Ok(message) => {
+                        if tx.send(StreamEvent::Message(message)).await.is_err() {
+                            // The receiver has been dropped, so nobody listens this connection.
+                            token.cancel();
+                        }
+                    }
+                    Err(err) => {
+                        warn!("{}", err);
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[derive(Debug, PartialEq)]
+enum Frame {
+    /// Comment line, which is used as a heartbeat.
+    Comment,
+    Event {
+        event: String,
+        data: String,
+    },
+}
+
+/// Split a byte stream into frames, which are separated by a blank line.
+#[derive(Debug, Default)]
+struct FrameParser {
+    buffer: Vec<u8>,
+    event: Option<String>,
+    data: Vec<String>,
+}
+
+impl FrameParser {
+    fn feed(&mut self, chunk: &[u8]) -> Vec<Frame> {
+        self.buffer.extend_from_slice(chunk);
+        let mut frames = Vec::new();
+        while let Some(pos) = self.buffer.iter().position(|b| *b == b'\n') {
+            let line: Vec<u8> = self.buffer.drain(..=pos).collect();
+            let line = String::from_utf8_lossy(&line);
+            let line = line.trim_end_matches(['\n', '\r']);
+            if line.is_empty() {
+                if !self.data.is_empty() {
+                    frames.push(Frame::Event {
+                        event: self.event.take().unwrap_or(String::from("message")),
+                        data: self.data.join("\n"),
+                    });
+                }
+                self.event = None;
+                self.data.clear();
+                continue;
+            }
+            if line.starts_with(':') {
+                frames.push(Frame::Comment);
+                continue;
+            }
+            let (field, value) = match line.split_once(':') {
+                Some((field, value)) => (field, value.strip_prefix(' ').unwrap_or(value)),
+                None => (line, ""),
+            };
+            match field {
+                "event" => self.event = Some(value.to_string()),
+                "data" => self.data.push(value.to_string()),
+                _ => {}
+            }
+        }
+        frames
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::http::ClientBuilder;
+    use reqwest::header::{HeaderMap, HeaderValue};
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+    use tokio::net::TcpListener;
+
+    #[test]
+    fn test_frame_parser() {
+        let mut parser = FrameParser::default();
+        let frames = parser.feed(b":thump\n\nevent: delete\nda");
+        assert_eq!(frames, vec![Frame::Comment]);
+
+        let frames =
+            parser.feed(b"ta: 109\r\n\r\nevent: update\ndata: {\"id\":\ndata: \"110\"}\n\n");
+        assert_eq!(
+            frames,
+            vec![
+                Frame::Event {
+                    event: String::from("delete"),
+                    data: String::from("109"),
+                },
+                Frame::Event {
+                    event: String::from("update"),
+                    data: String::from("{\"id\":\n\"110\"}"),
+                },
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_connect_with_client() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!(
+            "http://{}/api/v1/streaming/user",
+            listener.local_addr().unwrap()
+        );
+        let server = tokio::spawn(async move {
+            let (mut tcp, _) = listener.accept().await.unwrap();
+            let mut request = Vec::new();
+            let mut buf = [0u8; 1024];
+            while !request.ends_with(b"\r\n\r\n") {
+                let n = tcp.read(&mut buf).await.unwrap();
+                request.extend_from_slice(&buf[..n]);
+            }
+            tcp.write_all(
+                b"HTTP/1.1 200 OK\r\ncontent-type: text/event-stream\r\n\r\nevent: delete\ndata: 109\n\n",
+            )
+            .await
+            .unwrap();
+            let _ = tcp.read(&mut buf).await;
+            String::from_utf8(request).unwrap().to_lowercase()
+        });
+
+        let mut headers = HeaderMap::new();
+        headers.insert("x-megalodon", HeaderValue::from_static("sse"));
+        let client = ClientBuilder::new()
+            .default_headers(headers)
+            .build(None)
+            .unwrap();
+        let sse = Sse::new(
+            url,
+            Some(String::from("token")),
+            client,
+            ReconnectPolicy::default(),
+            |_, data| Ok(Message::Delete(data)),
+        );
+        let (tx, mut rx) = mpsc::channel(8);
+        let token = CancellationToken::new();
+        let connection = tokio::spawn({
+            let token = token.clone();
+            async move { sse.connect(tx, token).await }
+        });
+
+        assert!(matches!(rx.recv().await, Some(StreamEvent::Connected)));
+        assert!(
+            matches!(rx.recv().await, Some(StreamEvent::Message(Message::Delete(id))) if id == "109")
+        );
+        token.cancel();
+        connection.await.unwrap();
+
+        let request = server.await.unwrap();
+        assert!(request.contains("x-megalodon: sse"));
+        assert!(request.contains("authorization: bearer token"));
+    }
+}
@@ -1,13 +1,21 @@
 //! Streaming modules
 use crate::entities as MegalodonEntities;
 use async_trait::async_trait;
+use futures_util::Stream;
+use rand::Rng;
+use std::fmt;
 use std::future::Future;
 use std::pin::Pin;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::mpsc;
+use tokio_util::sync::CancellationToken;
+use tracing::{info, warn};
 
 /// Streaming interface to listen message.
 #[async_trait]
 pub trait Streaming {
-    /// Whether WebSocket streaming is supported or not.
+    /// Whether streaming is supported or not.
     fn is_supported(&self) -> bool;
     /// Start listening stream messages. When receive a message, the callback function will be called.
     async fn listen(
@@ -19,6 +27,319 @@ pub trait Streaming {
                 + 'async_trait,
         >,
     );
+    /// Start streaming in background, and return a stream of events with a handle to close the connection.
+    /// This method must be called in the context of tokio runtime.
+    fn stream(&self) -> (EventStream, StreamHandle);
+    /// Set the policy to reconnect when the connection is lost. It affects the following `listen` and `stream`.
+    fn set_reconnect_policy(&mut self, policy: ReconnectPolicy);
+    /// Set the transport of the connection. It is ignored when the SNS does not support the transport.
+    fn set_transport(&mut self, transport: Transport);
+    /// Set the interval between polls. It is ignored when the streaming is not emulated by polling.
+    fn set_poll_interval(&mut self, interval: Duration);
+}
+
+/// Transport of streaming connections.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Transport {
+    /// WebSocket.
+    #[default]
+    WebSocket,
+    /// HTTP Server-Sent Events.
+    Sse,
+    /// Try WebSocket at first, and fall back to Server-Sent Events when WebSocket can not be connected.
+    Auto,
+}
+
+/// Streaming interface to receive several streams on a single connection.
+pub trait MultiplexStreaming {
+    /// Start streaming in background with the initial subscriptions, and return a stream of events with a handle to control the connection.
+    /// This method must be called in the context of tokio runtime.
+    fn stream(&self, subscriptions: Vec<Subscription>) -> (MultiplexEventStream, MultiplexHandle);
+    /// Set the policy to reconnect when the connection is lost. It affects the following `stream`.
+    fn set_reconnect_policy(&mut self, policy: ReconnectPolicy);
+}
+
+/// Policy to reconnect streaming connections.
+///
+/// The default policy retries forever every 5 seconds.
+/// Set `multiplier` and `jitter` for exponential backoff with jitter.
+#[derive(Clone)]
+pub struct ReconnectPolicy {
+    /// Interval before the first reconnect attempt.
+    pub initial_interval: Duration,
+    /// Upper limit of the interval.
+    pub max_interval: Duration,
+    /// The interval is multiplied by this value for each attempt.
+    pub multiplier: f64,
+    /// Randomize the interval by this ratio, from 0.0 to 1.0. Other values are clamped, and NaN means 0.0.
+    pub jitter: f64,
+    /// Give up after this number of consecutive attempts. `None` means retry forever.
+    pub max_attempts: Option<u32>,
+    /// Called with the number of attempts when giving up reconnecting.
+    pub on_give_up: Option<Arc<dyn Fn(u32) + Send + Sync>>,
+}
+
+impl Default for ReconnectPolicy {
+    fn default() -> Self {
+        Self {
+            initial_interval: Duration::from_millis(5000),
+            max_interval: Duration::from_millis(5000),
+            multiplier: 1.0,
+            jitter: 0.0,
+            max_attempts: None,
+            on_give_up: None,
+        }
+    }
+}
+
+impl ReconnectPolicy {
+    /// Create a policy with exponential backoff, which doubles the interval up to `max_interval`.
+    pub fn exponential(initial_interval: Duration, max_interval: Duration) -> Self {
+        Self {
+            initial_interval,
+            max_interval,
+            multiplier: 2.0,
+            jitter: 0.2,
+            ..Default::default()
+        }
+    }
+
+    /// Interval before the given attempt, which starts from 1.
+    pub fn interval(&self, attempt: u32) -> Duration {
+        let exponent = attempt.saturating_sub(1).min(i32::MAX as u32) as i32;
+        let base = self.initial_interval.as_secs_f64() * self.multiplier.max(1.0).powi(exponent);
+        let base = base.min(self.max_interval.as_secs_f64());
+        // NaN is kept by clamp, and random_range panics with it.
+        let jitter = if self.jitter.is_nan() {
+            0.0
+        } else {
+            self.jitter.clamp(0.0, 1.0)
+        };
+        if jitter == 0.0 {
+            return Duration::from_secs_f64(base);
+        }
+        let ratio = rand::rng().random_range(1.0 - jitter..=1.0 + jitter);
+        Duration::from_secs_f64(base * ratio)
+    }
+
+    /// Whether the given attempt exceeds `max_attempts`.
+    pub fn is_exhausted(&self, attempt: u32) -> bool {
+        match self.max_attempts {
+            Some(max_attempts) => attempt > max_attempts,
+            None => false,
+        }
+    }
+
+    pub(crate) fn give_up(&self, attempts: u32) {
+        if let Some(on_give_up) = &self.on_give_up {
+            on_give_up(attempts)
+        }
+    }
+}
+
+impl fmt::Debug for ReconnectPolicy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("megalodon::streaming::ReconnectPolicy")
+            .field("initial_interval", &self.initial_interval)
+            .field("max_interval", &self.max_interval)
+            .field("multiplier", &self.multiplier)
+            .field("jitter", &self.jitter)
+            .field("max_attempts", &self.max_attempts)
+            .field("on_give_up", &self.on_give_up.is_some())
+            .finish()
+    }
+}
+
+/// Stream of [`StreamEvent`].
+pub type EventStream = Pin<Box<dyn Stream<Item = StreamEvent> + Send>>;
+
+/// Stream of [`StreamEvent`] whose messages are tagged with the subscription.
+pub type MultiplexEventStream = Pin<Box<dyn Stream<Item = StreamEvent<TaggedMessage>> + Send>>;
+
+/// Event definitions of [`Streaming::stream`] and [`MultiplexStreaming::stream`].
+#[derive(Debug, Clone)]
+pub enum StreamEvent<M = Message> {
+    /// Connection is established.
+    Connected,
+    /// Trying to reconnect after the connection is lost.
+    Reconnecting {
+        /// Number of attempts since the last successful connection.
+        attempt: u32,
+    },
+    /// Connection is lost or closed.
+    Disconnected {
+        /// Reason of the disconnection.
+        reason: String,
+    },
+    /// Stream message is received.
+    Message(M),
+}
+
+/// Handle to control the connection started by [`Streaming::stream`].
+#[derive(Debug, Clone)]
+pub struct StreamHandle {
+    token: CancellationToken,
+    captures: Option<mpsc::UnboundedSender<CaptureCommand>>,
+}
+
+#[derive(Debug, Clone)]
+pub(crate) enum CaptureCommand {
+    Subscribe(String),
+    Unsubscribe(String),
+}
+
+impl StreamHandle {
+    pub(crate) fn new(token: CancellationToken) -> Self {
+        Self {
+            token,
+            captures: None,
+        }
+    }
+
+    pub(crate) fn with_captures(
+        token: CancellationToken,
+        captures: mpsc::UnboundedSender<CaptureCommand>,
+    ) -> Self {
+        Self {
+            token,
+            captures: Some(captures),
+        }
+    }
+
+    /// Start receiving updates of the status, such as reactions, poll votes and deletion.
+    /// It is sent again after reconnecting.
+    /// Returns false if the SNS does not support it or the connection has been closed.
+    pub fn subscribe_status(&self, status_id: String) -> bool {
+        match &self.captures {
+            Some(captures) => captures.send(CaptureCommand::Subscribe(status_id)).is_ok(),
+            None => false,
+        }
+    }
+
+    /// Stop receiving updates of the status.
+    /// Returns false if the SNS does not support it or the connection has been closed.
+    pub fn unsubscribe_status(&self, status_id: String) -> bool {
+        match &self.captures {
+            Some(captures) => captures
+                .send(CaptureCommand::Unsubscribe(status_id))
+                .is_ok(),
+            None => false,
+        }
+    }
+
+    /// Close the connection. The stream ends after the connection is closed.
+    pub fn close(&self) {
+        self.token.cancel()
+    }
+
+    /// Whether the connection has been closed by [`StreamHandle::close`].
+    pub fn is_closed(&self) -> bool {
+        self.token.is_cancelled()
+    }
+}
+
+/// Handle to control the connection started by [`MultiplexStreaming::stream`].
+#[derive(Debug, Clone)]
+pub struct MultiplexHandle {
+    token: CancellationToken,
+    commands: mpsc::UnboundedSender<SubscriptionCommand>,
+}
+
+#[derive(Debug, Clone)]
+pub(crate) enum SubscriptionCommand {
+    Subscribe(Subscription),
+    Unsubscribe(Subscription),
+}
+
+impl MultiplexHandle {
+    pub(crate) fn new(
+        token: CancellationToken,
+        commands: mpsc::UnboundedSender<SubscriptionCommand>,
+    ) -> Self {
+        Self { token, commands }
+    }
+
+    /// Start receiving messages of the stream. It is sent again after reconnecting.
+    pub fn subscribe(&self, subscription: Subscription) {
+        let _ = self
+            .commands
+            .send(SubscriptionCommand::Subscribe(subscription));
+    }
+
+    /// Stop receiving messages of the stream.
+    pub fn unsubscribe(&self, subscription: Subscription) {
+        let _ = self
+            .commands
+            .send(SubscriptionCommand::Unsubscribe(subscription));
+    }
+
+    /// Close the connection. The stream ends after the connection is closed.
+    pub fn close(&self) {
+        self.token.cancel()
+    }
+
+    /// Whether the connection has been closed by [`MultiplexHandle::close`].
+    pub fn is_closed(&self) -> bool {
+        self.token.is_cancelled()
+    }
+}
+
+/// Streams which can be subscribed on a multiplexed connection.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Subscription {
+    /// Home timeline and notifications.
+    User,
+    /// Notifications only.
+    UserNotification,
+    /// Federated timeline.
+    Public,
+    /// Federated timeline with media only.
+    PublicMedia,
+    /// Local timeline.
+    PublicLocal,
+    /// Local timeline with media only.
+    PublicLocalMedia,
+    /// Remote timeline.
+    PublicRemote,
+    /// Remote timeline with media only.
+    PublicRemoteMedia,
+    /// Statuses with the hashtag.
+    Hashtag(String),
+    /// Local statuses with the hashtag.
+    HashtagLocal(String),
+    /// Statuses of the list.
+    List(String),
+    /// Direct messages.
+    Direct,
+}
+
+impl Subscription {
+    /// Stream name of the subscription.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Subscription::User => "user",
+            Subscription::UserNotification => "user:notification",
+            Subscription::Public => "public",
+            Subscription::PublicMedia => "public:media",
+            Subscription::PublicLocal => "public:local",
+            Subscription::PublicLocalMedia => "public:local:media",
+            Subscription::PublicRemote => "public:remote",
+            Subscription::PublicRemoteMedia => "public:remote:media",
+            Subscription::Hashtag(_) => "hashtag",
+            Subscription::HashtagLocal(_) => "hashtag:local",
+            Subscription::List(_) => "list",
+            Subscription::Direct => "direct",
+        }
+    }
+}
+
+/// Stream message with the subscription which it came from.
+#[derive(Debug, Clone)]
+pub struct TaggedMessage {
+    /// Subscription of the message. It is `None` for heartbeats and unknown streams.
+    pub stream: Option<Subscription>,
+    /// Received message.
+    pub message: Message,
 }
 
 /// Stream message definitions.
@@ -36,4 +357,373 @@ pub enum Message {
     StatusUpdate(MegalodonEntities::Status),
     /// Heartbeat for streaming connection.
     Heartbeat(),
+    /// FiltersChanged message of `filters_changed` event.
+    FiltersChanged,
+    /// Announcement message of `announcement` event.
+    Announcement(MegalodonEntities::Announcement),
+    /// AnnouncementReaction message of `announcement.reaction` event.
+    AnnouncementReaction(MegalodonEntities::announcement::AnnouncementReaction),
+    /// AnnouncementDelete message of `announcement.delete` event, which has the ID of the announcement.
+    AnnouncementDelete(String),
+    /// NotificationsMerged message of `notifications_merged` event.
+    NotificationsMerged,
+    /// EncryptedMessage message of `encrypted_message` event, which has the raw payload.
+    EncryptedMessage(String),
+    /// ChatUpdate message of `pleroma:chat_update` event.
+    ChatUpdate(MegalodonEntities::Chat),
+    /// Reacted message when a reaction is added to the status.
+    Reacted {
+        /// ID of the status.
+        status_id: String,
+        /// Name of the reaction.
+        name: String,
+        /// ID of the account which reacted.
+        account_id: String,
+    },
+    /// Unreacted message when a reaction is removed from the status.
+    Unreacted {
+        /// ID of the status.
+        status_id: String,
+        /// Name of the reaction.
+        name: String,
+        /// ID of the account which unreacted.
+        account_id: String,
+    },
+    /// PollVoted message when a vote is added to the poll of the status.
+    PollVoted {
+        /// ID of the status.
+        status_id: String,
+        /// Index of the voted option.
+        choice: u32,
+        /// ID of the account which voted.
+        account_id: String,
+    },
+    /// Message of an event which is not known by megalodon.
+    Unknown {
+        /// Name of the event.
+        event: String,
+        /// Raw payload of the event.
+        payload: String,
+    },
+}
+
+// Capacity of the channel between a connection task and the stream.
+pub(crate) const EVENT_CHANNEL_CAPACITY: usize = 64;
+
+/// Convert a receiver of events into a stream.
+pub(crate) fn event_stream<M: Send + 'static>(
+    rx: mpsc::Receiver<StreamEvent<M>>,
+) -> Pin<Box<dyn Stream<Item = StreamEvent<M>> + Send>> {
+    Box::pin(futures_util::stream::unfold(rx, |mut rx| async move {
+        rx.recv().await.map(|event| (event, rx))
+    }))
+}
+
+/// Reason why a connection of streaming is lost.
+#[derive(Debug, thiserror::Error)]
+pub(crate) enum ConnectionError {
+    #[error("connection error")]
+    Connection,
+    #[error("socket read error")]
+    SocketRead,
+    #[error("unusual socket close error")]
+    UnusualSocketClose,
+    #[error("timeout error")]
+    Timeout,
+    #[error("unauthorized error")]
+    Unauthorized,
+}
+
+/// Reconnecting loop of a connection, which sends lifecycle events and waits with [`ReconnectPolicy`].
+pub(crate) struct Reconnection<'a, M> {
+    url: &'a str,
+    policy: &'a ReconnectPolicy,
+    tx: &'a mpsc::Sender<StreamEvent<M>>,
+    token: &'a CancellationToken,
+    attempt: u32,
+}
+
+impl<'a, M> Reconnection<'a, M> {
+    pub(crate) fn new(
+        url: &'a str,
+        policy: &'a ReconnectPolicy,
+        tx: &'a mpsc::Sender<StreamEvent<M>>,
+        token: &'a CancellationToken,
+    ) -> Self {
+        Self {
+            url,
+            policy,
+            tx,
+            token,
+            attempt: 0,
+        }
+    }
+
+    /// Notify that the connection is established, and reset the number of attempts.
+    pub(crate) async fn connected(&mut self) {
+        self.attempt = 0;
+        if self.tx.send(StreamEvent::Connected).await.is_err() {
+            self.token.cancel();
+        }
+    }
+
+    /// Handle the end of a connection, and wait for the next attempt.
+    /// Returns `false` when the connection should not be retried.
+    pub(crate) async fn retry(&mut self, result: Result<(), ConnectionError>) -> bool {
+        let reason = match result {
+            Ok(()) => {
+                if self.token.is_cancelled() {
+                    info!("Connection for {} is closed by the client", self.url);
+                    let _ = self
+                        .tx
+                        .send(StreamEvent::Disconnected {
+                            reason: String::from("closed by the client"),
+                        })
+                        .await;
+                    return false;
+                }
+                warn!("connection for {} is closed, reconnecting...", self.url);
+                String::from("closed by the server")
+            }
+            Err(ConnectionError::Unauthorized) => {
+                info!("Unauthorized so give up");
+                let _ = self
+                    .tx
+                    .send(StreamEvent::Disconnected {
+                        reason: ConnectionError::Unauthorized.to_string(),
+                    })
+                    .await;
+                self.policy.give_up(self.attempt);
+                return false;
+            }
+            Err(err) => err.to_string(),
+        };
+        self.attempt += 1;
+        if self
+            .tx
+            .send(StreamEvent::Disconnected { reason })
+            .await
+            .is_err()
+        {
+            return false;
+        }
+        if self.policy.is_exhausted(self.attempt) {
+            info!(
+                "Reconnecting to {} failed {} times so give up",
+                self.url,
+                self.attempt - 1
+            );
+            self.policy.give_up(self.attempt - 1);
+            return false;
+        }
+        if self
+            .tx
+            .send(StreamEvent::Reconnecting {
+                attempt: self.attempt,
+            })
+            .await
+            .is_err()
+        {
+            return false;
+        }
+        tokio::select! {
+            _ = self.token.cancelled() => return false,
+            _ = tokio::time::sleep(self.policy.interval(self.attempt)) => {}
+        }
+        info!("Reconnecting to {}", self.url);
+        true
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod testing {
+    //! Lifecycle checks of [`Streaming::stream`], which are shared by the WebSocket of each SNS.
+    use super::*;
+    use futures_util::{SinkExt, StreamExt};
+    use std::sync::atomic::{AtomicU32, Ordering};
+    use tokio::net::TcpListener;
+    use tokio::time::timeout;
+    use tokio_tungstenite::accept_async;
+    use tokio_tungstenite::tungstenite::protocol::{
+        frame::coding::CloseCode, CloseFrame, Message as WebSocketMessage,
+    };
+
+    const TIMEOUT: Duration = Duration::from_secs(5);
+
+    async fn bind() -> (TcpListener, String) {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("ws://{}", listener.local_addr().unwrap());
+        (listener, url)
+    }
+
+    fn describe(event: &StreamEvent) -> String {
+        match event {
+            StreamEvent::Connected => String::from("connected"),
+            StreamEvent::Reconnecting { attempt } => format!("reconnecting {}", attempt),
+            StreamEvent::Disconnected { reason } => format!("disconnected: {}", reason),
+            StreamEvent::Message(_) => String::from("message"),
+        }
+    }
+
+    /// The server closes the connection and goes away, so the reconnection fails and gives up.
+    pub(crate) async fn assert_reconnect_lifecycle<S: Streaming>(build: impl FnOnce(String) -> S) {
+        let (listener, url) = bind().await;
+        let server = tokio::spawn(async move {
+            let (tcp, _) = listener.accept().await.unwrap();
+            drop(listener);
+            let mut socket = accept_async(tcp).await.unwrap();
+            let frame = CloseFrame {
+                code: CloseCode::Normal,
+                reason: "".into(),
+            };
+            socket.close(Some(frame)).await.unwrap();
+            while socket.next().await.is_some() {}
+        });
+
+        let given_up = Arc::new(AtomicU32::new(0));
+        let counter = given_up.clone();
+        let mut streaming = build(url);
+        streaming.set_reconnect_policy(ReconnectPolicy {
+            initial_interval: Duration::from_millis(10),
+            max_interval: Duration::from_millis(10),
+            max_attempts: Some(1),
+            on_give_up: Some(Arc::new(move |attempts| {
+                counter.store(attempts, Ordering::SeqCst)
+            })),
+            ..Default::default()
+        });
+        let (stream, _handle) = streaming.stream();
+        let events = timeout(TIMEOUT, stream.collect::<Vec<_>>()).await.unwrap();
+
+        assert_eq!(
+            events.iter().map(describe).collect::<Vec<_>>(),
+            vec![
+                "connected",
+                "disconnected: closed by the server",
+                "reconnecting 1",
+                "disconnected: connection error",
+            ]
+        );
+        assert_eq!(given_up.load(Ordering::SeqCst), 1);
+        server.await.unwrap();
+    }
+
+    /// Closing the handle closes the socket and ends the stream.
+    pub(crate) async fn assert_handle_close<S: Streaming>(build: impl FnOnce(String) -> S) {
+        let (listener, url) = bind().await;
+        let server = tokio::spawn(async move {
+            let (tcp, _) = listener.accept().await.unwrap();
+            let mut socket = accept_async(tcp).await.unwrap();
+            while let Some(Ok(message)) = socket.next().await {
+                if message.is_close() {
+                    return true;
+                }
+            }
+            false
+        });
+
+        let (mut stream, handle) = build(url).stream();
+        let event = timeout(TIMEOUT, stream.next()).await.unwrap();
+        assert!(matches!(event, Some(StreamEvent::Connected)));
+
+        handle.close();
+        assert!(handle.is_closed());
+        let event = timeout(TIMEOUT, stream.next()).await.unwrap();
+        assert!(
+            matches!(event, Some(StreamEvent::Disconnected { reason }) if reason == "closed by the client")
+        );
+        assert!(timeout(TIMEOUT, stream.next()).await.unwrap().is_none());
+        assert!(timeout(TIMEOUT, server).await.unwrap().unwrap());
+    }
+
+    /// Dropping the stream closes the socket at the next message.
+    pub(crate) async fn assert_receiver_dropped<S: Streaming>(build: impl FnOnce(String) -> S) {
+        let (listener, url) = bind().await;
+        let server = tokio::spawn(async move {
+            let (tcp, _) = listener.accept().await.unwrap();
+            let mut socket = accept_async(tcp).await.unwrap();
+            let mut interval = tokio::time::interval(Duration::from_millis(10));
+            loop {
+                tokio::select! {
+                    _ = interval.tick() => {
+                        if socket.send(WebSocketMessage::Ping(Vec::<u8>::new().into())).await.is_err() {
+                            return false;
+                        }
+                    }
+                    message = socket.next() => match message {
+                        Some(Ok(message)) if message.is_close() => return true,
+                        Some(Ok(_)) => {}
+                        _ => return false,
+                    }
+                }
+            }
+        });
+
+        let (mut stream, handle) = build(url).stream();
+        let event = timeout(TIMEOUT, stream.next()).await.unwrap();
+        assert!(matches!(event, Some(StreamEvent::Connected)));
+
+        drop(stream);
+        assert!(timeout(TIMEOUT, server).await.unwrap().unwrap());
+        assert!(handle.is_closed());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_reconnect_policy_default() {
+        let policy = ReconnectPolicy::default();
+
+        assert_eq!(policy.interval(1), Duration::from_millis(5000));
+        assert_eq!(policy.interval(10), Duration::from_millis(5000));
+        assert!(!policy.is_exhausted(100));
+    }
+
+    #[test]
+    fn test_reconnect_policy_exponential() {
+        let policy = ReconnectPolicy {
+            jitter: 0.0,
+            max_attempts: Some(3),
+            ..ReconnectPolicy::exponential(Duration::from_secs(1), Duration::from_secs(5))
+        };
+
+        assert_eq!(policy.interval(1), Duration::from_secs(1));
+        assert_eq!(policy.interval(2), Duration::from_secs(2));
+        assert_eq!(policy.interval(3), Duration::from_secs(4));
+        assert_eq!(policy.interval(4), Duration::from_secs(5));
+        assert!(!policy.is_exhausted(3));
+        assert!(policy.is_exhausted(4));
+    }
+
+    #[test]
+    fn test_reconnect_policy_jitter() {
+        let policy = ReconnectPolicy::exponential(Duration::from_secs(10), Duration::from_secs(60));
+
+        for _ in 0..100 {
+            let interval = policy.interval(1);
+            assert!(interval >= Duration::from_secs(8));
+            assert!(interval <= Duration::from_secs(12));
+        }
+    }
+
+    #[test]
+    fn test_reconnect_policy_invalid_jitter() {
+        for jitter in [f64::NAN, -0.5, f64::NEG_INFINITY] {
+            let policy = ReconnectPolicy {
+                jitter,
+                ..ReconnectPolicy::exponential(Duration::from_secs(1), Duration::from_secs(5))
+            };
+            assert_eq!(policy.interval(2), Duration::from_secs(2));
+        }
+
+        let policy = ReconnectPolicy {
+            jitter: f64::INFINITY,
+            ..ReconnectPolicy::exponential(Duration::from_secs(10), Duration::from_secs(60))
+        };
+        let interval = policy.interval(1);
+        assert!(interval <= Duration::from_secs(20));
+    }
 }
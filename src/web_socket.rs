@@ -0,0 +1,488 @@
+//! WebSocket transport for streaming, which is shared by Mastodon compatible servers
+use std::time::Duration;
+
+use crate::default::DEFAULT_UA;
+use crate::error::{Error, Kind};
+use crate::sse::EventParser;
+use crate::streaming::{
+    event_stream, ConnectionError, Message, MultiplexEventStream, MultiplexHandle,
+    MultiplexStreaming, ReconnectPolicy, Reconnection, StreamEvent, Subscription,
+    SubscriptionCommand, TaggedMessage, EVENT_CHANNEL_CAPACITY,
+};
+use futures_util::{SinkExt, StreamExt};
+use serde::Deserialize;
+use serde_json::json;
+use tokio::net::TcpStream;
+use tokio::sync::mpsc;
+use tokio_tungstenite::{
+    connect_async_tls_with_config,
+    tungstenite::{
+        client::IntoClientRequest,
+        http::StatusCode,
+        protocol::{frame::coding::CloseCode, Message as WebSocketMessage},
+        Error as WebSocketError,
+    },
+    MaybeTlsStream, WebSocketStream,
+};
+use tokio_util::sync::CancellationToken;
+use tracing::{debug, error, warn};
+use url::Url;
+
+const READ_MESSAGE_TIMEOUT_SECONDS: u64 = 60;
+
+pub(crate) type Socket = WebSocketStream<MaybeTlsStream<TcpStream>>;
+
+/// Open a WebSocket connection. Returns `None` when the token is cancelled before connecting.
+pub(crate) async fn open(
+    url: &str,
+    user_agent: &str,
+    token: &CancellationToken,
+) -> Result<Option<Socket>, ConnectionError> {
+    let mut req = Url::parse(url)
+        .unwrap()
+        .into_client_request()
+        .map_err(|e| {
+            error!("Failed to parse url: {}", e);
+            ConnectionError::Connection
+        })?;
+    req.headers_mut()
+        .insert("User-Agent", user_agent.parse().unwrap());
+    let connector = crate::tls::build_connector();
+    let connected = tokio::select! {
+        _ = token.cancelled() => return Ok(None),
+        connected = connect_async_tls_with_config(req, None, false, connector) => connected,
+    };
+    let (socket, response) = connected.map_err(|e| {
+        error!("Failed to connect: {}", e);
+        match e {
+            WebSocketError::Http(response) => match response.status() {
+                StatusCode::UNAUTHORIZED => ConnectionError::Unauthorized,
+                _ => ConnectionError::Connection,
+            },
+            _ => ConnectionError::Connection,
+        }
+    })?;
+
+    debug!("Connected to {}", url);
+    debug!("Response HTTP code: {}", response.status());
+    debug!("Response contains the following headers:");
+    for (ref header, _value) in response.headers() {
+        debug!("* {}", header);
+    }
+    Ok(Some(socket))
+}
+
+/// Receive the next message, replying pong to ping.
+/// Returns `None` when the server closes the connection normally.
+pub(crate) async fn receive(
+    socket: &mut Socket,
+    url: &str,
+) -> Result<Option<WebSocketMessage>, ConnectionError> {
+    let res = tokio::time::timeout(
+        Duration::from_secs(READ_MESSAGE_TIMEOUT_SECONDS),
+        socket.next(),
+    )
+    .await
+    .map_err(|e| {
+        error!("Timeout reading message: {}", e);
+        ConnectionError::Timeout
+    })?;
+    let Some(r) = res else {
+        warn!("WebSocket stream has ended");
+        return Err(ConnectionError::SocketRead);
+    };
+    let msg = r.map_err(|e| {
+        error!("Failed to read message: {}", e);
+        ConnectionError::SocketRead
+    })?;
+    if msg.is_ping() {
+        let _ = socket
+            .send(WebSocketMessage::Pong(Vec::<u8>::new().into()))
+            .await
+            .map_err(|e| {
+                error!("{:#?}", e);
+                e
+            });
+    }
+    if msg.is_close() {
+        close(socket).await;
+        if let WebSocketMessage::Close(Some(close)) = msg {
+            warn!("Connection to {} is closed because {}", url, close.code);
+            if close.code != CloseCode::Normal {
+                return Err(ConnectionError::UnusualSocketClose);
+            }
+        }
+        return Ok(None);
+    }
+    Ok(Some(msg))
+}
+
+pub(crate) async fn close(socket: &mut Socket) {
+    let _ = socket.close(None).await.map_err(|e| {
+        error!("{:#?}", e);
+        e
+    });
+}
+
+/// WebSocket connection which receives several streams with `subscribe` and `unsubscribe` messages.
+#[derive(Debug, Clone)]
+pub(crate) struct MultiplexWebSocket {
+    url: String,
+    access_token: Option<String>,
+    user_agent: String,
+    reconnect_policy: ReconnectPolicy,
+    parse: EventParser,
+}
+
+#[derive(Deserialize)]
+struct RawMessage {
+    stream: Option<Vec<String>>,
+    event: String,
+    #[serde(default)]
+    payload: String,
+}
+
+impl MultiplexWebSocket {
+    pub(crate) fn new(
+        url: String,
+        access_token: Option<String>,
+        user_agent: Option<String>,
+        parse: EventParser,
+    ) -> Self {
+        Self {
+            url,
+            access_token,
+            user_agent: user_agent.unwrap_or(DEFAULT_UA.to_string()),
+            reconnect_policy: ReconnectPolicy::default(),
+            parse,
+        }
+    }
+
+    fn parse(&self, message: WebSocketMessage) -> Result<TaggedMessage, Error> {
+        if message.is_ping() || message.is_pong() {
+            Ok(TaggedMessage {
+                stream: None,
+                message: Message::Heartbeat(),
+            })
+        } else if message.is_text() {
+            let raw = serde_json::from_str::<RawMessage>(message.to_text()?)?;
+            Ok(TaggedMessage {
+                stream: raw.stream.and_then(|stream| parse_subscription(&stream)),
+                message: (self.parse)(&raw.event, raw.payload)?,
+            })
+        } else {
+            Err(Error::new_own(
+                String::from("Receiving message is not ping, pong or text"),
+                Kind::ParseError,
+                None,
+                None,
+                None,
+            ))
+        }
+    }
+
+    async fn connect(
+        &self,
+        url: &str,
+        mut subscriptions: Vec<Subscription>,
+        mut commands: mpsc::UnboundedReceiver<SubscriptionCommand>,
+        tx: mpsc::Sender<StreamEvent<TaggedMessage>>,
+        token: CancellationToken,
+    ) {
+        let mut reconnection = Reconnection::new(url, &self.reconnect_policy, &tx, &token);
+        loop {
+            let result = self
+                .do_connect(
+                    url,
+                    &mut subscriptions,
+                    &mut commands,
+                    &tx,
+                    &token,
+                    &mut reconnection,
+                )
+                .await;
+            if !reconnection.retry(result).await {
+                return;
+            }
+        }
+    }
+
+    async fn do_connect(
+        &self,
+        url: &str,
+        subscriptions: &mut Vec<Subscription>,
+        commands: &mut mpsc::UnboundedReceiver<SubscriptionCommand>,
+        tx: &mpsc::Sender<StreamEvent<TaggedMessage>>,
+        token: &CancellationToken,
+        reconnection: &mut Reconnection<'_, TaggedMessage>,
+    ) -> Result<(), ConnectionError> {
+        let Some(mut socket) = open(url, &self.user_agent, token).await? else {
+            return Ok(());
+        };
+        reconnection.connected().await;
+
+        // Subscriptions are lost with the previous connection, so send all of them again.
+        while let Ok(command) = commands.try_recv() {
+            apply_command(subscriptions, command);
+        }
+        for subscription in subscriptions.iter() {
+            let text = command_message("subscribe", subscription);
+            socket
+                .send(WebSocketMessage::Text(text.into()))
+                .await
+                .map_err(|e| {
+                    error!("Failed to subscribe: {}", e);
+                    ConnectionError::Connection
+                })?;
+        }
+
+        loop {
+            let msg = tokio::select! {
+                _ = token.cancelled() => {
+                    close(&mut socket).await;
+                    return Ok(());
+                }
+                Some(command) = commands.recv() => {
+                    let text = match &command {
+                        SubscriptionCommand::Subscribe(subscription) => {
+                            command_message("subscribe", subscription)
+                        }
+                        SubscriptionCommand::Unsubscribe(subscription) => {
+                            command_message("unsubscribe", subscription)
+                        }
+                    };
+                    apply_command(subscriptions, command);
+                    socket
+                        .send(WebSocketMessage::Text(text.into()))
+                        .await
+                        .map_err(|e| {
+                            error!("Failed to send subscription: {}", e);
+                            ConnectionError::SocketRead
+                        })?;
+                    continue;
+                }
+                msg = receive(&mut socket, url) => msg?,
+            };
+            let Some(msg) = msg else {
+                return Ok(());
+            };
+            match self.parse(msg) {
+                Ok(message) => {
+                    if tx.send(StreamEvent::Message(message)).await.is_err() {
+                        // The receiver has been dropped, so nobody listens this connection.
+                        token.cancel();
+                    }
+                }
+                Err(err) => {
+                    warn!("{}", err);
+                }
+            }
+        }
+    }
+
+    fn streaming_url(&self) -> String {
+        match &self.access_token {
+            Some(access_token) => format!("{}?access_token={}", self.url, access_token),
+            None => self.url.clone(),
+        }
+    }
+}
+
+impl MultiplexStreaming for MultiplexWebSocket {
+    fn stream(&self, subscriptions: Vec<Subscription>) -> (MultiplexEventStream, MultiplexHandle) {
+        let url = self.streaming_url();
+        let (tx, rx) = mpsc::channel(EVENT_CHANNEL_CAPACITY);
+        let (command_tx, command_rx) = mpsc::unbounded_channel();
+        let token = CancellationToken::new();
+        let ws = self.clone();
+        let connection_token = token.clone();
+        let mut initial = Vec::new();
+        for subscription in subscriptions {
+            apply_command(&mut initial, SubscriptionCommand::Subscribe(subscription));
+        }
+        tokio::spawn(async move {
+            ws.connect(url.as_str(), initial, command_rx, tx, connection_token)
+                .await;
+        });
+
+        (event_stream(rx), MultiplexHandle::new(token, command_tx))
+    }
+
+    fn set_reconnect_policy(&mut self, policy: ReconnectPolicy) {
+        self.reconnect_policy = policy;
+    }
+}
+
+fn apply_command(subscriptions: &mut Vec<Subscription>, command: SubscriptionCommand) {
+    match command {
+        SubscriptionCommand::Subscribe(subscription) => {
+            if !subscriptions.contains(&subscription) {
+                subscriptions.push(subscription);
+            }
+        }
+        SubscriptionCommand::Unsubscribe(subscription) => {
+            subscriptions.retain(|s| s != &subscription);
+        }
+    }
+}
+
+fn command_message(r#type: &str, subscription: &Subscription) -> String {
+    let mut data = json!({
+        "type": r#type,
+        "stream": subscription.name(),
+    });
+    match subscription {
+        Subscription::Hashtag(tag) | Subscription::HashtagLocal(tag) => {
+            data["tag"] = json!(tag);
+        }
+        Subscription::List(list_id) => {
+            data["list"] = json!(list_id);
+        }
+        _ => {}
+    }
+    data.to_string()
+}
+
+fn parse_subscription(stream: &[String]) -> Option<Subscription> {
+    match stream {
+        [name] => match name.as_str() {
+            "user" => Some(Subscription::User),
+            "user:notification" => Some(Subscription::UserNotification),
+            "public" => Some(Subscription::Public),
+            "public:media" => Some(Subscription::PublicMedia),
+            "public:local" => Some(Subscription::PublicLocal),
+            "public:local:media" => Some(Subscription::PublicLocalMedia),
+            "public:remote" => Some(Subscription::PublicRemote),
+            "public:remote:media" => Some(Subscription::PublicRemoteMedia),
+            "direct" => Some(Subscription::Direct),
+            _ => None,
+        },
+        [name, param] => match name.as_str() {
+            "hashtag" => Some(Subscription::Hashtag(param.clone())),
+            "hashtag:local" => Some(Subscription::HashtagLocal(param.clone())),
+            "list" => Some(Subscription::List(param.clone())),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tokio::net::TcpListener;
+
+    fn parse_delete(event: &str, payload: String) -> Result<Message, Error> {
+        match event {
+            "delete" => Ok(Message::Delete(payload)),
+            _ => Ok(Message::FiltersChanged),
+        }
+    }
+
+    #[test]
+    fn test_command_message() {
+        let text = command_message("subscribe", &Subscription::Hashtag(String::from("rust")));
+        let data: serde_json::Value = serde_json::from_str(&text).unwrap();
+        assert_eq!(
+            data,
+            json!({"type": "subscribe", "stream": "hashtag", "tag": "rust"})
+        );
+
+        let text = command_message("unsubscribe", &Subscription::PublicLocal);
+        let data: serde_json::Value = serde_json::from_str(&text).unwrap();
+        assert_eq!(
+            data,
+            json!({"type": "unsubscribe", "stream": "public:local"})
+        );
+    }
+
+    #[test]
+    fn test_parse_tagged_message() {
+        let ws =
+            MultiplexWebSocket::new(String::from("wss://example.com"), None, None, parse_delete);
+        let text = json!({
+            "stream": ["list", "12"],
+            "event": "delete",
+            "payload": "109",
+        });
+        let tagged = ws
+            .parse(WebSocketMessage::Text(text.to_string().into()))
+            .unwrap();
+        assert_eq!(tagged.stream, Some(Subscription::List(String::from("12"))));
+        assert!(matches!(tagged.message, Message::Delete(id) if id == "109"));
+
+        let text = json!({"stream": ["user"], "event": "filters_changed"});
+        let tagged = ws
+            .parse(WebSocketMessage::Text(text.to_string().into()))
+            .unwrap();
+        assert_eq!(tagged.stream, Some(Subscription::User));
+        assert!(matches!(tagged.message, Message::FiltersChanged));
+
+        let tagged = ws
+            .parse(WebSocketMessage::Ping(Vec::<u8>::new().into()))
+            .unwrap();
+        assert_eq!(tagged.stream, None);
+    }
+
+    #[test]
+    fn test_apply_command() {
+        let mut subscriptions = vec![Subscription::User];
+        apply_command(
+            &mut subscriptions,
+            SubscriptionCommand::Subscribe(Subscription::User),
+        );
+        apply_command(
+            &mut subscriptions,
+            SubscriptionCommand::Subscribe(Subscription::List(String::from("1"))),
+        );
+        assert_eq!(
+            subscriptions,
+            vec![Subscription::User, Subscription::List(String::from("1"))]
+        );
+        apply_command(
+            &mut subscriptions,
+            SubscriptionCommand::Unsubscribe(Subscription::User),
+        );
+        assert_eq!(subscriptions, vec![Subscription::List(String::from("1"))]);
+    }
+
+    #[tokio::test]
+    async fn test_multiplex_stream() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("ws://{}", listener.local_addr().unwrap());
+        let server = tokio::spawn(async move {
+            let (tcp, _) = listener.accept().await.unwrap();
+            let mut socket = tokio_tungstenite::accept_async(tcp).await.unwrap();
+            let subscribe = socket.next().await.unwrap().unwrap();
+            let text = json!({
+                "stream": ["hashtag", "rust"],
+                "event": "delete",
+                "payload": "109",
+            });
+            socket
+                .send(WebSocketMessage::Text(text.to_string().into()))
+                .await
+                .unwrap();
+            while socket.next().await.is_some() {}
+            serde_json::from_str::<serde_json::Value>(subscribe.to_text().unwrap()).unwrap()
+        });
+
+        let ws = MultiplexWebSocket::new(url, None, None, parse_delete);
+        let (mut stream, handle) = ws.stream(vec![Subscription::Hashtag(String::from("rust"))]);
+        assert!(matches!(stream.next().await, Some(StreamEvent::Connected)));
+        let Some(StreamEvent::Message(tagged)) = stream.next().await else {
+            panic!("message is not received");
+        };
+        assert_eq!(
+            tagged.stream,
+            Some(Subscription::Hashtag(String::from("rust")))
+        );
+        assert!(matches!(tagged.message, Message::Delete(id) if id == "109"));
+
+        handle.close();
+        assert_eq!(
+            server.await.unwrap(),
+            json!({"type": "subscribe", "stream": "hashtag", "tag": "rust"})
+        );
+    }
+}